@@ -0,0 +1,671 @@
+//! Firebird ARRAY values
+//!
+//! The array columns only carry an id in the row data, the elements are
+//! read and written in slices described by a SDL (slice description
+//! language) buffer. The elements are always exchanged in a few simple
+//! types (see [`SliceType`]) and the server converts them from / to the
+//! declared element type of the column.
+
+use crate::{
+    date_time,
+    error::{err_column_null, err_type_conv},
+    ibase, Charset, Column, ColumnToVal, FbError, IntoParam, Numeric, SqlType,
+};
+
+/// Blr codes used to describe the array elements
+mod blr {
+    pub const SHORT: u8 = 7;
+    pub const LONG: u8 = 8;
+    pub const QUAD: u8 = 9;
+    pub const FLOAT: u8 = 10;
+    pub const D_FLOAT: u8 = 11;
+    pub const SQL_DATE: u8 = 12;
+    pub const SQL_TIME: u8 = 13;
+    pub const TEXT: u8 = 14;
+    pub const INT64: u8 = 16;
+    pub const BOOL: u8 = 23;
+    pub const DOUBLE: u8 = 27;
+    pub const TIMESTAMP: u8 = 35;
+    pub const VARYING: u8 = 37;
+    pub const CSTRING: u8 = 40;
+}
+
+/// Maximum number of dimensions of a firebird array
+const MAX_DIMENSIONS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Lower and upper bounds (both inclusive) of an array dimension
+pub struct ArrayBound {
+    pub lower: i32,
+    pub upper: i32,
+}
+
+impl ArrayBound {
+    pub fn new(lower: i32, upper: i32) -> Self {
+        Self { lower, upper }
+    }
+
+    /// Number of elements in the dimension
+    pub fn len(&self) -> usize {
+        (self.upper as i64 - self.lower as i64 + 1).max(0) as usize
+    }
+
+    /// Returns `true` if the dimension has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Number of elements in an array with the specified bounds
+fn element_count(bounds: &[ArrayBound]) -> usize {
+    bounds.iter().map(ArrayBound::len).product()
+}
+
+#[derive(Debug, Clone)]
+/// Array column / parameter value
+pub struct SqlArray {
+    /// Sql type code of the elements, like `ibase::SQL_LONG`.
+    ///
+    /// Ignored in the parameters, the type of the column is used instead
+    pub element_type: u32,
+
+    /// Bounds of each dimension. In the parameters it can be left empty
+    /// to use the bounds of the column
+    pub bounds: Vec<ArrayBound>,
+
+    /// Elements in row major order (the last dimension changes faster)
+    pub elements: Vec<SqlType>,
+}
+
+impl SqlArray {
+    /// Create an array parameter using the bounds of the column.
+    ///
+    /// For one dimensional columns the array can have less elements than
+    /// the column, and only the first elements are written
+    pub fn new(elements: Vec<SqlType>) -> Self {
+        Self {
+            element_type: 0,
+            bounds: vec![],
+            elements,
+        }
+    }
+
+    /// Create an array parameter from a list of values
+    pub fn from_values<T, I>(values: I) -> Self
+    where
+        T: IntoParam,
+        I: IntoIterator<Item = T>,
+    {
+        Self::new(values.into_iter().map(IntoParam::into_param).collect())
+    }
+
+    /// Create an array parameter for a slice of the column, with the
+    /// specified bounds
+    pub fn with_bounds(bounds: Vec<ArrayBound>, elements: Vec<SqlType>) -> Result<Self, FbError> {
+        if bounds.is_empty() || bounds.len() > MAX_DIMENSIONS {
+            return Err(format!(
+                "An array must have between 1 and {} dimensions, found {}",
+                MAX_DIMENSIONS,
+                bounds.len()
+            )
+            .into());
+        }
+
+        let count = element_count(&bounds);
+        if count != elements.len() {
+            return Err(format!(
+                "The array bounds specify {} elements, but {} were provided",
+                count,
+                elements.len()
+            )
+            .into());
+        }
+
+        Ok(Self {
+            element_type: 0,
+            bounds,
+            elements,
+        })
+    }
+
+    /// Number of dimensions of the array
+    pub fn dimensions(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Convert the elements to a rust type
+    pub fn to_vec<T>(&self) -> Result<Vec<T>, FbError>
+    where
+        Column: ColumnToVal<T>,
+    {
+        self.elements
+            .iter()
+            .map(|el| Column::new(String::new(), self.element_type, el.clone()).to_val())
+            .collect()
+    }
+}
+
+impl IntoParam for SqlArray {
+    fn into_param(self) -> SqlType {
+        SqlType::Array(self)
+    }
+}
+
+impl ColumnToVal<SqlArray> for Column {
+    fn to_val(self) -> Result<SqlArray, FbError> {
+        match self.value {
+            SqlType::Array(a) => Ok(a),
+
+            SqlType::Null => Err(err_column_null("SqlArray")),
+
+            col => err_type_conv(col, "SqlArray"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Type of the elements exchanged in the array slices
+pub enum SliceType {
+    /// 64 bits integer, with the scale of the column
+    Int64 {
+        scale: i8,
+    },
+    /// Double precision float
+    Double,
+    /// Text with the maximum length in bytes (without the length prefix)
+    Varying {
+        length: u16,
+    },
+    Timestamp,
+    Date,
+    Time,
+    Boolean,
+}
+
+impl SliceType {
+    /// Size of each element in the slice buffer, in the native (fbclient) layout
+    pub fn size(self) -> usize {
+        match self {
+            SliceType::Int64 { .. } | SliceType::Double | SliceType::Timestamp => 8,
+            SliceType::Varying { length } => length as usize + 2,
+            SliceType::Date | SliceType::Time => 4,
+            SliceType::Boolean => 1,
+        }
+    }
+
+    /// Decode one element from the native layout
+    pub fn decode(self, data: &[u8], charset: &Charset) -> Result<SqlType, FbError> {
+        if data.len() < self.size() {
+            return Err("Invalid array slice, missing bytes".into());
+        }
+
+        let i32_at =
+            |i: usize| i32::from_ne_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        let u64_at = || {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(&data[..8]);
+            u64::from_ne_bytes(bytes)
+        };
+
+        Ok(match self {
+            SliceType::Int64 { scale: 0 } => SqlType::Integer(u64_at() as i64),
+
+            SliceType::Int64 { scale } => {
+                SqlType::Decimal(Numeric::new(u64_at() as i64 as i128, scale as i16))
+            }
+
+            SliceType::Double => SqlType::Floating(f64::from_bits(u64_at())),
+
+            SliceType::Varying { length } => {
+                let len = u16::from_ne_bytes([data[0], data[1]]).min(length) as usize;
+
+                SqlType::Text(charset.decode(&data[2..2 + len])?)
+            }
+
+            SliceType::Timestamp => {
+                SqlType::Timestamp(date_time::decode_timestamp(ibase::ISC_TIMESTAMP {
+                    timestamp_date: i32_at(0),
+                    timestamp_time: i32_at(4) as u32,
+                }))
+            }
+
+            SliceType::Date => SqlType::Date(date_time::decode_date(i32_at(0))),
+
+            SliceType::Time => SqlType::Time(date_time::decode_time(i32_at(0) as u32)),
+
+            SliceType::Boolean => SqlType::Boolean(data[0] != 0),
+        })
+    }
+
+    /// Encode one element in the native layout, appending to `out`
+    pub fn encode(
+        self,
+        value: &SqlType,
+        charset: &Charset,
+        out: &mut Vec<u8>,
+    ) -> Result<(), FbError> {
+        let err = || err_type_conv(value.clone(), &format!("{:?} array element", self));
+
+        match (self, value) {
+            (_, SqlType::Null) => return Err("Array elements can't be null".into()),
+
+            (SliceType::Int64 { scale }, value) => {
+                let num = match value {
+                    SqlType::Integer(i) => Numeric::new(*i as i128, 0),
+                    SqlType::Int128(i) => Numeric::new(*i, 0),
+                    SqlType::Decimal(d) => *d,
+                    _ => return err(),
+                };
+
+                let value = i64::try_from(num.rescale(scale as i16)?.value).map_err(|_| {
+                    FbError::from(format!("The value {} is too large for the array", num))
+                })?;
+
+                out.extend_from_slice(&value.to_ne_bytes());
+            }
+
+            (SliceType::Double, value) => {
+                let f = match value {
+                    SqlType::Floating(f) => *f,
+                    SqlType::Integer(i) => *i as f64,
+                    SqlType::Int128(i) => *i as f64,
+                    SqlType::Decimal(d) => d.to_f64(),
+                    _ => return err(),
+                };
+
+                out.extend_from_slice(&f.to_ne_bytes());
+            }
+
+            (SliceType::Varying { length }, SqlType::Text(s)) => {
+                let bytes = charset.encode(s)?;
+                if bytes.len() > length as usize {
+                    return Err(format!(
+                        "The text '{}' is too large for the array, the maximum is {} bytes",
+                        s, length
+                    )
+                    .into());
+                }
+
+                out.extend_from_slice(&(bytes.len() as u16).to_ne_bytes());
+                out.extend_from_slice(&bytes);
+                out.resize(out.len() + length as usize - bytes.len(), 0);
+            }
+
+            (SliceType::Timestamp, SqlType::Timestamp(dt)) => {
+                let ts = date_time::encode_timestamp(*dt);
+
+                out.extend_from_slice(&ts.timestamp_date.to_ne_bytes());
+                out.extend_from_slice(&ts.timestamp_time.to_ne_bytes());
+            }
+
+            (SliceType::Timestamp, SqlType::Date(d)) => {
+                let ts = date_time::encode_timestamp(d.and_time(chrono::NaiveTime::MIN));
+
+                out.extend_from_slice(&ts.timestamp_date.to_ne_bytes());
+                out.extend_from_slice(&ts.timestamp_time.to_ne_bytes());
+            }
+
+            (SliceType::Date, SqlType::Date(d)) => {
+                out.extend_from_slice(&date_time::encode_date(*d).to_ne_bytes())
+            }
+
+            (SliceType::Date, SqlType::Timestamp(dt)) => {
+                out.extend_from_slice(&date_time::encode_date(dt.date()).to_ne_bytes())
+            }
+
+            (SliceType::Time, SqlType::Time(t)) => {
+                out.extend_from_slice(&date_time::encode_time(*t).to_ne_bytes())
+            }
+
+            (SliceType::Time, SqlType::Timestamp(dt)) => {
+                out.extend_from_slice(&date_time::encode_time(dt.time()).to_ne_bytes())
+            }
+
+            (SliceType::Boolean, SqlType::Boolean(b)) => out.push(*b as u8),
+
+            _ => return err(),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Description of an array column, needed to read and write the slices
+pub struct ArrayDesc {
+    /// Blr type code of the elements
+    pub dtype: u8,
+
+    /// Scale of the integer elements
+    pub scale: i8,
+
+    /// Length in bytes of the text elements
+    pub length: u16,
+
+    /// Table of the column
+    pub relation: String,
+
+    /// Column name
+    pub field: String,
+
+    /// Bounds of each dimension
+    pub bounds: Vec<ArrayBound>,
+}
+
+impl ArrayDesc {
+    /// Query returning the element type and the bounds of an array column,
+    /// with the table and column names as parameters.
+    ///
+    /// Used by the clients without access to the `isc_array_lookup_bounds`
+    pub const LOOKUP_SQL: &'static str = "SELECT F.RDB$FIELD_TYPE, F.RDB$FIELD_SCALE, F.RDB$FIELD_LENGTH, D.RDB$LOWER_BOUND, D.RDB$UPPER_BOUND \
+        FROM RDB$RELATION_FIELDS RF \
+        JOIN RDB$FIELDS F ON F.RDB$FIELD_NAME = RF.RDB$FIELD_SOURCE \
+        JOIN RDB$FIELD_DIMENSIONS D ON D.RDB$FIELD_NAME = F.RDB$FIELD_NAME \
+        WHERE RF.RDB$RELATION_NAME = ? AND RF.RDB$FIELD_NAME = ? \
+        ORDER BY D.RDB$DIMENSION";
+
+    /// Build the descriptor from the rows returned by the `LOOKUP_SQL`
+    pub fn from_lookup(
+        relation: &str,
+        field: &str,
+        rows: Vec<Vec<Column>>,
+    ) -> Result<Self, FbError> {
+        let mut desc = ArrayDesc {
+            dtype: 0,
+            scale: 0,
+            length: 0,
+            relation: relation.to_string(),
+            field: field.to_string(),
+            bounds: Vec::with_capacity(rows.len()),
+        };
+
+        for row in rows {
+            let mut cols = row.into_iter();
+            let mut next = || -> Result<i64, FbError> {
+                cols.next()
+                    .ok_or_else(|| FbError::from("Invalid array descriptor lookup"))?
+                    .to_val()
+            };
+
+            desc.dtype = next()? as u8;
+            desc.scale = next()? as i8;
+            desc.length = next()? as u16;
+            desc.bounds
+                .push(ArrayBound::new(next()? as i32, next()? as i32));
+        }
+
+        if desc.bounds.is_empty() {
+            return Err(format!("Array column {}.{} not found", relation, field).into());
+        }
+
+        Ok(desc)
+    }
+
+    /// Sql type code of the elements
+    pub fn element_type(&self) -> u32 {
+        match self.dtype {
+            blr::SHORT => ibase::SQL_SHORT,
+            blr::LONG => ibase::SQL_LONG,
+            blr::QUAD => ibase::SQL_QUAD,
+            blr::INT64 => ibase::SQL_INT64,
+            blr::FLOAT => ibase::SQL_FLOAT,
+            blr::D_FLOAT => ibase::SQL_D_FLOAT,
+            blr::DOUBLE => ibase::SQL_DOUBLE,
+            blr::TEXT | blr::CSTRING => ibase::SQL_TEXT,
+            blr::VARYING => ibase::SQL_VARYING,
+            blr::TIMESTAMP => ibase::SQL_TIMESTAMP,
+            blr::SQL_DATE => ibase::SQL_TYPE_DATE,
+            blr::SQL_TIME => ibase::SQL_TYPE_TIME,
+            blr::BOOL => ibase::SQL_BOOLEAN,
+            _ => 0,
+        }
+    }
+
+    /// Type used for the elements in the slices
+    pub fn slice_type(&self) -> Result<SliceType, FbError> {
+        Ok(match self.dtype {
+            blr::SHORT | blr::LONG | blr::INT64 | blr::QUAD => {
+                SliceType::Int64 { scale: self.scale }
+            }
+            blr::FLOAT | blr::D_FLOAT | blr::DOUBLE => SliceType::Double,
+            blr::TEXT | blr::VARYING | blr::CSTRING => SliceType::Varying {
+                length: self.length,
+            },
+            blr::TIMESTAMP => SliceType::Timestamp,
+            blr::SQL_DATE => SliceType::Date,
+            blr::SQL_TIME => SliceType::Time,
+            blr::BOOL => SliceType::Boolean,
+            dtype => {
+                return Err(format!(
+                    "Unsupported array element type ({}) in {}.{}",
+                    dtype, self.relation, self.field
+                )
+                .into())
+            }
+        })
+    }
+
+    /// Size in bytes of a slice with the specified bounds, in the native layout
+    pub fn slice_length(&self, bounds: &[ArrayBound]) -> Result<usize, FbError> {
+        Ok(element_count(bounds) * self.slice_type()?.size())
+    }
+
+    /// Build the SDL describing a slice with the specified bounds
+    pub fn sdl(&self, bounds: &[ArrayBound]) -> Result<Vec<u8>, FbError> {
+        if bounds.len() != self.bounds.len() {
+            return Err(format!(
+                "The array column {}.{} has {} dimensions, but the slice has {}",
+                self.relation,
+                self.field,
+                self.bounds.len(),
+                bounds.len()
+            )
+            .into());
+        }
+
+        let mut sdl = Vec::with_capacity(64);
+
+        sdl.extend_from_slice(&[
+            ibase::isc_sdl_version1 as u8,
+            ibase::isc_sdl_struct as u8,
+            1, // Element count
+        ]);
+
+        match self.slice_type()? {
+            SliceType::Int64 { scale } => sdl.extend_from_slice(&[blr::INT64, scale as u8]),
+            SliceType::Double => sdl.push(blr::DOUBLE),
+            SliceType::Varying { length } => {
+                sdl.push(blr::VARYING);
+                sdl.extend_from_slice(&length.to_le_bytes());
+            }
+            SliceType::Timestamp => sdl.push(blr::TIMESTAMP),
+            SliceType::Date => sdl.push(blr::SQL_DATE),
+            SliceType::Time => sdl.push(blr::SQL_TIME),
+            SliceType::Boolean => sdl.push(blr::BOOL),
+        }
+
+        sdl_name(&mut sdl, ibase::isc_sdl_relation as u8, &self.relation)?;
+        sdl_name(&mut sdl, ibase::isc_sdl_field as u8, &self.field)?;
+
+        // One loop for each dimension
+        for (var, bound) in bounds.iter().enumerate() {
+            if bound.lower == 1 {
+                sdl.extend_from_slice(&[ibase::isc_sdl_do1 as u8, var as u8]);
+            } else {
+                sdl.extend_from_slice(&[ibase::isc_sdl_do2 as u8, var as u8]);
+                sdl_literal(&mut sdl, bound.lower);
+            }
+            sdl_literal(&mut sdl, bound.upper);
+        }
+
+        // Element indexed by the loop variables
+        sdl.extend_from_slice(&[
+            ibase::isc_sdl_element as u8,
+            1,
+            ibase::isc_sdl_scalar as u8,
+            0,
+            bounds.len() as u8,
+        ]);
+        for var in 0..bounds.len() {
+            sdl.extend_from_slice(&[ibase::isc_sdl_variable as u8, var as u8]);
+        }
+
+        sdl.push(ibase::isc_sdl_eoc as u8);
+
+        Ok(sdl)
+    }
+
+    /// Convert the slice data (native layout) to an array value
+    pub fn decode_slice(
+        &self,
+        bounds: Vec<ArrayBound>,
+        data: &[u8],
+        charset: &Charset,
+    ) -> Result<SqlArray, FbError> {
+        let slice_type = self.slice_type()?;
+
+        let elements = data
+            .chunks_exact(slice_type.size())
+            .map(|el| slice_type.decode(el, charset))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SqlArray {
+            element_type: self.element_type(),
+            bounds,
+            elements,
+        })
+    }
+
+    /// Convert an array value to the slice data (native layout),
+    /// returning the bounds of the slice and the data
+    pub fn encode_slice(
+        &self,
+        array: &SqlArray,
+        charset: &Charset,
+    ) -> Result<(Vec<ArrayBound>, Vec<u8>), FbError> {
+        let slice_type = self.slice_type()?;
+
+        let bounds = match &self.bounds[..] {
+            // One dimension, write only the elements provided
+            [bound] if array.bounds.is_empty() && array.elements.len() <= bound.len() => {
+                vec![ArrayBound::new(
+                    bound.lower,
+                    bound.lower + array.elements.len() as i32 - 1,
+                )]
+            }
+            _ if array.bounds.is_empty() => self.bounds.clone(),
+            _ => array.bounds.clone(),
+        };
+
+        let count = element_count(&bounds);
+        if count != array.elements.len() {
+            return Err(format!(
+                "The array column {}.{} expects {} elements, but {} were provided",
+                self.relation,
+                self.field,
+                count,
+                array.elements.len()
+            )
+            .into());
+        }
+
+        let mut data = Vec::with_capacity(count * slice_type.size());
+        for el in &array.elements {
+            slice_type.encode(el, charset, &mut data)?;
+        }
+
+        Ok((bounds, data))
+    }
+}
+
+/// Append a relation or field name to the sdl
+fn sdl_name(sdl: &mut Vec<u8>, kind: u8, name: &str) -> Result<(), FbError> {
+    let len = u8::try_from(name.len())
+        .map_err(|_| FbError::from(format!("The name {} is too long", name)))?;
+
+    sdl.extend_from_slice(&[kind, len]);
+    sdl.extend_from_slice(name.as_bytes());
+
+    Ok(())
+}
+
+/// Append an integer literal to the sdl, using the smallest representation
+fn sdl_literal(sdl: &mut Vec<u8>, value: i32) {
+    if let Ok(v) = i8::try_from(value) {
+        sdl.extend_from_slice(&[ibase::isc_sdl_tiny_integer as u8, v as u8]);
+    } else if let Ok(v) = i16::try_from(value) {
+        sdl.push(ibase::isc_sdl_short_integer as u8);
+        sdl.extend_from_slice(&v.to_le_bytes());
+    } else {
+        sdl.push(ibase::isc_sdl_long_integer as u8);
+        sdl.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+#[test]
+fn array_sdl() {
+    let desc = ArrayDesc {
+        dtype: blr::LONG,
+        scale: 0,
+        length: 4,
+        relation: "T".to_string(),
+        field: "ARR".to_string(),
+        bounds: vec![ArrayBound::new(1, 3), ArrayBound::new(0, 300)],
+    };
+
+    assert_eq!(
+        desc.sdl(&desc.bounds).unwrap(),
+        vec![
+            1, 6, 1, 16, 0, // Version, struct with a int64 element
+            2, 1, b'T', // Relation
+            4, 3, b'A', b'R', b'R', // Field
+            35, 0, 9, 3, // do1 with upper bound 3
+            34, 1, 9, 0, 10, 44, 1, // do2 with lower bound 0 and upper bound 300
+            36, 1, 8, 0, 2, 7, 0, 7, 1, // Element with the 2 loop variables
+            255
+        ]
+    );
+    assert_eq!(desc.slice_length(&desc.bounds).unwrap(), 3 * 301 * 8);
+}
+
+#[test]
+fn array_slice_encoding() {
+    let charset = crate::charset::UTF_8;
+    let desc = ArrayDesc {
+        dtype: blr::VARYING,
+        scale: 0,
+        length: 5,
+        relation: "T".to_string(),
+        field: "ARR".to_string(),
+        bounds: vec![ArrayBound::new(1, 4)],
+    };
+
+    let array = SqlArray::from_values(vec!["a", "bc", "def"]);
+    let (bounds, data) = desc.encode_slice(&array, &charset).unwrap();
+
+    assert_eq!(bounds, vec![ArrayBound::new(1, 3)]);
+    assert_eq!(data.len(), 3 * 7);
+
+    let decoded = desc.decode_slice(bounds.clone(), &data, &charset).unwrap();
+    assert_eq!(decoded.to_vec::<String>().unwrap(), vec!["a", "bc", "def"]);
+
+    let too_many = SqlArray::from_values(vec!["a", "b", "c", "d", "e"]);
+    assert!(desc.encode_slice(&too_many, &charset).is_err());
+
+    let too_long = SqlArray::from_values(vec!["abcdef"]);
+    assert!(desc.encode_slice(&too_long, &charset).is_err());
+
+    let desc = ArrayDesc {
+        dtype: blr::SHORT,
+        scale: -2,
+        ..desc
+    };
+    let array = SqlArray::from_values(vec![Numeric::new(125, -2), Numeric::new(3, 0)]);
+    let (bounds, data) = desc.encode_slice(&array, &charset).unwrap();
+    let decoded = desc.decode_slice(bounds, &data, &charset).unwrap();
+
+    assert_eq!(
+        decoded.to_vec::<Numeric>().unwrap(),
+        vec![Numeric::new(125, -2), Numeric::new(300, -2)]
+    );
+}
@@ -0,0 +1,184 @@
+//! Blob ids and types used to stream the blob contents
+
+use crate::{
+    error::{err_column_null, err_type_conv},
+    ibase, Charset, Column, ColumnToVal, FbError, IntoParam, SqlType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Identifies a blob in the database, as stored in the blob columns.
+///
+/// Can be sent as a parameter to store an already written blob
+pub struct BlobId(pub u64);
+
+impl From<ibase::ISC_QUAD> for BlobId {
+    fn from(quad: ibase::ISC_QUAD) -> Self {
+        BlobId(((quad.gds_quad_high as u32 as u64) << 32) | quad.gds_quad_low as u64)
+    }
+}
+
+impl From<BlobId> for ibase::ISC_QUAD {
+    fn from(id: BlobId) -> Self {
+        ibase::ISC_QUAD {
+            gds_quad_high: (id.0 >> 32) as u32 as _,
+            gds_quad_low: id.0 as u32,
+        }
+    }
+}
+
+impl IntoParam for BlobId {
+    fn into_param(self) -> SqlType {
+        SqlType::Blob(self)
+    }
+}
+
+impl ColumnToVal<BlobId> for Column {
+    fn to_val(self) -> Result<BlobId, FbError> {
+        match self.value {
+            SqlType::Blob(id) => Ok(id),
+
+            SqlType::LazyBlob(blob) => Ok(blob.id),
+
+            SqlType::Null => Err(err_column_null("BlobId")),
+
+            col => err_type_conv(col, "BlobId"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Blob column fetched without the data, used when the lazy blobs
+/// are enabled in the connection.
+///
+/// The data is read with `Transaction::read_blob`
+pub struct BlobRef {
+    /// Blob id
+    pub id: BlobId,
+    /// Charset to decode the data, if it is a text blob
+    pub charset: Option<Charset>,
+}
+
+impl BlobRef {
+    /// Value of the blob with the data read, as `Text` or `Binary`
+    pub fn decode(self, data: Vec<u8>) -> Result<SqlType, FbError> {
+        Ok(match self.charset {
+            Some(charset) => SqlType::Text(charset.decode(data)?),
+            None => SqlType::Binary(data),
+        })
+    }
+}
+
+impl ColumnToVal<BlobRef> for Column {
+    fn to_val(self) -> Result<BlobRef, FbError> {
+        match self.value {
+            SqlType::LazyBlob(blob) => Ok(blob),
+
+            SqlType::Null => Err(err_column_null("BlobRef")),
+
+            col => err_type_conv(col, "BlobRef"),
+        }
+    }
+}
+
+#[repr(i16)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+/// Reference for the offset of a blob seek
+pub enum BlobSeekMode {
+    /// From the start of the blob
+    Start = 0,
+    /// From the current read position
+    Current = 1,
+    /// From the end of the blob
+    End = 2,
+}
+
+/// Blob parameter buffer used to create the stream blobs, as only
+/// these can be read with seeks
+pub const STREAM_BLOB_BPB: [u8; 4] = [
+    ibase::isc_bpb_version1 as u8,
+    ibase::isc_bpb_type as u8,
+    1,
+    ibase::isc_bpb_type_stream as u8,
+];
+
+/// Items requested to get the blob length
+pub const BLOB_LENGTH_INFO: [u8; 1] = [ibase::isc_info_blob_total_length as u8];
+
+/// Parse the response of a blob info request with the `BLOB_LENGTH_INFO`
+pub fn parse_blob_length(mut info: &[u8]) -> Result<u64, FbError> {
+    let err = || FbError::from("Invalid blob info response");
+
+    while let [item, rest @ ..] = info {
+        if *item == ibase::isc_info_end as u8 {
+            break;
+        }
+
+        if rest.len() < 2 {
+            return Err(err());
+        }
+        let len = u16::from_le_bytes([rest[0], rest[1]]) as usize;
+        let value = rest.get(2..2 + len).ok_or_else(err)?;
+
+        if *item == ibase::isc_info_blob_total_length as u8 {
+            // Little endian integer with `len` bytes
+            return Ok(value.iter().rev().fold(0, |acc, b| (acc << 8) | *b as u64));
+        }
+
+        info = &rest[2 + len..];
+    }
+
+    Err(err())
+}
+
+#[test]
+fn blob_id_quad() {
+    let quad = ibase::ISC_QUAD {
+        gds_quad_high: 0x81,
+        gds_quad_low: 0xF000_0001,
+    };
+
+    let id = BlobId::from(quad);
+    assert_eq!(BlobId(0x0000_0081_F000_0001), id);
+
+    let quad = ibase::ISC_QUAD::from(id);
+    assert_eq!(0x81, quad.gds_quad_high);
+    assert_eq!(0xF000_0001, quad.gds_quad_low);
+}
+
+#[test]
+fn lazy_blob_ref() {
+    let col = Column::new(
+        "TXT".to_string(),
+        ibase::SQL_BLOB,
+        SqlType::LazyBlob(BlobRef {
+            id: BlobId(10),
+            charset: Some(crate::charset::UTF_8),
+        }),
+    );
+
+    // Only loaded explicitly
+    assert!(ColumnToVal::<String>::to_val(col.clone()).is_err());
+    assert_eq!(
+        BlobId(10),
+        ColumnToVal::<BlobId>::to_val(col.clone()).unwrap()
+    );
+
+    let blob: BlobRef = col.to_val().unwrap();
+    let val = blob.decode("abc".as_bytes().to_vec()).unwrap();
+    assert!(matches!(val, SqlType::Text(s) if s == "abc"));
+
+    let blob = BlobRef {
+        id: BlobId(11),
+        charset: None,
+    };
+    assert!(matches!(blob.decode(vec![1, 2]).unwrap(), SqlType::Binary(b) if b == [1, 2]));
+}
+
+#[test]
+fn blob_length_info() {
+    assert_eq!(
+        70000,
+        parse_blob_length(&[6, 4, 0, 0x70, 0x11, 0x01, 0x00, 1]).unwrap()
+    );
+    assert!(parse_blob_length(&[1]).is_err());
+}
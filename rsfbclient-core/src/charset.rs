@@ -18,6 +18,38 @@ pub struct Charset {
 }
 
 impl Charset {
+    /// Find the charset from the id used by firebird.
+    ///
+    /// Returns `None` for `NONE`, `OCTETS` and the unsupported charsets
+    pub fn from_id(id: u8) -> Option<Self> {
+        Some(match id {
+            2 => ASCII,
+            // UNICODE_FSS, compatible with utf-8
+            3 | 4 => UTF_8,
+            6 => EUC_JP,
+            21 => ISO_8859_1,
+            22 => ISO_8859_2,
+            23 => ISO_8859_3,
+            34 => ISO_8859_4,
+            35 => ISO_8859_5,
+            36 => ISO_8859_6,
+            37 => ISO_8859_7,
+            40 => ISO_8859_13,
+            51 => WIN_1250,
+            52 => WIN_1251,
+            53 => WIN_1252,
+            54 => WIN_1253,
+            55 => WIN_1254,
+            56 => BIG5_2003,
+            59 => WIN_1256,
+            60 => WIN_1257,
+            63 => KOI8_R,
+            64 => KOI8_U,
+            65 => WIN_1258,
+            _ => return None,
+        })
+    }
+
     /// Decode the bytes using the current charset
     pub fn decode<'a, B>(&self, bytes: B) -> Result<String, FbError>
     where
@@ -123,6 +155,9 @@ impl PartialEq for Charset {
     }
 }
 
+/// Id of the `OCTETS` charset, used by the text columns with binary data
+pub const OCTETS_ID: u8 = 1;
+
 /// The default charset. Works in most cases
 pub const UTF_8: Charset = Charset {
     on_firebird: "UTF8",
@@ -0,0 +1,252 @@
+//! Client side conversion of the parameters to the types
+//! described by the server for the statement inputs
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::{date_time, ibase, ColumnMeta, FbError, Numeric, SqlType};
+
+/// Convert a parameter to the type described by the server, so it doesn't
+/// need to be cast in the server. The values without a conversion to the
+/// described type are returned unchanged, leaving the cast to the server.
+///
+/// The `index` (starting from 0) is used only in the error messages
+pub fn coerce_param(index: usize, param: SqlType, meta: &ColumnMeta) -> Result<SqlType, FbError> {
+    coerce(param, meta).map_err(|e| match e {
+        FbError::Other(msg) => FbError::Other(format!(
+            "Error converting the parameter {} to {}: {}",
+            index + 1,
+            type_name(meta),
+            msg
+        )),
+        e => e,
+    })
+}
+
+fn coerce(param: SqlType, meta: &ColumnMeta) -> Result<SqlType, FbError> {
+    // Sent as text, as only the server knows the id of the region
+    if let SqlType::TimestampTz(dt, Some(region)) = &param {
+        return Ok(SqlType::Text(date_time::timestamp_tz_text(dt, region)));
+    }
+
+    match meta.sql_type {
+        ibase::SQL_SHORT | ibase::SQL_LONG | ibase::SQL_INT64 | ibase::SQL_INT128 => {
+            let num = match param {
+                SqlType::Integer(i) => Numeric::new(i as i128, 0),
+                SqlType::Int128(i) => Numeric::new(i, 0),
+                SqlType::Decimal(n) => n,
+                SqlType::Boolean(b) => Numeric::new(b as i128, 0),
+                SqlType::Text(s) => s.parse()?,
+                param => return Ok(param),
+            };
+
+            let num = match num.rescale(meta.scale) {
+                Ok(num) => num,
+                // More decimal places than the column, so the server rounds it
+                Err(_) if num.scale < meta.scale => return Ok(SqlType::Decimal(num)),
+                Err(e) => return Err(e),
+            };
+
+            let fits = match meta.sql_type {
+                ibase::SQL_SHORT => i16::try_from(num.value).is_ok(),
+                ibase::SQL_LONG => i32::try_from(num.value).is_ok(),
+                ibase::SQL_INT64 => i64::try_from(num.value).is_ok(),
+                _ => true,
+            };
+            if !fits {
+                return Err(format!("The value {} is out of range", num).into());
+            }
+
+            Ok(match (meta.scale, meta.sql_type) {
+                (0, ibase::SQL_INT128) => SqlType::Int128(num.value),
+                (0, _) => SqlType::Integer(num.value as i64),
+                _ => SqlType::Decimal(num),
+            })
+        }
+
+        ibase::SQL_FLOAT | ibase::SQL_DOUBLE | ibase::SQL_D_FLOAT => Ok(match param {
+            SqlType::Integer(i) => SqlType::Floating(i as f64),
+            SqlType::Int128(i) => SqlType::Floating(i as f64),
+            SqlType::Decimal(n) => SqlType::Floating(n.to_f64()),
+            SqlType::Text(s) => SqlType::Floating(
+                s.trim()
+                    .parse()
+                    .map_err(|_| format!("'{}' doesn't represent a floating point value", s))?,
+            ),
+            param => param,
+        }),
+
+        ibase::SQL_BOOLEAN => Ok(match param {
+            SqlType::Integer(0) => SqlType::Boolean(false),
+            SqlType::Integer(1) => SqlType::Boolean(true),
+            SqlType::Integer(i) => {
+                return Err(format!("The value {} is not a boolean (0 or 1)", i).into())
+            }
+            SqlType::Text(s) if s.trim().eq_ignore_ascii_case("true") => SqlType::Boolean(true),
+            SqlType::Text(s) if s.trim().eq_ignore_ascii_case("false") => SqlType::Boolean(false),
+            param => param,
+        }),
+
+        // The text not in the ISO formats is sent as is, to allow
+        // the firebird literals like 'TODAY' and 'dd.mm.yyyy'
+        ibase::SQL_TYPE_DATE => Ok(match param {
+            SqlType::Text(s) => parse_date(&s)
+                .map(SqlType::Date)
+                .unwrap_or(SqlType::Text(s)),
+            param => param,
+        }),
+
+        ibase::SQL_TYPE_TIME => Ok(match param {
+            SqlType::Text(s) => parse_time(&s)
+                .map(SqlType::Time)
+                .unwrap_or(SqlType::Text(s)),
+            param => param,
+        }),
+
+        ibase::SQL_TIMESTAMP => Ok(match param {
+            SqlType::Text(s) => parse_timestamp(&s)
+                .map(SqlType::Timestamp)
+                .unwrap_or(SqlType::Text(s)),
+            SqlType::Date(d) => SqlType::Timestamp(d.and_time(NaiveTime::MIN)),
+            param => param,
+        }),
+
+        _ => Ok(param),
+    }
+}
+
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
+}
+
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    let s = s.trim();
+
+    NaiveTime::parse_from_str(s, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(s, "%H:%M"))
+        .ok()
+}
+
+fn parse_timestamp(s: &str) -> Option<NaiveDateTime> {
+    let s = s.trim();
+
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f"))
+        .ok()
+        .or_else(|| parse_date(s).map(|d| d.and_time(NaiveTime::MIN)))
+}
+
+/// Name of the described type, for the error messages
+fn type_name(meta: &ColumnMeta) -> &'static str {
+    match meta.sql_type {
+        ibase::SQL_SHORT | ibase::SQL_LONG | ibase::SQL_INT64 | ibase::SQL_INT128
+            if meta.scale < 0 =>
+        {
+            "NUMERIC"
+        }
+        ibase::SQL_SHORT => "SMALLINT",
+        ibase::SQL_LONG => "INTEGER",
+        ibase::SQL_INT64 => "BIGINT",
+        ibase::SQL_INT128 => "INT128",
+        ibase::SQL_FLOAT | ibase::SQL_D_FLOAT => "FLOAT",
+        ibase::SQL_DOUBLE => "DOUBLE PRECISION",
+        ibase::SQL_BOOLEAN => "BOOLEAN",
+        ibase::SQL_TYPE_DATE => "DATE",
+        ibase::SQL_TYPE_TIME => "TIME",
+        ibase::SQL_TIMESTAMP => "TIMESTAMP",
+        _ => "the parameter type",
+    }
+}
+
+#[test]
+fn coerce_integers() {
+    let meta = |sql_type, scale| ColumnMeta {
+        sql_type,
+        scale,
+        ..Default::default()
+    };
+
+    let smallint = meta(ibase::SQL_SHORT, 0);
+    assert!(matches!(
+        coerce_param(0, SqlType::Text(" 12 ".to_string()), &smallint),
+        Ok(SqlType::Integer(12))
+    ));
+    assert!(matches!(
+        coerce_param(0, SqlType::Boolean(true), &smallint),
+        Ok(SqlType::Integer(1))
+    ));
+
+    let err = coerce_param(1, SqlType::Integer(100_000), &smallint).unwrap_err();
+    assert_eq!(
+        "error: Error converting the parameter 2 to SMALLINT: The value 100000 is out of range",
+        err.to_string()
+    );
+    let err = coerce_param(2, SqlType::Text("abc".to_string()), &smallint).unwrap_err();
+    assert!(err.to_string().contains("parameter 3"), "{}", err);
+
+    let numeric = meta(ibase::SQL_INT64, -2);
+    assert!(matches!(
+        coerce_param(0, SqlType::Integer(10), &numeric),
+        Ok(SqlType::Decimal(Numeric {
+            value: 1000,
+            scale: -2
+        }))
+    ));
+    assert!(matches!(
+        coerce_param(0, SqlType::Text("1.5".to_string()), &numeric),
+        Ok(SqlType::Decimal(Numeric {
+            value: 150,
+            scale: -2
+        }))
+    ));
+    // Rounded by the server
+    assert!(matches!(
+        coerce_param(0, SqlType::Text("1.505".to_string()), &numeric),
+        Ok(SqlType::Decimal(Numeric {
+            value: 1505,
+            scale: -3
+        }))
+    ));
+
+    // No conversion to the type
+    assert!(matches!(
+        coerce_param(0, SqlType::Floating(1.5), &smallint),
+        Ok(SqlType::Floating(_))
+    ));
+}
+
+#[test]
+fn coerce_dates() {
+    let meta = |sql_type| ColumnMeta {
+        sql_type,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        coerce_param(0, SqlType::Text("2020-02-01".to_string()), &meta(ibase::SQL_TYPE_DATE)),
+        Ok(SqlType::Date(d)) if d == NaiveDate::from_ymd_opt(2020, 2, 1).unwrap()
+    ));
+    assert!(matches!(
+        coerce_param(
+            0,
+            SqlType::Text("TODAY".to_string()),
+            &meta(ibase::SQL_TYPE_DATE)
+        ),
+        Ok(SqlType::Text(_))
+    ));
+    assert!(matches!(
+        coerce_param(
+            0,
+            SqlType::Text("10:20".to_string()),
+            &meta(ibase::SQL_TYPE_TIME)
+        ),
+        Ok(SqlType::Time(_))
+    ));
+    assert!(matches!(
+        coerce_param(
+            0,
+            SqlType::Text("2020-02-01T10:20:30.5".to_string()),
+            &meta(ibase::SQL_TIMESTAMP)
+        ),
+        Ok(SqlType::Timestamp(_))
+    ));
+}
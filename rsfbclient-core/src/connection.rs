@@ -3,7 +3,7 @@
 use num_enum::TryFromPrimitive;
 use std::str::FromStr;
 
-use crate::*;
+use crate::{error::err_unsupported, *};
 
 ///A wrapper trait compatible with the niceties provided by the main rsfbclient crate
 pub trait FirebirdClient
@@ -54,6 +54,11 @@ pub trait FirebirdClientDbOps: Send {
         page_size: Option<u32>,
         dialect: Dialect,
     ) -> Result<Self::DbHandle, FbError>;
+
+    /// Take the warnings returned by the server since the last call
+    fn take_warnings(&mut self) -> Vec<FbWarning> {
+        Vec::new()
+    }
 }
 
 ///Responsible for actual transaction and statement execution
@@ -64,6 +69,8 @@ pub trait FirebirdClientSqlOps {
     type TrHandle: Send;
     /// A statement handle
     type StmtHandle: Send;
+    /// A blob handle
+    type BlobHandle: Send;
 
     /// Start a new transaction, with the specified transaction parameter buffer
     fn begin_transaction(
@@ -79,6 +86,34 @@ pub trait FirebirdClientSqlOps {
         op: TrOp,
     ) -> Result<(), FbError>;
 
+    /// Prepare a transaction for the two-phase commit, the first phase.
+    /// After this, the transaction can only be committed or rolled back.
+    ///
+    /// The message is stored in the database with the transaction, to
+    /// help the recovery if it is left in limbo
+    fn prepare_transaction(
+        &mut self,
+        _tr_handle: &mut Self::TrHandle,
+        _message: &[u8],
+    ) -> Result<(), FbError> {
+        err_unsupported("prepare transaction")
+    }
+
+    /// Ids of the transactions in limbo in the database, the
+    /// ones prepared for the two-phase commit, but not finished
+    fn limbo_transactions(&mut self, _db_handle: &mut Self::DbHandle) -> Result<Vec<u64>, FbError> {
+        err_unsupported("limbo transactions")
+    }
+
+    /// Reconnect to a transaction in limbo, to commit or rollback it
+    fn reconnect_transaction(
+        &mut self,
+        _db_handle: &mut Self::DbHandle,
+        _id: u64,
+    ) -> Result<Self::TrHandle, FbError> {
+        err_unsupported("reconnect transaction")
+    }
+
     /// Execute a sql immediately, without returning rows
     fn exec_immediate(
         &mut self,
@@ -98,6 +133,22 @@ pub trait FirebirdClientSqlOps {
         sql: &str,
     ) -> Result<(StmtType, Self::StmtHandle), FbError>;
 
+    /// Description of the columns returned by a prepared statement
+    fn describe_columns(
+        &mut self,
+        _stmt_handle: &mut Self::StmtHandle,
+    ) -> Result<Vec<ColumnMeta>, FbError> {
+        err_unsupported("describe columns")
+    }
+
+    /// Description of the input parameters of a prepared statement
+    fn describe_params(
+        &mut self,
+        _stmt_handle: &mut Self::StmtHandle,
+    ) -> Result<Vec<ColumnMeta>, FbError> {
+        err_unsupported("describe params")
+    }
+
     /// Closes or drops a statement
     fn free_statement(
         &mut self,
@@ -136,6 +187,68 @@ pub trait FirebirdClientSqlOps {
         tr_handle: &mut Self::TrHandle,
         stmt_handle: &mut Self::StmtHandle,
     ) -> Result<Option<Vec<Column>>, FbError>;
+
+    /// If true, the blob columns will be fetched as `SqlType::LazyBlob`,
+    /// with only the blob id and without the data
+    fn set_lazy_blobs(&mut self, _lazy_blobs: bool) {}
+
+    /// Create a new stream blob for writing
+    /// Returns the blob handle and id
+    fn create_blob(
+        &mut self,
+        _db_handle: &mut Self::DbHandle,
+        _tr_handle: &mut Self::TrHandle,
+    ) -> Result<(Self::BlobHandle, BlobId), FbError> {
+        err_unsupported("create blob")
+    }
+
+    /// Open an existing blob for reading
+    fn open_blob(
+        &mut self,
+        _db_handle: &mut Self::DbHandle,
+        _tr_handle: &mut Self::TrHandle,
+        _blob_id: BlobId,
+    ) -> Result<Self::BlobHandle, FbError> {
+        err_unsupported("open blob")
+    }
+
+    /// Read the next blob data.
+    /// Returns the data and `true` if the end of the blob was reached
+    fn get_segment(
+        &mut self,
+        _blob_handle: &mut Self::BlobHandle,
+    ) -> Result<(Vec<u8>, bool), FbError> {
+        err_unsupported("get segment")
+    }
+
+    /// Write the data to the blob
+    fn put_segment(
+        &mut self,
+        _blob_handle: &mut Self::BlobHandle,
+        _data: &[u8],
+    ) -> Result<(), FbError> {
+        err_unsupported("put segment")
+    }
+
+    /// Move the read position of a stream blob
+    fn seek_blob(
+        &mut self,
+        _blob_handle: &mut Self::BlobHandle,
+        _mode: BlobSeekMode,
+        _offset: i32,
+    ) -> Result<(), FbError> {
+        err_unsupported("seek blob")
+    }
+
+    /// Total length of the blob in bytes
+    fn blob_length(&mut self, _blob_handle: &mut Self::BlobHandle) -> Result<u64, FbError> {
+        err_unsupported("blob length")
+    }
+
+    /// Close the blob, completing the writes of a new blob
+    fn close_blob(&mut self, _blob_handle: &mut Self::BlobHandle) -> Result<(), FbError> {
+        err_unsupported("close blob")
+    }
 }
 
 /// Firebird base event API
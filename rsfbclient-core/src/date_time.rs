@@ -1,4 +1,5 @@
-use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use std::{collections::HashMap, fmt};
 
 use crate::{
     error::{err_column_null, err_type_conv},
@@ -7,6 +8,37 @@ use crate::{
 
 const FRACTION_TO_NANOS: u32 = 1e9 as u32 / ibase::ISC_TIME_SECONDS_PRECISION;
 
+/// Time zone ids up to `2 * TZ_OFFSET_BASE` represent a fixed offset
+/// of `id - TZ_OFFSET_BASE` minutes. The ids above are regions
+const TZ_OFFSET_BASE: i32 = 23 * 60 + 59;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Local time with an offset from UTC, used by the `TIME WITH TIME ZONE` values
+pub struct OffsetTime {
+    /// Local time
+    pub time: NaiveTime,
+
+    /// Offset from UTC
+    pub offset: FixedOffset,
+}
+
+impl OffsetTime {
+    pub fn new(time: NaiveTime, offset: FixedOffset) -> Self {
+        Self { time, offset }
+    }
+
+    /// The same time, in UTC
+    pub fn utc_time(&self) -> NaiveTime {
+        self.time - self.offset
+    }
+}
+
+impl fmt::Display for OffsetTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.time, self.offset)
+    }
+}
+
 /// Convert a numeric day to [day, month, year]. (Ported from the firebird source)
 ///
 /// Calenders are divided into 4 year cycles: 3 non-leap years, and 1 leap year.
@@ -110,6 +142,111 @@ pub fn encode_timestamp(dt: NaiveDateTime) -> ibase::ISC_TIMESTAMP {
     }
 }
 
+/// Convert a firebird time zone id to the offset from UTC.
+///
+/// The region ids (like `America/Sao_Paulo`) don't have a fixed offset, so the
+/// `ext_offset` sent by the server in the extended (`_EX`) types is used
+pub fn decode_time_zone(time_zone: u16, ext_offset: i16) -> Result<FixedOffset, FbError> {
+    let minutes = if time_zone as i32 <= TZ_OFFSET_BASE * 2 {
+        time_zone as i32 - TZ_OFFSET_BASE
+    } else {
+        ext_offset as i32
+    };
+
+    FixedOffset::east_opt(minutes * 60)
+        .ok_or_else(|| format!("Invalid time zone offset: {} minutes", minutes).into())
+}
+
+/// Returns true if the firebird time zone id is of a region, like `America/Sao_Paulo`,
+/// and not a fixed offset
+pub fn is_time_zone_region(time_zone: u16) -> bool {
+    time_zone as i32 > TZ_OFFSET_BASE * 2
+}
+
+/// Sql to get the names of the time zone regions by the id, as firebird
+/// sends only the id. Used to load the `TimeZoneNames` of a connection
+pub const TIME_ZONES_SQL: &str =
+    "SELECT RDB$TIME_ZONE_ID, TRIM(RDB$TIME_ZONE_NAME) FROM RDB$TIME_ZONES";
+
+/// Names of the time zone regions by the firebird id
+#[derive(Debug, Clone, Default)]
+pub struct TimeZoneNames(HashMap<u16, String>);
+
+impl TimeZoneNames {
+    /// Build from the rows returned by the `TIME_ZONES_SQL`
+    pub fn from_rows(rows: Vec<Vec<Column>>) -> Result<Self, FbError> {
+        let mut names = HashMap::with_capacity(rows.len());
+
+        for row in rows {
+            let mut cols = row.into_iter();
+            let err = || FbError::from("Invalid time zones lookup");
+
+            let id: u16 = cols.next().ok_or_else(err)?.to_val()?;
+            let name: String = cols.next().ok_or_else(err)?.to_val()?;
+
+            names.insert(id, name);
+        }
+
+        Ok(Self(names))
+    }
+
+    /// Name of the region with the id
+    pub fn get(&self, time_zone: u16) -> Option<&str> {
+        self.0.get(&time_zone).map(String::as_str)
+    }
+}
+
+/// Text form of a timestamp with the time zone region name,
+/// parsed by the server to send the values with a region
+pub fn timestamp_tz_text(dt: &DateTime<FixedOffset>, region: &str) -> String {
+    format!(
+        "{}.{:04} {}",
+        dt.naive_local().format("%Y-%m-%d %H:%M:%S"),
+        dt.nanosecond() / FRACTION_TO_NANOS,
+        region
+    )
+}
+
+/// Convert an offset from UTC to a firebird time zone id
+pub fn encode_time_zone(offset: FixedOffset) -> u16 {
+    (offset.local_minus_utc() / 60 + TZ_OFFSET_BASE) as u16
+}
+
+/// Convert a numeric timestamp with time zone to a DateTime
+pub fn decode_timestamp_tz(
+    ts: ibase::ISC_TIMESTAMP_TZ_EX,
+) -> Result<DateTime<FixedOffset>, FbError> {
+    let offset = decode_time_zone(ts.time_zone, ts.ext_offset)?;
+
+    Ok(DateTime::from_naive_utc_and_offset(
+        decode_timestamp(ts.utc_timestamp),
+        offset,
+    ))
+}
+
+/// Convert a DateTime to a numeric timestamp with time zone
+pub fn encode_timestamp_tz(dt: DateTime<FixedOffset>) -> ibase::ISC_TIMESTAMP_TZ {
+    ibase::ISC_TIMESTAMP_TZ {
+        utc_timestamp: encode_timestamp(dt.naive_utc()),
+        time_zone: encode_time_zone(*dt.offset()),
+    }
+}
+
+/// Convert a numeric time with time zone to an OffsetTime
+pub fn decode_time_tz(time: ibase::ISC_TIME_TZ_EX) -> Result<OffsetTime, FbError> {
+    let offset = decode_time_zone(time.time_zone, time.ext_offset)?;
+
+    Ok(OffsetTime::new(decode_time(time.utc_time) + offset, offset))
+}
+
+/// Convert an OffsetTime to a numeric time with time zone
+pub fn encode_time_tz(time: OffsetTime) -> ibase::ISC_TIME_TZ {
+    ibase::ISC_TIME_TZ {
+        utc_time: encode_time(time.utc_time()),
+        time_zone: encode_time_zone(time.offset),
+    }
+}
+
 impl IntoParam for NaiveDateTime {
     fn into_param(self) -> SqlType {
         SqlType::Timestamp(self)
@@ -118,21 +255,39 @@ impl IntoParam for NaiveDateTime {
 
 impl IntoParam for NaiveDate {
     fn into_param(self) -> SqlType {
-        // Mimics firebird conversion
-        self.and_time(NaiveTime::from_hms(0, 0, 0)).into_param()
+        SqlType::Date(self)
     }
 }
 
 impl IntoParam for NaiveTime {
     fn into_param(self) -> SqlType {
-        // Mimics firebird conversion
-        chrono::Utc::today().naive_utc().and_time(self).into_param()
+        SqlType::Time(self)
+    }
+}
+
+impl IntoParam for DateTime<FixedOffset> {
+    fn into_param(self) -> SqlType {
+        SqlType::TimestampTz(self, None)
+    }
+}
+
+impl IntoParam for DateTime<Utc> {
+    fn into_param(self) -> SqlType {
+        SqlType::TimestampTz(self.fixed_offset(), None)
+    }
+}
+
+impl IntoParam for OffsetTime {
+    fn into_param(self) -> SqlType {
+        SqlType::TimeTz(self)
     }
 }
 
 impl ColumnToVal<chrono::NaiveDate> for Column {
     fn to_val(self) -> Result<chrono::NaiveDate, FbError> {
         match self.value {
+            SqlType::Date(d) => Ok(d),
+
             SqlType::Timestamp(ts) => Ok(ts.date()),
 
             SqlType::Null => Err(err_column_null("NaiveDate")),
@@ -145,6 +300,8 @@ impl ColumnToVal<chrono::NaiveDate> for Column {
 impl ColumnToVal<chrono::NaiveTime> for Column {
     fn to_val(self) -> Result<chrono::NaiveTime, FbError> {
         match self.value {
+            SqlType::Time(t) => Ok(t),
+
             SqlType::Timestamp(ts) => Ok(ts.time()),
 
             SqlType::Null => Err(err_column_null("NaiveTime")),
@@ -159,9 +316,358 @@ impl ColumnToVal<chrono::NaiveDateTime> for Column {
         match self.value {
             SqlType::Timestamp(ts) => Ok(ts),
 
+            // Mimics firebird conversion
+            SqlType::Date(d) => Ok(d.and_time(NaiveTime::MIN)),
+
+            // Mimics firebird conversion
+            SqlType::Time(t) => Ok(Utc::now().date_naive().and_time(t)),
+
             SqlType::Null => Err(err_column_null("NaiveDateTime")),
 
             col => err_type_conv(col, "NaiveDateTime"),
         }
     }
 }
+
+impl ColumnToVal<DateTime<FixedOffset>> for Column {
+    fn to_val(self) -> Result<DateTime<FixedOffset>, FbError> {
+        match self.value {
+            SqlType::TimestampTz(dt, _) => Ok(dt),
+
+            SqlType::Null => Err(err_column_null("DateTime<FixedOffset>")),
+
+            col => err_type_conv(col, "DateTime<FixedOffset>"),
+        }
+    }
+}
+
+impl ColumnToVal<DateTime<Utc>> for Column {
+    fn to_val(self) -> Result<DateTime<Utc>, FbError> {
+        match self.value {
+            SqlType::TimestampTz(dt, _) => Ok(dt.with_timezone(&Utc)),
+
+            SqlType::Null => Err(err_column_null("DateTime<Utc>")),
+
+            col => err_type_conv(col, "DateTime<Utc>"),
+        }
+    }
+}
+
+impl ColumnToVal<OffsetTime> for Column {
+    fn to_val(self) -> Result<OffsetTime, FbError> {
+        match self.value {
+            SqlType::TimeTz(t) => Ok(t),
+
+            SqlType::Null => Err(err_column_null("OffsetTime")),
+
+            col => err_type_conv(col, "OffsetTime"),
+        }
+    }
+}
+
+#[cfg(feature = "chrono-tz")]
+mod chrono_tz_conv {
+    use super::*;
+    use chrono_tz::Tz;
+
+    /// Sent with the region name, so the server keeps the region of the value
+    impl IntoParam for DateTime<Tz> {
+        fn into_param(self) -> SqlType {
+            let region = self.timezone().name().to_string();
+
+            SqlType::TimestampTz(self.fixed_offset(), Some(region))
+        }
+    }
+
+    /// The values stored with an offset instead of a region
+    /// are only accepted if the offset is zero, as UTC
+    impl ColumnToVal<DateTime<Tz>> for Column {
+        fn to_val(self) -> Result<DateTime<Tz>, FbError> {
+            match self.value {
+                SqlType::TimestampTz(dt, Some(region)) => {
+                    let tz: Tz = region.parse().map_err(|_| {
+                        FbError::from(format!("Unknown time zone region: {}", region))
+                    })?;
+
+                    Ok(dt.with_timezone(&tz))
+                }
+
+                SqlType::TimestampTz(dt, None) if dt.offset().local_minus_utc() == 0 => {
+                    Ok(dt.with_timezone(&Tz::UTC))
+                }
+
+                SqlType::TimestampTz(dt, None) => Err(format!(
+                    "The value {} has an offset and not a time zone region, use DateTime<FixedOffset>",
+                    dt
+                )
+                .into()),
+
+                SqlType::Null => Err(err_column_null("DateTime<Tz>")),
+
+                col => err_type_conv(col, "DateTime<Tz>"),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_conv {
+    //! Conversions of the `time` crate types, made through the chrono types
+    use super::*;
+    use time::{error::ComponentRange, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    fn err_range(e: ComponentRange) -> FbError {
+        FbError::Other(format!("Value out of the time crate range: {}", e))
+    }
+
+    fn date_to_chrono(d: time::Date) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(d.year(), d.month() as u32, d.day() as u32)
+    }
+
+    fn time_to_chrono(t: time::Time) -> Option<NaiveTime> {
+        NaiveTime::from_hms_nano_opt(
+            t.hour() as u32,
+            t.minute() as u32,
+            t.second() as u32,
+            t.nanosecond(),
+        )
+    }
+
+    fn date_from_chrono(d: NaiveDate) -> Result<time::Date, FbError> {
+        Month::try_from(d.month() as u8)
+            .and_then(|month| time::Date::from_calendar_date(d.year(), month, d.day() as u8))
+            .map_err(err_range)
+    }
+
+    fn time_from_chrono(t: NaiveTime) -> Result<time::Time, FbError> {
+        time::Time::from_hms_nano(
+            t.hour() as u8,
+            t.minute() as u8,
+            t.second() as u8,
+            t.nanosecond(),
+        )
+        .map_err(err_range)
+    }
+
+    // The values out of the chrono range are sent as text, so the server reports the error
+
+    impl IntoParam for time::Date {
+        fn into_param(self) -> SqlType {
+            match date_to_chrono(self) {
+                Some(d) => d.into_param(),
+                None => SqlType::Text(self.to_string()),
+            }
+        }
+    }
+
+    impl IntoParam for time::Time {
+        fn into_param(self) -> SqlType {
+            match time_to_chrono(self) {
+                Some(t) => t.into_param(),
+                None => SqlType::Text(self.to_string()),
+            }
+        }
+    }
+
+    impl IntoParam for PrimitiveDateTime {
+        fn into_param(self) -> SqlType {
+            match (date_to_chrono(self.date()), time_to_chrono(self.time())) {
+                (Some(d), Some(t)) => d.and_time(t).into_param(),
+                _ => SqlType::Text(self.to_string()),
+            }
+        }
+    }
+
+    impl IntoParam for OffsetDateTime {
+        fn into_param(self) -> SqlType {
+            let dt = FixedOffset::east_opt(self.offset().whole_seconds()).and_then(|offset| {
+                DateTime::from_timestamp(self.unix_timestamp(), self.nanosecond())
+                    .map(|dt| dt.with_timezone(&offset))
+            });
+
+            match dt {
+                Some(dt) => dt.into_param(),
+                None => SqlType::Text(self.to_string()),
+            }
+        }
+    }
+
+    impl ColumnToVal<time::Date> for Column {
+        fn to_val(self) -> Result<time::Date, FbError> {
+            if self.value.is_null() {
+                return Err(err_column_null("time::Date"));
+            }
+
+            date_from_chrono(self.to_val()?)
+        }
+    }
+
+    impl ColumnToVal<time::Time> for Column {
+        fn to_val(self) -> Result<time::Time, FbError> {
+            if self.value.is_null() {
+                return Err(err_column_null("time::Time"));
+            }
+
+            time_from_chrono(self.to_val()?)
+        }
+    }
+
+    impl ColumnToVal<PrimitiveDateTime> for Column {
+        fn to_val(self) -> Result<PrimitiveDateTime, FbError> {
+            if self.value.is_null() {
+                return Err(err_column_null("PrimitiveDateTime"));
+            }
+
+            let dt: NaiveDateTime = self.to_val()?;
+
+            Ok(PrimitiveDateTime::new(
+                date_from_chrono(dt.date())?,
+                time_from_chrono(dt.time())?,
+            ))
+        }
+    }
+
+    impl ColumnToVal<OffsetDateTime> for Column {
+        fn to_val(self) -> Result<OffsetDateTime, FbError> {
+            if self.value.is_null() {
+                return Err(err_column_null("OffsetDateTime"));
+            }
+
+            let dt: DateTime<FixedOffset> = self.to_val()?;
+            let offset =
+                UtcOffset::from_whole_seconds(dt.offset().local_minus_utc()).map_err(err_range)?;
+
+            Ok(PrimitiveDateTime::new(
+                date_from_chrono(dt.date_naive())?,
+                time_from_chrono(dt.time())?,
+            )
+            .assume_offset(offset))
+        }
+    }
+}
+
+#[test]
+fn time_zones() {
+    let offset = FixedOffset::west_opt(3 * 3600).unwrap();
+
+    assert_eq!(1259, encode_time_zone(offset));
+    assert_eq!(offset, decode_time_zone(1259, 0).unwrap());
+    // Region ids use the extended offset
+    assert_eq!(offset, decode_time_zone(65000, -180).unwrap());
+
+    let dt = DateTime::parse_from_rfc3339("2021-03-04T10:20:30.1234-03:00").unwrap();
+    let ts = encode_timestamp_tz(dt);
+    let decoded = decode_timestamp_tz(ibase::ISC_TIMESTAMP_TZ_EX {
+        utc_timestamp: ts.utc_timestamp,
+        time_zone: ts.time_zone,
+        ext_offset: 0,
+    })
+    .unwrap();
+    assert_eq!(dt, decoded);
+    assert_eq!(offset, *decoded.offset());
+
+    let time = OffsetTime::new(NaiveTime::from_hms_opt(1, 30, 0).unwrap(), offset);
+    assert_eq!(NaiveTime::from_hms_opt(4, 30, 0).unwrap(), time.utc_time());
+    let encoded = encode_time_tz(time);
+    let decoded = decode_time_tz(ibase::ISC_TIME_TZ_EX {
+        utc_time: encoded.utc_time,
+        time_zone: encoded.time_zone,
+        ext_offset: 0,
+    })
+    .unwrap();
+    assert_eq!(time, decoded);
+    assert_eq!("01:30:00-03:00", time.to_string());
+}
+
+#[test]
+#[cfg(feature = "time")]
+fn time_crate_conversions() {
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    let date = Date::from_calendar_date(2021, Month::March, 4).unwrap();
+    let time = Time::from_hms_milli(10, 20, 30, 123).unwrap();
+    let dt = PrimitiveDateTime::new(date, time);
+    let offset_dt = dt.assume_offset(UtcOffset::from_hms(-3, 0, 0).unwrap());
+
+    let col = |value| Column::new("DT".to_string(), 0, value);
+
+    assert_eq!(
+        date,
+        ColumnToVal::<Date>::to_val(col(date.into_param())).unwrap()
+    );
+    assert_eq!(
+        time,
+        ColumnToVal::<Time>::to_val(col(time.into_param())).unwrap()
+    );
+    assert_eq!(
+        dt,
+        ColumnToVal::<PrimitiveDateTime>::to_val(col(dt.into_param())).unwrap()
+    );
+    // Date from a timestamp, as in chrono
+    assert_eq!(
+        date,
+        ColumnToVal::<Date>::to_val(col(dt.into_param())).unwrap()
+    );
+
+    let param = offset_dt.into_param();
+    assert!(matches!(
+        param,
+        SqlType::TimestampTz(dt, None) if dt.to_rfc3339() == "2021-03-04T10:20:30.123-03:00"
+    ));
+    let back = ColumnToVal::<OffsetDateTime>::to_val(col(param)).unwrap();
+    assert_eq!(offset_dt, back);
+    assert_eq!(offset_dt.offset(), back.offset());
+
+    assert!(ColumnToVal::<Date>::to_val(col(SqlType::Null)).is_err());
+}
+
+#[test]
+#[cfg(feature = "chrono-tz")]
+fn time_zone_region_conversions() {
+    use chrono::TimeZone;
+    use chrono_tz::Tz;
+
+    let sao_paulo = 65000;
+    assert!(is_time_zone_region(sao_paulo));
+    assert!(!is_time_zone_region(TZ_OFFSET_BASE as u16 - 180));
+
+    let names = TimeZoneNames::from_rows(vec![vec![
+        Column::new("ID".to_string(), 0, SqlType::Integer(sao_paulo as i64)),
+        Column::new(
+            "NAME".to_string(),
+            0,
+            SqlType::Text("America/Sao_Paulo".to_string()),
+        ),
+    ]])
+    .unwrap();
+    assert_eq!(Some("America/Sao_Paulo"), names.get(sao_paulo));
+    assert_eq!(None, names.get(sao_paulo + 1));
+
+    let col = |value| Column::new("DT".to_string(), 0, value);
+
+    let dt = FixedOffset::west_opt(3 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2021, 3, 4, 10, 20, 30)
+        .unwrap();
+    let region = names.get(sao_paulo).map(str::to_string);
+
+    let val: DateTime<Tz> = col(SqlType::TimestampTz(dt, region)).to_val().unwrap();
+    assert_eq!(Tz::America__Sao_Paulo, val.timezone());
+    assert_eq!(dt, val);
+
+    let param = val.into_param();
+    assert!(matches!(
+        &param,
+        SqlType::TimestampTz(_, Some(region)) if region == "America/Sao_Paulo"
+    ));
+    assert_eq!(
+        "2021-03-04 10:20:30.0000 America/Sao_Paulo",
+        timestamp_tz_text(&dt, "America/Sao_Paulo")
+    );
+
+    // Only the values in UTC can be read without a region
+    assert!(ColumnToVal::<DateTime<Tz>>::to_val(col(SqlType::TimestampTz(dt, None))).is_err());
+    let utc = dt.with_timezone(&Utc).fixed_offset();
+    let val: DateTime<Tz> = col(SqlType::TimestampTz(utc, None)).to_val().unwrap();
+    assert_eq!(Tz::UTC, val.timezone());
+}
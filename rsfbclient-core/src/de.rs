@@ -0,0 +1,459 @@
+//! Deserialization of the rows with serde
+
+use serde::de::{
+    self, value::SeqDeserializer, DeserializeOwned, DeserializeSeed, Deserializer,
+    IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::{fmt, vec};
+
+use crate::{ser::TIMESTAMP_FORMAT, Column, FbError, FromRow, Row, SqlType};
+
+impl de::Error for FbError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        FbError::Other(msg.to_string())
+    }
+}
+
+impl Row {
+    /// Deserialize the row to a type implementing `serde::Deserialize`.
+    ///
+    /// The struct fields are matched with the columns by the name, ignoring the
+    /// case and accepting the relation qualified names, like in `get_by_name`.
+    /// Tuples and sequences receive the columns in order.
+    ///
+    /// The decimals and the dates are deserialized from text when the type
+    /// doesn't request a number, see the `Serialize` implementation of `SqlType`
+    pub fn deserialize<T>(self) -> Result<T, FbError>
+    where
+        T: DeserializeOwned,
+    {
+        T::deserialize(RowDeserializer(self.cols))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Row deserialized with serde, to be used as the row type of the queries.
+///
+/// See `Row::deserialize`
+pub struct Deserialized<T>(pub T);
+
+impl<T> FromRow for Deserialized<T>
+where
+    T: DeserializeOwned,
+{
+    fn try_from(row: Vec<Column>) -> Result<Self, FbError> {
+        Row { cols: row }.deserialize().map(Deserialized)
+    }
+}
+
+/// Deserializes the columns as a struct, map or sequence
+struct RowDeserializer(Vec<Column>);
+
+impl<'de> Deserializer<'de> for RowDeserializer {
+    type Error = FbError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        let entries = self
+            .0
+            .into_iter()
+            .map(|col| (col.name.clone(), col))
+            .collect::<Vec<_>>();
+
+        visitor.visit_map(ColumnsMap {
+            entries: entries.into_iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FbError> {
+        let mut cols: Vec<Option<Column>> = self.0.into_iter().map(Some).collect();
+
+        // The missing columns are left to the visitor, that accepts them in the optional fields
+        let entries = fields
+            .iter()
+            .filter_map(|field| {
+                let col = cols
+                    .iter_mut()
+                    .find(|col| matches!(col, Some(col) if col.is_named(field)))?
+                    .take()?;
+
+                Some((field.to_string(), col))
+            })
+            .collect::<Vec<_>>();
+
+        visitor.visit_map(ColumnsMap {
+            entries: entries.into_iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        visitor.visit_seq(ColumnsSeq(self.0.into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, FbError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, FbError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, FbError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct enum identifier ignored_any
+    }
+}
+
+/// Deserialize the column value, adding the column name to the errors
+fn deserialize_column<'de, T>(seed: T, col: Column) -> Result<T::Value, FbError>
+where
+    T: DeserializeSeed<'de>,
+{
+    let name = col.name;
+
+    seed.deserialize(ValueDeserializer(col.value))
+        .map_err(|e| match e {
+            FbError::Other(msg) => FbError::Other(format!("Column {}: {}", name, msg)),
+            e => e,
+        })
+}
+
+/// Columns accessed as a map, by the name
+struct ColumnsMap {
+    entries: vec::IntoIter<(String, Column)>,
+    /// Column of the last key
+    value: Option<Column>,
+}
+
+impl<'de> MapAccess<'de> for ColumnsMap {
+    type Error = FbError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, FbError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, col)) => {
+                self.value = Some(col);
+
+                seed.deserialize(IntoDeserializer::<FbError>::into_deserializer(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, FbError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let col = self
+            .value
+            .take()
+            .ok_or_else(|| FbError::from("Column value requested before the name"))?;
+
+        deserialize_column(seed, col)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Columns accessed as a sequence, in order
+struct ColumnsSeq(vec::IntoIter<Column>);
+
+impl<'de> SeqAccess<'de> for ColumnsSeq {
+    type Error = FbError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, FbError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0
+            .next()
+            .map(|col| deserialize_column(seed, col))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// Deserializes a column value
+struct ValueDeserializer(SqlType);
+
+impl<'de> IntoDeserializer<'de, FbError> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl ValueDeserializer {
+    /// Deserialize the integers, accepting the decimals without the fractional part
+    fn deserialize_integer<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        match self.0 {
+            SqlType::Decimal(d) => {
+                let value = d.rescale(0)?.value;
+
+                match i64::try_from(value) {
+                    Ok(i) => visitor.visit_i64(i),
+                    Err(_) => visitor.visit_i128(value),
+                }
+            }
+
+            value => ValueDeserializer(value).deserialize_any(visitor),
+        }
+    }
+}
+
+/// Implements the integer deserialization methods
+macro_rules! deserialize_integers {
+    ( $( $method: ident ),+ ) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+                self.deserialize_integer(visitor)
+            }
+        )+
+    };
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer {
+    type Error = FbError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        match self.0 {
+            SqlType::Text(s) => visitor.visit_string(s),
+
+            SqlType::Integer(i) => visitor.visit_i64(i),
+
+            SqlType::Int128(i) => match i64::try_from(i) {
+                Ok(i) => visitor.visit_i64(i),
+                Err(_) => visitor.visit_i128(i),
+            },
+
+            SqlType::Floating(f) => visitor.visit_f64(f),
+
+            SqlType::Decimal(d) => visitor.visit_string(d.to_string()),
+
+            SqlType::DecimalFloat(d) => visitor.visit_string(d.to_string()),
+
+            SqlType::Timestamp(ts) => visitor.visit_string(ts.format(TIMESTAMP_FORMAT).to_string()),
+
+            SqlType::Date(d) => visitor.visit_string(d.to_string()),
+
+            SqlType::Time(t) => visitor.visit_string(t.to_string()),
+
+            SqlType::TimestampTz(ts, _) => visitor.visit_string(ts.to_rfc3339()),
+
+            SqlType::TimeTz(t) => visitor.visit_string(t.to_string()),
+
+            SqlType::Binary(b) => visitor.visit_byte_buf(b),
+
+            SqlType::Boolean(b) => visitor.visit_bool(b),
+
+            SqlType::Array(a) => visitor.visit_seq(SeqDeserializer::new(
+                a.elements.into_iter().map(ValueDeserializer),
+            )),
+
+            SqlType::List(l) => {
+                visitor.visit_seq(SeqDeserializer::new(l.into_iter().map(ValueDeserializer)))
+            }
+
+            SqlType::Blob(_) => Err("This is a blob id. Open the blob to read the contents".into()),
+
+            SqlType::LazyBlob(_) => {
+                Err("This is a lazy blob. Read the contents with `Transaction::read_blob`".into())
+            }
+
+            SqlType::Null => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        match self.0 {
+            SqlType::Null => visitor.visit_none(),
+            value => visitor.visit_some(ValueDeserializer(value)),
+        }
+    }
+
+    deserialize_integers!(
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128
+    );
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        match self.0 {
+            SqlType::Decimal(d) => visitor.visit_f64(d.to_f64()),
+            SqlType::DecimalFloat(d) => visitor.visit_f64(d.to_f64()),
+            value => ValueDeserializer(value).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        match self.0 {
+            SqlType::Integer(i) => visitor.visit_string(i.to_string()),
+            SqlType::Int128(i) => visitor.visit_string(i.to_string()),
+            SqlType::Floating(f) => visitor.visit_string(f.to_string()),
+            SqlType::Boolean(b) => visitor.visit_string(b.to_string()),
+            value => ValueDeserializer(value).deserialize_any(visitor),
+        }
+    }
+
+    /// Also accepts the binary data, as the `Vec<u8>` is deserialized as a sequence
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FbError> {
+        match self.0 {
+            SqlType::Binary(b) => visitor.visit_seq(SeqDeserializer::new(b.into_iter())),
+            value => ValueDeserializer(value).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, FbError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Only the enum variants without data, from the variant name
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FbError> {
+        match self.0 {
+            SqlType::Text(s) => visitor.visit_enum(s.into_deserializer()),
+            value => ValueDeserializer(value).deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool char bytes byte_buf unit unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[test]
+fn deserialize_row() {
+    use crate::{charset, BlobId, BlobRef, Numeric};
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    enum Kind {
+        Admin,
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct User {
+        id: i32,
+        name: Option<String>,
+        #[serde(rename = "users.value")]
+        value: f64,
+        kind: Kind,
+        photo: Vec<u8>,
+        missing: Option<i32>,
+    }
+
+    let row = || Row {
+        cols: vec![
+            Column::new("ID".to_string(), 0, SqlType::Integer(10)),
+            Column::new(
+                "VALUE".to_string(),
+                0,
+                SqlType::Decimal(Numeric::new(150, -2)),
+            ),
+            Column::new("NAME".to_string(), 0, SqlType::Null),
+            Column::new("KIND".to_string(), 0, SqlType::Text("Admin".to_string())),
+            Column::new("PHOTO".to_string(), 0, SqlType::Binary(vec![1, 2])),
+        ],
+    };
+
+    let user = row().deserialize::<User>();
+    // The relation qualified name needs the column metadata
+    assert!(user.is_err());
+
+    let mut row = row();
+    row.cols[1].meta = Some(std::sync::Arc::new(crate::ColumnMeta {
+        field: "VALUE".to_string(),
+        relation: "USERS".to_string(),
+        ..Default::default()
+    }));
+    let user = row.deserialize::<User>().unwrap();
+    assert_eq!(
+        User {
+            id: 10,
+            name: None,
+            value: 1.5,
+            kind: Kind::Admin,
+            photo: vec![1, 2],
+            missing: None,
+        },
+        user
+    );
+
+    let row = Row {
+        cols: vec![
+            Column::new("ID".to_string(), 0, SqlType::Integer(100_000)),
+            Column::new("NAME".to_string(), 0, SqlType::Text("abc".to_string())),
+        ],
+    };
+    let err = row.deserialize::<(i16, String)>().unwrap_err();
+    assert!(err.to_string().contains("Column ID"), "{}", err);
+
+    let row = Row {
+        cols: vec![Column::new(
+            "TEXT".to_string(),
+            0,
+            SqlType::LazyBlob(BlobRef {
+                id: BlobId(2),
+                charset: Some(charset::UTF_8),
+            }),
+        )],
+    };
+    // Lazy blobs are only loaded explicitly
+    assert!(row.deserialize::<(String,)>().is_err());
+}
@@ -0,0 +1,666 @@
+//! Decimal floating point numbers (DECFLOAT)
+//!
+//! Firebird 4 stores the `DECFLOAT(16)` and `DECFLOAT(34)` values in the
+//! IEEE 754-2008 decimal64 and decimal128 formats. The server and the
+//! fbclient use the DPD (densely packed decimal) encoding, but the BID
+//! (binary integer decimal) encoding is supported as well.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    error::{err_column_null, err_type_conv},
+    Column, ColumnToVal, FbError, IntoParam, Numeric, SqlType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Decimal floating point value
+///
+/// The values are not normalized, so `1.0` and `1.00` are
+/// different values, as in the IEEE 754 decimal formats
+pub enum DecFloat {
+    /// Finite value, representing `coefficient * 10^exponent`
+    Finite {
+        negative: bool,
+        coefficient: u128,
+        exponent: i32,
+    },
+
+    /// Positive or negative infinity
+    Infinity { negative: bool },
+
+    /// Not a number. Signaling NaNs raise errors on the server
+    NaN { negative: bool, signaling: bool },
+}
+
+/// Layout of the IEEE 754 decimal interchange formats
+struct Format {
+    /// Total bits of the format
+    bits: u32,
+    /// Digits of the coefficient
+    digits: u32,
+    /// Bits of the exponent continuation field
+    ecbits: u32,
+    /// Exponent bias
+    bias: i32,
+}
+
+impl Format {
+    /// Bits of the trailing significand field
+    fn tbits(&self) -> u32 {
+        self.bits - 6 - self.ecbits
+    }
+
+    /// Max biased exponent
+    fn max_exponent(&self) -> i32 {
+        3 * (1 << self.ecbits) - 1
+    }
+}
+
+/// Format of the `DECFLOAT(16)`
+const DECIMAL64: Format = Format {
+    bits: 64,
+    digits: 16,
+    ecbits: 8,
+    bias: 398,
+};
+
+/// Format of the `DECFLOAT(34)`
+const DECIMAL128: Format = Format {
+    bits: 128,
+    digits: 34,
+    ecbits: 12,
+    bias: 6176,
+};
+
+/// Mask with the `n` lower bits set
+fn mask(n: u32) -> u128 {
+    (1 << n) - 1
+}
+
+impl DecFloat {
+    /// Create a finite value from the signed coefficient and exponent
+    pub fn new(coefficient: i128, exponent: i32) -> Self {
+        DecFloat::Finite {
+            negative: coefficient < 0,
+            coefficient: coefficient.unsigned_abs(),
+            exponent,
+        }
+    }
+
+    /// Returns `true` if the value is a NaN
+    pub fn is_nan(&self) -> bool {
+        matches!(self, DecFloat::NaN { .. })
+    }
+
+    /// Returns `true` if the value is not a NaN or an infinity
+    pub fn is_finite(&self) -> bool {
+        matches!(self, DecFloat::Finite { .. })
+    }
+
+    /// Convert to a floating point value. May lose precision
+    pub fn to_f64(self) -> f64 {
+        match self {
+            DecFloat::Finite {
+                negative,
+                coefficient,
+                exponent,
+            } => {
+                // Parsing gives the correctly rounded double
+                let f = format!("{}e{}", coefficient, exponent)
+                    .parse::<f64>()
+                    .unwrap_or(f64::NAN);
+
+                if negative {
+                    -f
+                } else {
+                    f
+                }
+            }
+
+            DecFloat::Infinity { negative: false } => f64::INFINITY,
+
+            DecFloat::Infinity { negative: true } => f64::NEG_INFINITY,
+
+            DecFloat::NaN { .. } => f64::NAN,
+        }
+    }
+
+    /// Decode a decimal64 value in the DPD encoding
+    pub fn from_dpd64(bits: u64) -> Self {
+        decode(bits as u128, &DECIMAL64, true)
+    }
+
+    /// Decode a decimal128 value in the DPD encoding
+    pub fn from_dpd128(bits: u128) -> Self {
+        decode(bits, &DECIMAL128, true)
+    }
+
+    /// Decode a decimal64 value in the BID encoding
+    pub fn from_bid64(bits: u64) -> Self {
+        decode(bits as u128, &DECIMAL64, false)
+    }
+
+    /// Decode a decimal128 value in the BID encoding
+    pub fn from_bid128(bits: u128) -> Self {
+        decode(bits, &DECIMAL128, false)
+    }
+
+    /// Encode as a decimal64 value in the DPD encoding
+    pub fn to_dpd64(self) -> Result<u64, FbError> {
+        encode(self, &DECIMAL64, true).map(|bits| bits as u64)
+    }
+
+    /// Encode as a decimal128 value in the DPD encoding
+    pub fn to_dpd128(self) -> Result<u128, FbError> {
+        encode(self, &DECIMAL128, true)
+    }
+
+    /// Encode as a decimal64 value in the BID encoding
+    pub fn to_bid64(self) -> Result<u64, FbError> {
+        encode(self, &DECIMAL64, false).map(|bits| bits as u64)
+    }
+
+    /// Encode as a decimal128 value in the BID encoding
+    pub fn to_bid128(self) -> Result<u128, FbError> {
+        encode(self, &DECIMAL128, false)
+    }
+}
+
+/// Decode the 3 digits of a DPD declet
+fn declet_to_int(declet: u128) -> u128 {
+    let b = |n: u32| (declet >> n) & 1;
+    let small = |hi: u32, mid: u32, lo: u32| (b(hi) << 2) | (b(mid) << 1) | b(lo);
+    let large = |lo: u32| 8 | b(lo);
+
+    let (d2, d1, d0) = if b(3) == 0 {
+        (small(9, 8, 7), small(6, 5, 4), small(2, 1, 0))
+    } else {
+        match (b(2), b(1), b(6), b(5)) {
+            (0, 0, _, _) => (small(9, 8, 7), small(6, 5, 4), large(0)),
+            (0, 1, _, _) => (small(9, 8, 7), large(4), small(6, 5, 0)),
+            (1, 0, _, _) => (large(7), small(6, 5, 4), small(9, 8, 0)),
+            (_, _, 0, 0) => (large(7), large(4), small(9, 8, 0)),
+            (_, _, 0, 1) => (large(7), small(9, 8, 4), large(0)),
+            (_, _, 1, 0) => (small(9, 8, 7), large(4), large(0)),
+            _ => (large(7), large(4), large(0)),
+        }
+    };
+
+    d2 * 100 + d1 * 10 + d0
+}
+
+/// Encode 3 digits (0 to 999) as a DPD declet
+fn int_to_declet(n: u128) -> u128 {
+    let (d2, d1, d0) = (n / 100, n / 10 % 10, n % 10);
+    // Bits of the digits, for the positions of the table
+    let (a, b, c) = ((d2 >> 2) & 1, (d2 >> 1) & 1, d2 & 1);
+    let (d, e, f) = ((d1 >> 2) & 1, (d1 >> 1) & 1, d1 & 1);
+    let (g, h, i) = ((d0 >> 2) & 1, (d0 >> 1) & 1, d0 & 1);
+
+    let bits: [u128; 10] = match (d2 > 7, d1 > 7, d0 > 7) {
+        (false, false, false) => [a, b, c, d, e, f, 0, g, h, i],
+        (false, false, true) => [a, b, c, d, e, f, 1, 0, 0, i],
+        (false, true, false) => [a, b, c, g, h, f, 1, 0, 1, i],
+        (true, false, false) => [g, h, c, d, e, f, 1, 1, 0, i],
+        (true, true, false) => [g, h, c, 0, 0, f, 1, 1, 1, i],
+        (true, false, true) => [d, e, c, 0, 1, f, 1, 1, 1, i],
+        (false, true, true) => [a, b, c, 1, 0, f, 1, 1, 1, i],
+        (true, true, true) => [0, 0, c, 1, 1, f, 1, 1, 1, i],
+    };
+
+    bits.iter().fold(0, |acc, bit| (acc << 1) | bit)
+}
+
+fn decode(bits: u128, fmt: &Format, dpd: bool) -> DecFloat {
+    let negative = (bits >> (fmt.bits - 1)) & 1 == 1;
+    let comb = (bits >> (fmt.bits - 6)) & 0b11111;
+    let tbits = fmt.tbits();
+
+    match comb {
+        0b11110 => return DecFloat::Infinity { negative },
+        0b11111 => {
+            return DecFloat::NaN {
+                negative,
+                signaling: (bits >> (fmt.bits - 7)) & 1 == 1,
+            }
+        }
+        _ => {}
+    }
+
+    let (biased, coefficient) = if dpd {
+        let (exp_msb, msd) = if comb >> 3 != 0b11 {
+            (comb >> 3, comb & 0b111)
+        } else {
+            ((comb >> 1) & 0b11, 8 | (comb & 1))
+        };
+
+        let biased = (exp_msb << fmt.ecbits) | ((bits >> tbits) & mask(fmt.ecbits));
+
+        let coefficient = (0..tbits / 10).rev().fold(msd, |acc, i| {
+            acc * 1000 + declet_to_int((bits >> (i * 10)) & mask(10))
+        });
+
+        (biased, coefficient)
+    } else {
+        let (biased, coefficient) = if comb >> 3 != 0b11 {
+            (
+                (bits >> (tbits + 3)) & mask(fmt.ecbits + 2),
+                bits & mask(tbits + 3),
+            )
+        } else {
+            (
+                (bits >> (tbits + 1)) & mask(fmt.ecbits + 2),
+                (0b100 << (tbits + 1)) | (bits & mask(tbits + 1)),
+            )
+        };
+
+        // Non canonical coefficients are handled as zero
+        if coefficient >= 10u128.pow(fmt.digits) {
+            (biased, 0)
+        } else {
+            (biased, coefficient)
+        }
+    };
+
+    DecFloat::Finite {
+        negative,
+        coefficient,
+        exponent: biased as i32 - fmt.bias,
+    }
+}
+
+fn encode(val: DecFloat, fmt: &Format, dpd: bool) -> Result<u128, FbError> {
+    let sign_bit = |negative: bool| (negative as u128) << (fmt.bits - 1);
+
+    let (negative, mut coefficient, exponent) = match val {
+        DecFloat::Infinity { negative } => {
+            return Ok(sign_bit(negative) | (0b11110 << (fmt.bits - 6)))
+        }
+
+        DecFloat::NaN {
+            negative,
+            signaling,
+        } => {
+            return Ok(sign_bit(negative)
+                | (0b11111 << (fmt.bits - 6))
+                | ((signaling as u128) << (fmt.bits - 7)))
+        }
+
+        DecFloat::Finite {
+            negative,
+            coefficient,
+            exponent,
+        } => (negative, coefficient, exponent as i64),
+    };
+
+    let err = || {
+        FbError::from(format!(
+            "The value {} can't be represented as a DECFLOAT({})",
+            val, fmt.digits
+        ))
+    };
+
+    let limit = 10u128.pow(fmt.digits);
+    let mut biased = exponent + fmt.bias as i64;
+
+    // Remove the trailing zeros that don't fit in the coefficient
+    while coefficient >= limit && coefficient % 10 == 0 {
+        coefficient /= 10;
+        biased += 1;
+    }
+    if coefficient >= limit {
+        return Err(err());
+    }
+
+    if coefficient == 0 {
+        biased = biased.clamp(0, fmt.max_exponent() as i64);
+    }
+
+    // Fit the exponent in the range, without changing the value
+    while biased > fmt.max_exponent() as i64 && coefficient * 10 < limit {
+        coefficient *= 10;
+        biased -= 1;
+    }
+    while biased < 0 && coefficient % 10 == 0 {
+        coefficient /= 10;
+        biased += 1;
+    }
+    if biased < 0 || biased > fmt.max_exponent() as i64 {
+        return Err(err());
+    }
+
+    let biased = biased as u128;
+    let tbits = fmt.tbits();
+
+    let bits = if dpd {
+        let msd_factor = 10u128.pow(fmt.digits - 1);
+        let (msd, mut rest) = (coefficient / msd_factor, coefficient % msd_factor);
+
+        let mut trailing = 0;
+        for i in 0..tbits / 10 {
+            trailing |= int_to_declet(rest % 1000) << (i * 10);
+            rest /= 1000;
+        }
+
+        let exp_msb = biased >> fmt.ecbits;
+        let comb = if msd < 8 {
+            (exp_msb << 3) | msd
+        } else {
+            0b11000 | (exp_msb << 1) | (msd & 1)
+        };
+
+        (comb << (fmt.bits - 6)) | ((biased & mask(fmt.ecbits)) << tbits) | trailing
+    } else if coefficient < (1 << (tbits + 3)) {
+        (biased << (tbits + 3)) | coefficient
+    } else {
+        (0b11 << (fmt.bits - 3)) | (biased << (tbits + 1)) | (coefficient & mask(tbits + 1))
+    };
+
+    Ok(sign_bit(negative) | bits)
+}
+
+impl From<Numeric> for DecFloat {
+    fn from(n: Numeric) -> Self {
+        DecFloat::new(n.value, n.scale as i32)
+    }
+}
+
+impl fmt::Display for DecFloat {
+    /// Uses the `to-scientific-string` conversion of the
+    /// General Decimal Arithmetic specification
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = |negative: bool| if negative { "-" } else { "" };
+
+        let (negative, coefficient, exponent) = match *self {
+            DecFloat::Infinity { negative } => return write!(f, "{}Infinity", sign(negative)),
+
+            DecFloat::NaN {
+                negative,
+                signaling,
+            } => {
+                return write!(
+                    f,
+                    "{}{}NaN",
+                    sign(negative),
+                    if signaling { "s" } else { "" }
+                )
+            }
+
+            DecFloat::Finite {
+                negative,
+                coefficient,
+                exponent,
+            } => (negative, coefficient, exponent as i64),
+        };
+
+        let digits = coefficient.to_string();
+        let adjusted = exponent + digits.len() as i64 - 1;
+
+        if exponent <= 0 && adjusted >= -6 {
+            let places = -exponent as usize;
+            if places == 0 {
+                return write!(f, "{}{}", sign(negative), digits);
+            }
+
+            let (int_part, frac_part) = if digits.len() > places {
+                digits.split_at(digits.len() - places)
+            } else {
+                ("0", digits.as_str())
+            };
+
+            write!(
+                f,
+                "{}{}.{:0>width$}",
+                sign(negative),
+                int_part,
+                frac_part,
+                width = places
+            )
+        } else {
+            let (first, rest) = digits.split_at(1);
+
+            write!(f, "{}{}", sign(negative), first)?;
+            if !rest.is_empty() {
+                write!(f, ".{}", rest)?;
+            }
+            write!(
+                f,
+                "E{}{}",
+                if adjusted < 0 { "-" } else { "+" },
+                adjusted.abs()
+            )
+        }
+    }
+}
+
+impl FromStr for DecFloat {
+    type Err = FbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || FbError::from(format!("'{}' doesn't represent a decfloat value", s));
+
+        let s = s.trim();
+        let (negative, body) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let body = body.to_ascii_lowercase();
+
+        match body.as_str() {
+            "inf" | "infinity" => return Ok(DecFloat::Infinity { negative }),
+            "nan" => {
+                return Ok(DecFloat::NaN {
+                    negative,
+                    signaling: false,
+                })
+            }
+            "snan" => {
+                return Ok(DecFloat::NaN {
+                    negative,
+                    signaling: true,
+                })
+            }
+            _ => {}
+        }
+
+        let (mantissa, exponent) = match body.split_once('e') {
+            Some((m, e)) => (m, e.parse::<i32>().map_err(|_| err())?),
+            None => (body.as_str(), 0),
+        };
+        let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+        if (int_part.is_empty() && frac_part.is_empty())
+            || !int_part.chars().all(|c| c.is_ascii_digit())
+            || !frac_part.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(err());
+        }
+
+        let digits = format!("{}{}", int_part, frac_part);
+        let digits = digits.trim_start_matches('0');
+        let coefficient = if digits.is_empty() {
+            0
+        } else {
+            digits.parse::<u128>().map_err(|_| err())?
+        };
+
+        let exponent = i32::try_from(frac_part.len())
+            .ok()
+            .and_then(|places| exponent.checked_sub(places))
+            .ok_or_else(err)?;
+
+        Ok(DecFloat::Finite {
+            negative,
+            coefficient,
+            exponent,
+        })
+    }
+}
+
+impl IntoParam for DecFloat {
+    fn into_param(self) -> SqlType {
+        SqlType::DecimalFloat(self)
+    }
+}
+
+impl ColumnToVal<DecFloat> for Column {
+    fn to_val(self) -> Result<DecFloat, FbError> {
+        match self.value {
+            SqlType::DecimalFloat(d) => Ok(d),
+
+            SqlType::Decimal(n) => Ok(n.into()),
+
+            SqlType::Integer(i) => Ok(DecFloat::new(i as i128, 0)),
+
+            SqlType::Int128(i) => Ok(DecFloat::new(i, 0)),
+
+            SqlType::Null => Err(err_column_null("DecFloat")),
+
+            col => err_type_conv(col, "DecFloat"),
+        }
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal_conv {
+    use super::*;
+    use bigdecimal::{num_bigint::BigInt, BigDecimal, ToPrimitive};
+
+    impl TryFrom<DecFloat> for BigDecimal {
+        type Error = FbError;
+
+        fn try_from(d: DecFloat) -> Result<Self, Self::Error> {
+            match d {
+                DecFloat::Finite {
+                    negative,
+                    coefficient,
+                    exponent,
+                } => {
+                    let digits = BigInt::from(coefficient);
+                    let digits = if negative { -digits } else { digits };
+
+                    Ok(BigDecimal::new(digits, -(exponent as i64)))
+                }
+
+                _ => Err(format!("The value {} can't be represented as a BigDecimal", d).into()),
+            }
+        }
+    }
+
+    impl TryFrom<BigDecimal> for DecFloat {
+        type Error = FbError;
+
+        fn try_from(d: BigDecimal) -> Result<Self, Self::Error> {
+            let (digits, scale) = d.as_bigint_and_exponent();
+
+            match (digits.to_i128(), i32::try_from(-scale)) {
+                (Some(coefficient), Ok(exponent)) => Ok(DecFloat::new(coefficient, exponent)),
+                _ => Err(format!("The value {} can't be represented as a DecFloat", d).into()),
+            }
+        }
+    }
+
+    impl IntoParam for BigDecimal {
+        fn into_param(self) -> SqlType {
+            let (digits, scale) = self.as_bigint_and_exponent();
+
+            match (digits.to_i128(), i16::try_from(-scale)) {
+                (Some(value), Ok(scale)) => Numeric::new(value, scale).into_param(),
+                // Let the server parse the values out of range
+                _ => SqlType::Text(self.to_string()),
+            }
+        }
+    }
+
+    impl ColumnToVal<BigDecimal> for Column {
+        fn to_val(self) -> Result<BigDecimal, FbError> {
+            match self.value {
+                SqlType::Null => Err(err_column_null("BigDecimal")),
+
+                SqlType::Text(t) => t
+                    .trim()
+                    .parse::<BigDecimal>()
+                    .map_err(|_| FbError::from(format!("'{}' isn't a BigDecimal", t))),
+
+                _ => ColumnToVal::<DecFloat>::to_val(self)?.try_into(),
+            }
+        }
+    }
+}
+
+#[test]
+fn decfloat_declets() {
+    for n in 0..1000 {
+        assert_eq!(n, declet_to_int(int_to_declet(n)));
+    }
+
+    // Values from the DPD specification
+    assert_eq!(0x3D0, int_to_declet(750));
+    assert_eq!(0x0FF, int_to_declet(999));
+    assert_eq!(999, declet_to_int(0x3FF));
+}
+
+#[test]
+fn decfloat_encodings() {
+    let one = DecFloat::new(1, 0);
+
+    assert_eq!(0x2238000000000001, one.to_dpd64().unwrap());
+    assert_eq!(0x31C0000000000001, one.to_bid64().unwrap());
+    assert_eq!(0x22080000000000000000000000000001, one.to_dpd128().unwrap());
+    assert_eq!(one, DecFloat::from_dpd64(0x2238000000000001));
+    assert_eq!(
+        one,
+        DecFloat::from_bid128(0x30400000000000000000000000000001)
+    );
+
+    for s in [
+        "-7.50",
+        "9999999999999999",
+        "1.234567890123456E+369",
+        "8.5E-383",
+        "Infinity",
+        "-NaN",
+        "sNaN",
+    ] {
+        let d: DecFloat = s.parse().unwrap();
+
+        assert_eq!(d, DecFloat::from_dpd64(d.to_dpd64().unwrap()));
+        assert_eq!(d, DecFloat::from_bid64(d.to_bid64().unwrap()));
+        assert_eq!(d, DecFloat::from_dpd128(d.to_dpd128().unwrap()));
+        assert_eq!(d, DecFloat::from_bid128(d.to_bid128().unwrap()));
+    }
+
+    let max34: DecFloat = "9999999999999999999999999999999999E+6111".parse().unwrap();
+    assert_eq!(max34, DecFloat::from_dpd128(max34.to_dpd128().unwrap()));
+    assert_eq!(max34, DecFloat::from_bid128(max34.to_bid128().unwrap()));
+
+    assert!(max34.to_dpd64().is_err());
+    assert!("1E+400".parse::<DecFloat>().unwrap().to_dpd64().is_err());
+}
+
+#[test]
+fn decfloat_str() {
+    for s in [
+        "123.45",
+        "-0.05",
+        "0",
+        "0.000001",
+        "1E-7",
+        "1.5E+3",
+        "-Infinity",
+        "NaN",
+    ] {
+        let d: DecFloat = s.parse().unwrap();
+
+        assert_eq!(s, d.to_string());
+    }
+
+    assert_eq!(DecFloat::new(-15, 2), "-1.5e3".parse::<DecFloat>().unwrap());
+    assert_eq!("1.00E+5", DecFloat::new(100, 3).to_string());
+    assert_eq!("0.10", DecFloat::from(Numeric::new(10, -2)).to_string());
+
+    assert!("1.2.3".parse::<DecFloat>().is_err());
+    assert!("1e".parse::<DecFloat>().is_err());
+    assert!("abc".parse::<DecFloat>().is_err());
+}
@@ -1,15 +1,21 @@
 //! Error type for the connection
 
+use std::io;
 use std::str::Utf8Error;
 use std::string::FromUtf8Error;
 use thiserror::Error;
 
-use crate::SqlType;
+use crate::{ibase, SqlType};
 
 #[derive(Debug, Error)]
 pub enum FbError {
     #[error("sql error {code}: {msg}")]
-    Sql { msg: String, code: i32 },
+    Sql {
+        msg: String,
+        code: i32,
+        /// Status vector returned by the server
+        status: StatusVector,
+    },
 
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
@@ -18,6 +24,147 @@ pub enum FbError {
     Other(String),
 }
 
+impl FbError {
+    /// Status vector of the error, if returned by the server
+    pub fn status(&self) -> Option<&StatusVector> {
+        match self {
+            FbError::Sql { status, .. } => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Sql error code (SQLCODE)
+    pub fn sql_code(&self) -> Option<i32> {
+        match self {
+            FbError::Sql { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Standard SQLSTATE code
+    pub fn sql_state(&self) -> Option<&str> {
+        self.status().and_then(|st| st.sql_state.as_deref())
+    }
+
+    /// Returns true if the status vector contains the firebird error code (`ibase::isc_*`)
+    pub fn has_gds_code(&self, gds_code: u32) -> bool {
+        self.status()
+            .map(|st| st.contains(gds_code))
+            .unwrap_or(false)
+    }
+
+    /// Record locked by another transaction, or update conflict
+    pub fn is_lock_conflict(&self) -> bool {
+        [
+            ibase::isc_lock_conflict,
+            ibase::isc_update_conflict,
+            ibase::isc_lock_timeout,
+            ibase::isc_concurrent_transaction,
+        ]
+        .iter()
+        .any(|code| self.has_gds_code(*code))
+    }
+
+    /// Duplicated value in a primary key or unique constraint
+    pub fn is_unique_violation(&self) -> bool {
+        self.has_gds_code(ibase::isc_unique_key_violation) || self.has_gds_code(ibase::isc_no_dup)
+    }
+
+    /// Deadlock detected, the transaction should be retried
+    pub fn is_deadlock(&self) -> bool {
+        self.has_gds_code(ibase::isc_deadlock) || self.sql_state() == Some("40001")
+    }
+
+    /// The connection with the server was lost or shutdown
+    pub fn is_connection_lost(&self) -> bool {
+        match self {
+            FbError::Io(e) => matches!(
+                e.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+                    | io::ErrorKind::NotConnected
+            ),
+            _ => [
+                ibase::isc_network_error,
+                ibase::isc_net_read_err,
+                ibase::isc_net_write_err,
+                ibase::isc_conn_lost,
+                ibase::isc_lost_db_connection,
+                ibase::isc_shutdown,
+                ibase::isc_att_shutdown,
+                ibase::isc_net_server_shutdown,
+            ]
+            .iter()
+            .any(|code| self.has_gds_code(*code)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Firebird status vector, with the error codes and message arguments
+pub struct StatusVector {
+    /// Errors, in the order returned by the server
+    pub errors: Vec<StatusEntry>,
+    /// Standard SQLSTATE code
+    pub sql_state: Option<String>,
+}
+
+impl StatusVector {
+    /// Returns true if the firebird error code is in the vector
+    pub fn contains(&self, gds_code: u32) -> bool {
+        self.gds_codes().any(|code| code == gds_code)
+    }
+
+    /// Firebird error codes (`ibase::isc_*`) in the vector
+    pub fn gds_codes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.errors
+            .iter()
+            .map(|e| e.gds_code)
+            .filter(|code| *code != 0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error code with the arguments used in the message
+pub struct StatusEntry {
+    /// Firebird error code, `0` for the additional message strings
+    pub gds_code: u32,
+    /// Arguments of the message
+    pub args: Vec<StatusArg>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Argument of an error message
+pub enum StatusArg {
+    Number(i32),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("warning: {msg}")]
+/// Warning returned by the server along with a successful result,
+/// like the use of a deprecated syntax or a scheduled shutdown
+pub struct FbWarning {
+    pub msg: String,
+    /// Warning codes and arguments, in the `errors` field
+    pub status: StatusVector,
+}
+
+/// Maximum number of warnings kept until taken
+pub const MAX_WARNINGS: usize = 100;
+
+/// Add the warnings to the ones not yet taken, discarding
+/// the oldest to keep at most `MAX_WARNINGS`
+pub fn add_warnings(warnings: &mut Vec<FbWarning>, new: impl IntoIterator<Item = FbWarning>) {
+    warnings.extend(new);
+
+    if warnings.len() > MAX_WARNINGS {
+        warnings.drain(..warnings.len() - MAX_WARNINGS);
+    }
+}
+
 impl From<String> for FbError {
     fn from(msg: String) -> Self {
         Self::Other(msg)
@@ -49,9 +196,77 @@ pub fn err_column_null(type_name: &str) -> FbError {
     ))
 }
 
+/// Error for the operations the client does not implement
+pub fn err_unsupported<T>(operation: &str) -> Result<T, FbError> {
+    Err(FbError::Other(format!(
+        "The {} operation is not supported by this client",
+        operation
+    )))
+}
+
 pub fn err_type_conv<T>(from: SqlType, to: &str) -> Result<T, FbError> {
     Err(FbError::Other(format!(
         "Can't convert {:?} column to {}",
         from, to
     )))
 }
+
+#[test]
+fn error_classification() {
+    let err = FbError::Sql {
+        msg: "deadlock\nupdate conflicts with concurrent update".to_string(),
+        code: -913,
+        status: StatusVector {
+            errors: vec![
+                StatusEntry {
+                    gds_code: ibase::isc_deadlock,
+                    args: vec![],
+                },
+                StatusEntry {
+                    gds_code: ibase::isc_update_conflict,
+                    args: vec![],
+                },
+                StatusEntry {
+                    gds_code: ibase::isc_concurrent_transaction,
+                    args: vec![StatusArg::Number(40)],
+                },
+            ],
+            sql_state: Some("40001".to_string()),
+        },
+    };
+
+    assert!(err.is_deadlock());
+    assert!(err.is_lock_conflict());
+    assert!(!err.is_unique_violation());
+    assert!(!err.is_connection_lost());
+    assert_eq!(Some(-913), err.sql_code());
+    assert_eq!(Some("40001"), err.sql_state());
+
+    let err = FbError::from(io::Error::from(io::ErrorKind::ConnectionReset));
+    assert!(err.is_connection_lost());
+    assert!(err.status().is_none());
+}
+
+#[test]
+fn warnings_limit() {
+    let warning = |i: usize| FbWarning {
+        msg: i.to_string(),
+        status: StatusVector::default(),
+    };
+
+    let mut warnings = vec![];
+    add_warnings(&mut warnings, (0..MAX_WARNINGS - 1).map(warning));
+    assert_eq!(MAX_WARNINGS - 1, warnings.len());
+
+    add_warnings(
+        &mut warnings,
+        (MAX_WARNINGS - 1..MAX_WARNINGS + 10).map(warning),
+    );
+    assert_eq!(MAX_WARNINGS, warnings.len());
+    // The oldest are discarded
+    assert_eq!("10", warnings[0].msg);
+    assert_eq!(
+        (MAX_WARNINGS + 9).to_string(),
+        warnings[MAX_WARNINGS - 1].msg
+    );
+}
@@ -304,6 +304,13 @@ pub const SQL_QUAD: u32 = 550;
 pub const SQL_TYPE_TIME: u32 = 560;
 pub const SQL_TYPE_DATE: u32 = 570;
 pub const SQL_INT64: u32 = 580;
+pub const SQL_INT128: u32 = 32752;
+pub const SQL_DEC16: u32 = 32760;
+pub const SQL_DEC34: u32 = 32762;
+pub const SQL_TIMESTAMP_TZ_EX: u32 = 32748;
+pub const SQL_TIME_TZ_EX: u32 = 32750;
+pub const SQL_TIMESTAMP_TZ: u32 = 32754;
+pub const SQL_TIME_TZ: u32 = 32756;
 pub const SQL_BOOLEAN: u32 = 32764;
 pub const SQL_NULL: u32 = 32766;
 pub const SQL_DATE: u32 = 510;
@@ -2318,6 +2325,32 @@ pub struct ISC_TIMESTAMP {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct ISC_TIME_TZ {
+    pub utc_time: ISC_TIME,
+    pub time_zone: ISC_USHORT,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ISC_TIME_TZ_EX {
+    pub utc_time: ISC_TIME,
+    pub time_zone: ISC_USHORT,
+    pub ext_offset: ISC_SHORT,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ISC_TIMESTAMP_TZ {
+    pub utc_timestamp: ISC_TIMESTAMP,
+    pub time_zone: ISC_USHORT,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ISC_TIMESTAMP_TZ_EX {
+    pub utc_timestamp: ISC_TIMESTAMP,
+    pub time_zone: ISC_USHORT,
+    pub ext_offset: ISC_SHORT,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct GDS_QUAD_t {
     pub gds_quad_high: ISC_LONG,
     pub gds_quad_low: ISC_ULONG,
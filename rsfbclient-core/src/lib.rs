@@ -1,20 +1,42 @@
 //! Types, traits and constants to abstract over the different
 //! implementations of the firebird client
 
+pub mod array;
+pub mod blob;
 pub mod charset;
+mod coerce;
 mod connection;
 pub mod date_time;
+#[cfg(feature = "serde")]
+mod de;
+pub mod decfloat;
 pub(crate) mod error;
 pub mod ibase;
+mod named_params;
+pub mod numeric;
 mod params;
 mod row;
+#[cfg(feature = "serde")]
+mod ser;
 mod transaction;
 
+pub use array::SqlArray;
+pub use blob::{BlobId, BlobRef, BlobSeekMode};
 pub use charset::Charset;
+pub use coerce::coerce_param;
 pub use connection::*;
-pub use error::FbError;
+#[cfg(feature = "serde")]
+pub use de::Deserialized;
+pub use decfloat::DecFloat;
+pub use error::{
+    add_warnings, FbError, FbWarning, StatusArg, StatusEntry, StatusVector, MAX_WARNINGS,
+};
+pub use named_params::{expand_list_params, NamedParams};
+pub use numeric::Numeric;
 pub use params::*;
 pub use row::*;
+#[cfg(feature = "serde")]
+pub use ser::{serialize_params, Serialized};
 pub use transaction::*;
 
 #[derive(Debug, Clone)]
@@ -24,15 +46,48 @@ pub enum SqlType {
 
     Integer(i64),
 
+    /// 128 bits integer, only works in fb >= 4.0
+    Int128(i128),
+
     Floating(f64),
 
+    /// Exact NUMERIC / DECIMAL value
+    Decimal(Numeric),
+
+    /// DECFLOAT(16) / DECFLOAT(34) value, only works in fb >= 4.0
+    DecimalFloat(DecFloat),
+
     Timestamp(chrono::NaiveDateTime),
 
+    Date(chrono::NaiveDate),
+
+    Time(chrono::NaiveTime),
+
+    /// Timestamp with the offset from UTC, and the name of the time zone
+    /// region if the value has one. Only works in fb >= 4.0
+    TimestampTz(chrono::DateTime<chrono::FixedOffset>, Option<String>),
+
+    /// Only works in fb >= 4.0
+    TimeTz(date_time::OffsetTime),
+
     Binary(Vec<u8>),
 
     /// Only works in fb >= 3.0
     Boolean(bool),
 
+    /// Array column value
+    Array(SqlArray),
+
+    /// Id of an existing blob
+    Blob(BlobId),
+
+    /// Blob column not loaded yet
+    LazyBlob(BlobRef),
+
+    /// Values of a list parameter, expanded to one
+    /// parameter for each value. See `ParamList`
+    List(Vec<SqlType>),
+
     Null,
 }
 
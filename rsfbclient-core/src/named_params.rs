@@ -0,0 +1,518 @@
+//! Named parameters support, converting the `:name` parameters
+//! to the firebird positional parameters (`?`)
+
+use crate::{FbError, IntoParams, ParamsType, SqlType};
+use std::{borrow::Cow, ops::Range};
+
+/// Named params implementation.
+///
+/// Works on top of firebird positional parameters (`?`)
+pub struct NamedParams {
+    pub sql: String,
+    params_names: Vec<String>,
+}
+
+impl NamedParams {
+    /// Parse the sql statement and return a
+    /// structure representing the named parameters found
+    pub fn parse(raw_sql: &str) -> Result<Self, FbError> {
+        let mut sql = String::with_capacity(raw_sql.len());
+        let mut params_names = vec![];
+
+        // Start of the sql not yet copied
+        let mut copied = 0;
+
+        for range in placeholders(raw_sql) {
+            if raw_sql[range.clone()].starts_with(':') {
+                sql.push_str(&raw_sql[copied..range.start]);
+                sql.push('?');
+                params_names.push(raw_sql[range.start + 1..range.end].to_string());
+
+                copied = range.end;
+            }
+        }
+        sql.push_str(&raw_sql[copied..]);
+
+        Ok(NamedParams { sql, params_names })
+    }
+
+    /// Returns the sql as is, disabling named parameter function
+    pub fn empty(raw_sql: &str) -> Self {
+        Self {
+            sql: raw_sql.to_string(),
+            params_names: Default::default(),
+        }
+    }
+
+    /// Re-sort/convert the parameters, applying
+    /// the named params support
+    pub fn convert<P>(&self, params: P) -> Result<Vec<SqlType>, FbError>
+    where
+        P: IntoParams,
+    {
+        match params.try_to_params()? {
+            ParamsType::Named(names) => {
+                let mut new_params = vec![];
+
+                for qname in &self.params_names {
+                    if let Some(param) = names.get(qname) {
+                        new_params.push(param.clone());
+                    } else {
+                        return Err(FbError::from(format!(
+                            "Param :{} not found in the provided struct",
+                            qname
+                        )));
+                    }
+                }
+
+                Ok(new_params)
+            }
+            ParamsType::Positional(p) => Ok(p),
+        }
+    }
+}
+
+/// Expand the list parameters (`SqlType::List`) to one positional parameter
+/// for each value, returning the new sql and parameters. The named parameters
+/// are converted to positional ones when expanded.
+///
+/// Without list parameters, the sql and parameters are returned as they are
+pub fn expand_list_params(
+    sql: &str,
+    params: ParamsType,
+) -> Result<(Cow<'_, str>, ParamsType), FbError> {
+    let is_list = |p: &SqlType| matches!(p, SqlType::List(_));
+    let has_list = match &params {
+        ParamsType::Positional(params) => params.iter().any(is_list),
+        ParamsType::Named(params) => params.values().any(is_list),
+    };
+    if !has_list {
+        return Ok((Cow::Borrowed(sql), params));
+    }
+
+    let (sql, params) = match params {
+        ParamsType::Named(_) => {
+            let named = NamedParams::parse(sql)?;
+            let params = named.convert(params)?;
+
+            (Cow::Owned(named.sql), params)
+        }
+        ParamsType::Positional(params) => (Cow::Borrowed(sql), params),
+    };
+
+    let positions: Vec<_> = placeholders(&sql)
+        .into_iter()
+        .filter(|range| sql[range.clone()].starts_with('?'))
+        .collect();
+    if positions.len() != params.len() {
+        return Err(format!(
+            "The sql has {} parameters while {} were provided, can't expand the lists",
+            positions.len(),
+            params.len()
+        )
+        .into());
+    }
+
+    let mut expanded = String::with_capacity(sql.len());
+    let mut values = Vec::with_capacity(params.len());
+    let mut copied = 0;
+
+    for (range, param) in positions.into_iter().zip(params) {
+        match param {
+            SqlType::List(list) => {
+                expanded.push_str(&sql[copied..range.start]);
+                copied = range.end;
+
+                if list.is_empty() {
+                    expanded.push('?');
+                    values.push(SqlType::Null);
+                } else {
+                    expanded.push_str(&vec!["?"; list.len()].join(", "));
+                    values.extend(list);
+                }
+            }
+            param => values.push(param),
+        }
+    }
+    expanded.push_str(&sql[copied..]);
+
+    Ok((Cow::Owned(expanded), ParamsType::Positional(values)))
+}
+
+/// Find the parameters placeholders (`?` and `:name`) in the sql, skipping
+/// the comments, literals, quoted identifiers and PSQL bodies
+fn placeholders(sql: &str) -> Vec<Range<usize>> {
+    let bytes = sql.as_bytes();
+
+    let mut found = vec![];
+    let mut pos = 0;
+    // Parenthesis depth
+    let mut depth = 0_usize;
+    // First words of the statement, used to find the PSQL statements
+    let mut words: Vec<&str> = vec![];
+    // Inside a PSQL body, where the `:var` are variables
+    let mut psql_body = false;
+
+    while pos < bytes.len() {
+        pos = match bytes[pos] {
+            b'\'' => skip_quoted(bytes, pos, b'\''),
+
+            b'"' => skip_quoted(bytes, pos, b'"'),
+
+            b'q' | b'Q' if bytes.get(pos + 1) == Some(&b'\'') => skip_q_string(sql, pos),
+
+            b'-' if bytes.get(pos + 1) == Some(&b'-') => find(bytes, pos + 2, b"\n")
+                .map(|end| end + 1)
+                .unwrap_or(bytes.len()),
+
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => find(bytes, pos + 2, b"*/")
+                .map(|end| end + 2)
+                .unwrap_or(bytes.len()),
+
+            b'(' => {
+                depth += 1;
+                pos + 1
+            }
+
+            b')' => {
+                depth = depth.saturating_sub(1);
+                pos + 1
+            }
+
+            b'?' if !psql_body => {
+                found.push(pos..pos + 1);
+                pos + 1
+            }
+
+            b':' if !psql_body && bytes.get(pos + 1).map(|&b| is_ident_start(b)) == Some(true) => {
+                let end = ident_end(bytes, pos + 1);
+                found.push(pos..end);
+                end
+            }
+
+            b if is_ident_start(b) => {
+                let end = ident_end(bytes, pos);
+                let word = &sql[pos..end];
+
+                if depth == 0 && !psql_body {
+                    if word.eq_ignore_ascii_case("AS") && has_psql_body(&words) {
+                        psql_body = true;
+                    } else if words.len() < 4 {
+                        words.push(word);
+                    }
+                }
+
+                end
+            }
+
+            _ => pos + 1,
+        };
+    }
+
+    found
+}
+
+/// Returns `true` if the statement starting with the `words` has a PSQL body after the `AS`
+fn has_psql_body(words: &[&str]) -> bool {
+    let is =
+        |i: usize, word: &str| words.get(i).map(|w| w.eq_ignore_ascii_case(word)) == Some(true);
+
+    if is(0, "EXECUTE") {
+        return is(1, "BLOCK");
+    }
+
+    let object = if is(0, "CREATE") && is(1, "OR") && is(2, "ALTER") {
+        3
+    } else if is(0, "CREATE") || is(0, "ALTER") || is(0, "RECREATE") {
+        1
+    } else {
+        return false;
+    };
+
+    ["PROCEDURE", "FUNCTION", "TRIGGER", "PACKAGE"]
+        .iter()
+        .any(|kind| is(object, kind))
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+/// Position after the end of the identifier starting in `start`
+fn ident_end(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_' || b == b'$'))
+        .map(|len| start + len)
+        .unwrap_or(bytes.len())
+}
+
+/// Position of the `pattern`, searching from `start`
+fn find(bytes: &[u8], start: usize, pattern: &[u8]) -> Option<usize> {
+    bytes
+        .get(start..)?
+        .windows(pattern.len())
+        .position(|w| w == pattern)
+        .map(|i| start + i)
+}
+
+/// Position after the literal or identifier delimited by `quote`,
+/// where the quote is escaped by doubling it
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut pos = start + 1;
+
+    while let Some(end) = find(bytes, pos, &[quote]) {
+        if bytes.get(end + 1) == Some(&quote) {
+            pos = end + 2;
+        } else {
+            return end + 1;
+        }
+    }
+
+    bytes.len()
+}
+
+/// Position after a `q'<delimiter>...<delimiter>'` literal
+fn skip_q_string(sql: &str, start: usize) -> usize {
+    let open = match sql[start + 2..].chars().next() {
+        Some(open) => open,
+        None => return sql.len(),
+    };
+
+    let close = match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        c => c,
+    };
+
+    let content = start + 2 + open.len_utf8();
+    let end = format!("{}'", close);
+
+    sql.get(content..)
+        .and_then(|rest| rest.find(&end))
+        .map(|i| content + i + end.len())
+        .unwrap_or(sql.len())
+}
+
+#[cfg(test)]
+fn parsed(sql: &str) -> (String, Vec<String>) {
+    let params = NamedParams::parse(sql).unwrap();
+
+    (params.sql, params.params_names)
+}
+
+#[test]
+fn named_params_simple() {
+    assert_eq!(
+        (
+            "select * from t where a = ? and b = ?".to_string(),
+            vec!["a".to_string(), "b_2$".to_string()]
+        ),
+        parsed("select * from t where a = :a and b = :b_2$")
+    );
+
+    // Same name used twice
+    assert_eq!(
+        (
+            "update t set a = ? where a <> ? or b = ?".to_string(),
+            vec!["a".to_string(), "a".to_string(), "b".to_string()]
+        ),
+        parsed("update t set a = :a where a <> :a or b = :b")
+    );
+
+    // Not a parameter name, and positional parameters kept as is
+    assert_eq!(
+        ("select a : 1, ? from t".to_string(), vec![]),
+        parsed("select a : 1, ? from t")
+    );
+    assert_eq!(
+        ("select ?".to_string(), vec!["a".to_string()]),
+        parsed("select :a")
+    );
+}
+
+#[test]
+fn named_params_literals() {
+    let unchanged = [
+        "select ':a' from t",
+        "select 'it''s :a' from t",
+        "select \"col:a\" from t",
+        "select \"col \"\":a\" from t",
+        "select q'{it's :a}' from t",
+        "select Q'(it's (:a)' from t",
+        "select q'!it's :a!' from t",
+        "select q'<:a>' from t",
+        "select x'3A61' from t",
+        "select 'não :a' from t",
+        // Unterminated
+        "select 'a :a",
+        "select q'{a :a",
+    ];
+
+    for sql in unchanged {
+        assert_eq!((sql.to_string(), vec![]), parsed(sql), "{}", sql);
+    }
+
+    assert_eq!(
+        (
+            "select 'it''s', q'{it's}', \"a\" from t where a = ? and b = 'x'".to_string(),
+            vec!["a".to_string()]
+        ),
+        parsed("select 'it''s', q'{it's}', \"a\" from t where a = :a and b = 'x'")
+    );
+    assert_eq!(
+        (
+            "select 'ção' from t where a = ?".to_string(),
+            vec!["a".to_string()]
+        ),
+        parsed("select 'ção' from t where a = :a")
+    );
+    // Identifiers ending with q are not a q literal
+    assert_eq!(
+        (
+            "select seq'a' from t where a = ?".to_string(),
+            vec!["a".to_string()]
+        ),
+        parsed("select seq'a' from t where a = :a")
+    );
+}
+
+#[test]
+fn named_params_comments() {
+    assert_eq!(
+        (
+            "select a -- it's :b\nfrom t where a = ?".to_string(),
+            vec!["a".to_string()]
+        ),
+        parsed("select a -- it's :b\nfrom t where a = :a")
+    );
+    assert_eq!(
+        (
+            "select a /* it's :b\n :c */ from t where a = ?".to_string(),
+            vec!["a".to_string()]
+        ),
+        parsed("select a /* it's :b\n :c */ from t where a = :a")
+    );
+    assert_eq!(
+        ("select a -- :b".to_string(), vec![]),
+        parsed("select a -- :b")
+    );
+    assert_eq!(
+        ("select a /* :b".to_string(), vec![]),
+        parsed("select a /* :b")
+    );
+    // Not comments
+    assert_eq!(
+        (
+            "select 1 - ?, 2 / ? from t".to_string(),
+            vec!["a".to_string(), "b".to_string()]
+        ),
+        parsed("select 1 - :a, 2 / :b from t")
+    );
+}
+
+#[test]
+fn named_params_psql() {
+    assert_eq!(
+        (
+            "execute block (id int = ?, name varchar(10) = cast(? as varchar(10)))
+            returns (res int)
+            as
+            declare x int;
+            begin
+                select count(*) from t where id = :id into :x;
+                res = :x;
+                suspend;
+            end"
+            .to_string(),
+            vec!["id".to_string(), "name".to_string()]
+        ),
+        parsed(
+            "execute block (id int = :id, name varchar(10) = cast(:name as varchar(10)))
+            returns (res int)
+            as
+            declare x int;
+            begin
+                select count(*) from t where id = :id into :x;
+                res = :x;
+                suspend;
+            end"
+        )
+    );
+
+    let unchanged = [
+        "create procedure p (a int) returns (b int) as begin b = :a; suspend; end",
+        "create or alter trigger tg for t before insert as begin new.a = :x; end",
+        "recreate function f (a int) returns int as begin return :a; end",
+        "alter package pk as begin procedure p; end",
+    ];
+
+    for sql in unchanged {
+        assert_eq!((sql.to_string(), vec![]), parsed(sql), "{}", sql);
+    }
+
+    // Not a PSQL statement
+    assert_eq!(
+        (
+            "select a as b from t where c = ?".to_string(),
+            vec!["c".to_string()]
+        ),
+        parsed("select a as b from t where c = :c")
+    );
+}
+
+#[test]
+fn list_params_expansion() {
+    let positional = |params: Vec<SqlType>| ParamsType::Positional(params);
+    let list = |len: i64| SqlType::List((0..len).map(SqlType::Integer).collect());
+
+    let expanded = |sql, params| {
+        let (sql, params) = expand_list_params(sql, params).unwrap();
+        match params {
+            ParamsType::Positional(params) => (sql.into_owned(), params.len()),
+            ParamsType::Named(_) => panic!("Named parameters not converted"),
+        }
+    };
+
+    // Without lists
+    let (sql, _) = expand_list_params("select ? from t", positional(vec![SqlType::Null])).unwrap();
+    assert!(matches!(sql, Cow::Borrowed(_)));
+
+    assert_eq!(
+        (
+            "select '?' from t where a = ? and id in (?, ?, ?) -- ?".to_string(),
+            4
+        ),
+        expanded(
+            "select '?' from t where a = ? and id in (?) -- ?",
+            positional(vec![SqlType::Integer(1), list(3)])
+        )
+    );
+    assert_eq!(
+        ("select 1 from t where id in (?)".to_string(), 1),
+        expanded("select 1 from t where id in (?)", positional(vec![list(0)]))
+    );
+
+    let named = ParamsType::Named(
+        vec![
+            ("ids".to_string(), list(2)),
+            ("a".to_string(), SqlType::Integer(1)),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    assert_eq!(
+        (
+            "select 1 from t where id in (?, ?) and a = ? or b in (?, ?)".to_string(),
+            5
+        ),
+        expanded(
+            "select 1 from t where id in (:ids) and a = :a or b in (:ids)",
+            named
+        )
+    );
+
+    assert!(expand_list_params("select 1 from t", positional(vec![list(2)])).is_err());
+}
@@ -0,0 +1,290 @@
+//! Exact fixed point numbers (NUMERIC / DECIMAL)
+//!
+//! Firebird stores the `NUMERIC(p, s)` and `DECIMAL(p, s)` values as
+//! scaled integers, so the values are kept in the same form here to avoid
+//! any rounding made by a floating point conversion.
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::{
+    error::{err_column_null, err_type_conv},
+    Column, ColumnToVal, FbError, IntoParam, SqlType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Exact numeric value, representing `value * 10^scale`
+///
+/// The scale follows the firebird convention, so a `NUMERIC(15, 2)`
+/// column has scale `-2`
+pub struct Numeric {
+    /// Unscaled integer value. Wide enough for the `NUMERIC(38, s)`
+    /// values stored as `INT128` on firebird 4+
+    pub value: i128,
+
+    /// Power of 10 applied to the value
+    pub scale: i16,
+}
+
+impl Numeric {
+    /// Create a new numeric value from the unscaled integer and scale
+    pub fn new(value: i128, scale: i16) -> Self {
+        Self { value, scale }
+    }
+
+    /// Convert to a floating point value. May lose precision
+    pub fn to_f64(self) -> f64 {
+        if self.scale < 0 {
+            // Dividing by an exact power of 10 gives the nearest double
+            self.value as f64 / 10f64.powi(-(self.scale as i32))
+        } else {
+            self.value as f64 * 10f64.powi(self.scale as i32)
+        }
+    }
+
+    /// Returns the same value using a new scale, or an error if the
+    /// value does not fit in the new scale without losing data
+    pub fn rescale(self, scale: i16) -> Result<Self, FbError> {
+        let err = || {
+            FbError::from(format!(
+                "The value {} can't be represented with scale {}",
+                self, scale
+            ))
+        };
+
+        if self.value == 0 {
+            return Ok(Self { value: 0, scale });
+        }
+
+        // Computed as i32, as the difference of two i16 may overflow
+        let diff = u32::try_from((self.scale as i32 - scale as i32).abs()).map_err(|_| err())?;
+
+        let value = match scale.cmp(&self.scale) {
+            Ordering::Equal => self.value,
+
+            Ordering::Less => {
+                let factor = 10i128.checked_pow(diff).ok_or_else(err)?;
+
+                self.value.checked_mul(factor).ok_or_else(err)?
+            }
+
+            Ordering::Greater => {
+                let factor = 10i128.checked_pow(diff).ok_or_else(err)?;
+
+                if self.value % factor != 0 {
+                    return Err(err());
+                }
+
+                self.value / factor
+            }
+        };
+
+        Ok(Self { value, scale })
+    }
+}
+
+impl fmt::Display for Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.scale >= 0 {
+            if self.value == 0 {
+                return write!(f, "0");
+            }
+
+            return write!(f, "{}{}", self.value, "0".repeat(self.scale as usize));
+        }
+
+        let digits = self.value.unsigned_abs().to_string();
+        let places = self.scale.unsigned_abs() as usize;
+
+        let (int_part, frac_part) = if digits.len() > places {
+            digits.split_at(digits.len() - places)
+        } else {
+            ("0", digits.as_str())
+        };
+
+        write!(
+            f,
+            "{}{}.{:0>width$}",
+            if self.value < 0 { "-" } else { "" },
+            int_part,
+            frac_part,
+            width = places
+        )
+    }
+}
+
+impl FromStr for Numeric {
+    type Err = FbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || FbError::from(format!("'{}' doesn't represent a numeric value", s));
+
+        let s = s.trim();
+        let (int_part, frac_part) = s.split_once('.').unwrap_or((s, ""));
+
+        let digits = int_part.trim_start_matches(['-', '+']);
+        if (digits.is_empty() && frac_part.is_empty())
+            || !digits.chars().all(|c| c.is_ascii_digit())
+            || !frac_part.chars().all(|c| c.is_ascii_digit())
+            || int_part.len() - digits.len() > 1
+        {
+            return Err(err());
+        }
+
+        let scale = i16::try_from(frac_part.len()).map_err(|_| err())?;
+        let value = format!("{}{}", int_part, frac_part)
+            .parse::<i128>()
+            .map_err(|_| err())?;
+
+        Ok(Self {
+            value,
+            scale: -scale,
+        })
+    }
+}
+
+impl IntoParam for Numeric {
+    fn into_param(self) -> SqlType {
+        SqlType::Decimal(self)
+    }
+}
+
+impl ColumnToVal<Numeric> for Column {
+    fn to_val(self) -> Result<Numeric, FbError> {
+        match self.value {
+            SqlType::Decimal(n) => Ok(n),
+
+            SqlType::Integer(i) => Ok(Numeric::new(i as i128, 0)),
+
+            SqlType::Int128(i) => Ok(Numeric::new(i, 0)),
+
+            SqlType::Null => Err(err_column_null("Numeric")),
+
+            col => err_type_conv(col, "Numeric"),
+        }
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_conv {
+    use super::*;
+    use crate::DecFloat;
+    use rust_decimal::Decimal;
+
+    impl From<Decimal> for Numeric {
+        fn from(d: Decimal) -> Self {
+            Numeric::new(d.mantissa(), -(d.scale() as i16))
+        }
+    }
+
+    impl TryFrom<Numeric> for Decimal {
+        type Error = FbError;
+
+        fn try_from(n: Numeric) -> Result<Self, Self::Error> {
+            // The decimal doesn't support negative exponents
+            let n = if n.scale > 0 { n.rescale(0)? } else { n };
+
+            Decimal::try_from_i128_with_scale(n.value, (-n.scale) as u32)
+                .map_err(|_| format!("The value {} can't be represented as a Decimal", n).into())
+        }
+    }
+
+    impl IntoParam for Decimal {
+        fn into_param(self) -> SqlType {
+            Numeric::from(self).into_param()
+        }
+    }
+
+    impl ColumnToVal<Decimal> for Column {
+        fn to_val(self) -> Result<Decimal, FbError> {
+            match self.value {
+                SqlType::Null => Err(err_column_null("Decimal")),
+
+                SqlType::Text(t) => t
+                    .trim()
+                    .parse::<Decimal>()
+                    .map_err(|_| FbError::from(format!("'{}' isn't a Decimal", t))),
+
+                SqlType::DecimalFloat(DecFloat::Finite {
+                    negative,
+                    coefficient,
+                    exponent,
+                }) => {
+                    // At most 34 digits, so it fits
+                    let value = coefficient as i128;
+                    let value = if negative { -value } else { value };
+
+                    match i16::try_from(exponent) {
+                        Ok(scale) => Numeric::new(value, scale).try_into(),
+                        Err(_) => Err("The value can't be represented as a Decimal".into()),
+                    }
+                }
+
+                _ => ColumnToVal::<Numeric>::to_val(self)?.try_into(),
+            }
+        }
+    }
+}
+
+#[test]
+fn numeric_str() {
+    for (s, value, scale) in [
+        ("123.45", 12345, -2),
+        ("-0.05", -5, -2),
+        ("0.10", 10, -2),
+        ("-12", -12, 0),
+        ("7.000", 7000, -3),
+    ] {
+        let num = Numeric::from_str(s).expect("Error parsing the numeric");
+
+        assert_eq!(Numeric::new(value, scale), num);
+        assert_eq!(s, num.to_string());
+    }
+
+    assert_eq!("1200", Numeric::new(12, 2).to_string());
+    assert_eq!("0", Numeric::new(0, 2).to_string());
+
+    assert!(Numeric::from_str("1.2.3").is_err());
+    assert!(Numeric::from_str("--1").is_err());
+    assert!(Numeric::from_str("abc").is_err());
+}
+
+#[test]
+fn numeric_rescale() {
+    let num = Numeric::new(1250, -2);
+
+    assert_eq!(Numeric::new(125000, -4), num.rescale(-4).unwrap());
+    assert_eq!(Numeric::new(125, -1), num.rescale(-1).unwrap());
+    assert!(num.rescale(0).is_err());
+
+    assert!(Numeric::new(1, i16::MAX).rescale(i16::MIN).is_err());
+    assert!(Numeric::new(1, i16::MIN).rescale(i16::MAX).is_err());
+    assert_eq!(
+        Numeric::new(0, i16::MAX),
+        Numeric::new(0, i16::MIN).rescale(i16::MAX).unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "rust_decimal")]
+fn numeric_rust_decimal() {
+    use rust_decimal::Decimal;
+
+    let d: Decimal = "-123.450".parse().unwrap();
+    assert_eq!(Numeric::new(-123450, -3), Numeric::from(d));
+    assert_eq!(d, Decimal::try_from(Numeric::new(-123450, -3)).unwrap());
+    assert_eq!(
+        Decimal::from(1200),
+        Decimal::try_from(Numeric::new(12, 2)).unwrap()
+    );
+    assert!(Decimal::try_from(Numeric::new(i128::MAX, 0)).is_err());
+
+    let col = Column::new(
+        "VALUE".to_string(),
+        0,
+        SqlType::DecimalFloat("1.5E+3".parse().unwrap()),
+    );
+    assert_eq!(
+        Decimal::from(1500),
+        ColumnToVal::<Decimal>::to_val(col).unwrap()
+    );
+}
@@ -1,7 +1,6 @@
 //! Sql parameter types and traits
 
-use crate::{error::FbError, ibase, SqlType};
-use regex::{Captures, Regex};
+use crate::{ibase, FbError, SqlType};
 use std::collections::HashMap;
 
 pub use SqlType::*;
@@ -22,10 +21,27 @@ impl SqlType {
             }
             Integer(_) => (ibase::SQL_INT64 + 1, 0),
             Floating(_) => (ibase::SQL_DOUBLE + 1, 0),
+            Int128(_) => (ibase::SQL_INT128 + 1, 0),
+            Decimal(d) => {
+                if i64::try_from(d.value).is_ok() {
+                    (ibase::SQL_INT64 + 1, 0)
+                } else {
+                    (ibase::SQL_INT128 + 1, 0)
+                }
+            }
+            DecimalFloat(_) => (ibase::SQL_DEC34 + 1, 0),
             Timestamp(_) => (ibase::SQL_TIMESTAMP + 1, 0),
+            Date(_) => (ibase::SQL_TYPE_DATE + 1, 0),
+            Time(_) => (ibase::SQL_TYPE_TIME + 1, 0),
+            TimestampTz(..) => (ibase::SQL_TIMESTAMP_TZ + 1, 0),
+            TimeTz(_) => (ibase::SQL_TIME_TZ + 1, 0),
             Null => (ibase::SQL_TEXT + 1, 0),
             Binary(_) => (ibase::SQL_BLOB + 1, 0),
             Boolean(_) => (ibase::SQL_BOOLEAN + 1, 0),
+            Array(_) => (ibase::SQL_ARRAY + 1, 0),
+            Blob(_) | LazyBlob(_) => (ibase::SQL_BLOB + 1, 0),
+            // Expanded before sent
+            List(_) => (ibase::SQL_TEXT + 1, 0),
         }
     }
 }
@@ -53,6 +69,12 @@ impl IntoParam for i64 {
     }
 }
 
+impl IntoParam for i128 {
+    fn into_param(self) -> SqlType {
+        Int128(self)
+    }
+}
+
 impl IntoParam for bool {
     fn into_param(self) -> SqlType {
         Boolean(self)
@@ -100,6 +122,32 @@ where
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// List of values used as a single parameter, like in `WHERE id IN (?)`.
+///
+/// The placeholder is expanded to one parameter for each value, so a statement
+/// is prepared (and cached) for each length of the list. Only supported in the
+/// `Queryable` and `Execute` functions, as the sql depends on the values.
+///
+/// An empty list is sent as a single `NULL`, so `IN (?)` doesn't match any row.
+/// Beware that the same is true for `NOT IN (?)`
+pub struct ParamList<T>(pub Vec<T>);
+
+impl<T> IntoParam for ParamList<T>
+where
+    T: IntoParam,
+{
+    fn into_param(self) -> SqlType {
+        List(self.0.into_iter().map(IntoParam::into_param).collect())
+    }
+}
+
+impl<T> From<Vec<T>> for ParamList<T> {
+    fn from(values: Vec<T>) -> Self {
+        ParamList(values)
+    }
+}
+
 /// Implements `IntoParam` for all borrowed variants (&str, Cow and etc)
 impl<T, B> IntoParam for &B
 where
@@ -132,18 +180,13 @@ pub enum ParamsType {
     ///
     /// Support for this kind of parameter is provided by this library.
     ///
-    /// Currently only a naive regex-based approach is used, to support very basic
-    /// select, insert, etc statements
-    ///
-    /// **CAUTION!**
-    /// Named parameter support is still very preliminary.
-    /// Use of named parameters may currently give unexpected results. Please test your queries carefully
-    /// when using this feature.
-    ///
-    /// In particular, the simple regex-based parser is known to definitely to have trouble with:
-    ///   * occurences of apostrophe (`'`) anywhere except as string literal delimiters (for example, in comments)
-    ///   * statements with closed variable bindings (which uses the `:var` syntax) (for example, in PSQL via `EXECUTE BLOCK` or `EXECUTE PROCEDURE`)
+    /// The statement is tokenized to find the parameters, so the `:name` inside
+    /// comments, string literals (including `q'{...}'`) and quoted identifiers
+    /// are not replaced. The same name can be used more than once.
     ///
+    /// In `EXECUTE BLOCK` only the header is searched, as the `:var` in
+    /// the body are PSQL variables. The parameters must be passed to the
+    /// block inputs, like `EXECUTE BLOCK (id INT = :id) AS ...`
     ///
     /// This crate provides a [derive macro](prelude/derive.IntoParams.html) for supplying arguments via the fields of a struct and their labels.
     Named(HashMap<String, SqlType>),
@@ -167,6 +210,27 @@ impl ParamsType {
 /// This crate provides a [derive macro](prelude/derive.IntoParams.html) for supplying arguments via the fields of a struct and their labels.
 pub trait IntoParams {
     fn to_params(self) -> ParamsType;
+
+    /// Convert to the parameters, returning an error if the conversion
+    /// can fail. Used when executing, the default calls `to_params`
+    fn try_to_params(self) -> Result<ParamsType, FbError>
+    where
+        Self: Sized,
+    {
+        Ok(self.to_params())
+    }
+}
+
+/// Types always converted to named parameters. Required by the fields
+/// with the `flatten` attribute of the `IntoParams` derive in structs with named fields
+pub trait IntoNamedParams: IntoParams {
+    fn to_named_params(self) -> HashMap<String, SqlType>;
+}
+
+/// Types always converted to positional parameters. Required by the fields
+/// with the `flatten` attribute of the `IntoParams` derive in tuple structs
+pub trait IntoPositionalParams: IntoParams {
+    fn to_positional_params(self) -> Vec<SqlType>;
 }
 
 impl IntoParams for ParamsType {
@@ -183,6 +247,12 @@ impl IntoParams for Vec<SqlType> {
     }
 }
 
+impl IntoPositionalParams for Vec<SqlType> {
+    fn to_positional_params(self) -> Vec<SqlType> {
+        self
+    }
+}
+
 /// Represents 0 parameters
 impl IntoParams for () {
     fn to_params(self) -> ParamsType {
@@ -190,6 +260,12 @@ impl IntoParams for () {
     }
 }
 
+impl IntoPositionalParams for () {
+    fn to_positional_params(self) -> Vec<SqlType> {
+        vec![]
+    }
+}
+
 /// Generates IntoParams implementations for a tuple
 macro_rules! impl_into_params {
     ($([$t: ident, $v: ident]),+) => {
@@ -198,11 +274,20 @@ macro_rules! impl_into_params {
             $( $t: IntoParam, )+
         {
             fn to_params(self) -> ParamsType {
+                ParamsType::Positional(self.to_positional_params())
+            }
+        }
+
+        impl<$($t),+> IntoPositionalParams for ($($t,)+)
+        where
+            $( $t: IntoParam, )+
+        {
+            fn to_positional_params(self) -> Vec<SqlType> {
                 let ( $($v,)+ ) = self;
 
-                ParamsType::Positional(vec![ $(
+                vec![ $(
                     $v.into_param(),
-                )+ ])
+                )+ ]
             }
         }
     };
@@ -238,76 +323,3 @@ impls_into_params!(
     [N, n],
     [O, o]
 );
-
-/// Named params implementation.
-///
-/// Works on top of firebird positional parameters (`?`)
-pub struct NamedParams {
-    pub sql: String,
-    params_names: Vec<String>,
-}
-
-impl NamedParams {
-    /// Parse the sql statement and return a
-    /// structure representing the named parameters found
-    pub fn parse(raw_sql: &str) -> Result<Self, FbError> {
-        let rparams = Regex::new(r#"('[^']*')|:\w+"#)
-            .map_err(|e| FbError::from(format!("Error on start the regex for named params: {}", e)))
-            .unwrap();
-
-        let mut params_names = vec![];
-        let sql = rparams
-            .replace_all(raw_sql, |caps: &Captures| match caps.get(1) {
-                Some(same) => same.as_str().to_string(),
-                None => "?".to_string(),
-            })
-            .to_string();
-
-        for params in rparams.captures_iter(raw_sql) {
-            for param in params
-                .iter()
-                .filter_map(|p| p.map(|m| m.as_str()))
-                .filter(|p| p.starts_with(':'))
-            {
-                params_names.push(param.replace(':', ""));
-            }
-        }
-
-        Ok(NamedParams { sql, params_names })
-    }
-
-    /// Returns the sql as is, disabling named parameter function
-    pub fn empty(raw_sql: &str) -> Self {
-        Self {
-            sql: raw_sql.to_string(),
-            params_names: Default::default(),
-        }
-    }
-
-    /// Re-sort/convert the parameters, applying
-    /// the named params support
-    pub fn convert<P>(&self, params: P) -> Result<Vec<SqlType>, FbError>
-    where
-        P: IntoParams,
-    {
-        match params.to_params() {
-            ParamsType::Named(names) => {
-                let mut new_params = vec![];
-
-                for qname in &self.params_names {
-                    if let Some(param) = names.get(qname) {
-                        new_params.push(param.clone());
-                    } else {
-                        return Err(FbError::from(format!(
-                            "Param :{} not found in the provided struct",
-                            qname
-                        )));
-                    }
-                }
-
-                Ok(new_params)
-            }
-            ParamsType::Positional(p) => Ok(p),
-        }
-    }
-}
@@ -1,9 +1,11 @@
 //! Sql column types and traits
 
 use crate::{
+    charset, date_time,
     error::{err_column_null, err_type_conv},
-    FbError, SqlType,
+    ibase, Charset, FbError, SqlType,
 };
+use std::sync::Arc;
 
 pub use SqlType::*;
 
@@ -25,6 +27,20 @@ impl Row {
         }
     }
 
+    /// Get the column value by the name, ignoring the case.
+    ///
+    /// Also accepts the name qualified by the relation, like `TABLE.FIELD`
+    pub fn get_by_name<T>(&self, name: &str) -> Result<T, FbError>
+    where
+        Column: ColumnToVal<T>,
+    {
+        if let Some(col) = self.cols.iter().find(|col| col.is_named(name)) {
+            col.clone().to_val()
+        } else {
+            Err(format!("The column {} doesn't exists", name).into())
+        }
+    }
+
     /// Get the values for all columns
     pub fn get_all<T>(self) -> Result<T, FbError>
     where
@@ -39,6 +55,11 @@ pub struct Column {
     pub value: SqlType,
     pub raw_type: u32,
     pub name: String,
+    /// Sub type described by the server. For blobs, 0 is binary,
+    /// 1 is text and the others are internal or user defined
+    pub sub_type: i16,
+    /// Description of the column, shared by all the rows
+    pub meta: Option<Arc<ColumnMeta>>,
 }
 
 impl Column {
@@ -47,8 +68,107 @@ impl Column {
             name,
             raw_type,
             value,
+            sub_type: 0,
+            meta: None,
         }
     }
+
+    /// Set the sub type of the column
+    pub fn with_sub_type(mut self, sub_type: i16) -> Self {
+        self.sub_type = sub_type;
+        self
+    }
+
+    /// Set the description of the column, including the sub type
+    pub fn with_meta(mut self, meta: Arc<ColumnMeta>) -> Self {
+        self.sub_type = meta.sub_type;
+        self.meta = Some(meta);
+        self
+    }
+
+    /// Returns true if the column has the name, ignoring the case.
+    ///
+    /// The name can be qualified by the relation, like `TABLE.FIELD`,
+    /// if the description of the column is available
+    pub fn is_named(&self, name: &str) -> bool {
+        if self.name.eq_ignore_ascii_case(name) {
+            return true;
+        }
+
+        match (name.rsplit_once('.'), &self.meta) {
+            (Some((relation, field)), Some(meta)) => {
+                meta.relation.eq_ignore_ascii_case(relation)
+                    && (meta.field.eq_ignore_ascii_case(field)
+                        || meta.alias.eq_ignore_ascii_case(field))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Description of a column, as returned by the server
+pub struct ColumnMeta {
+    /// Name of the column in the results: the alias, or the field name
+    pub alias: String,
+    /// Name of the field in the relation, empty for expressions
+    pub field: String,
+    /// Table, view or procedure of the field, empty for expressions
+    pub relation: String,
+    /// Owner of the relation
+    pub owner: String,
+    /// Firebird type code (`ibase::SQL_*`), without the nullable flag
+    pub sql_type: u32,
+    /// Scale of the numeric and decimal types
+    pub scale: i16,
+    /// Sub type. For blobs, 0 is binary and 1 is text. For integers,
+    /// 1 is numeric and 2 is decimal
+    pub sub_type: i16,
+    /// Declared length of the data, in bytes
+    pub length: u32,
+    /// Firebird charset id, for the text and text blob columns
+    pub charset_id: Option<u8>,
+    /// True if the column accepts nulls
+    pub nullable: bool,
+}
+
+impl ColumnMeta {
+    /// Description of the type, from the values in a xsqlvar before the coercions.
+    ///
+    /// The names are left empty
+    pub fn from_type(sqltype: i16, scale: i16, sub_type: i16, length: i16) -> Self {
+        let sql_type = (sqltype & !1) as u32;
+
+        let charset_id = match sql_type {
+            // Low byte of the sub type, the high byte is the collation
+            ibase::SQL_TEXT | ibase::SQL_VARYING => Some(sub_type as u8),
+            // Stored in the scale
+            ibase::SQL_BLOB if sub_type == 1 => Some(scale as u8),
+            _ => None,
+        };
+
+        ColumnMeta {
+            sql_type,
+            scale,
+            sub_type,
+            length: length as u16 as u32,
+            charset_id,
+            nullable: sqltype & 1 != 0,
+            ..Default::default()
+        }
+    }
+
+    /// Charset of the text columns, if supported
+    pub fn charset(&self) -> Option<Charset> {
+        self.charset_id.and_then(Charset::from_id)
+    }
+
+    /// Returns true for the text columns with the `OCTETS` charset,
+    /// like the `CHAR(16) CHARACTER SET OCTETS` used for uuids
+    pub fn is_octets(&self) -> bool {
+        matches!(self.sql_type, ibase::SQL_TEXT | ibase::SQL_VARYING)
+            && self.charset_id == Some(charset::OCTETS_ID)
+    }
 }
 
 #[allow(clippy::wrong_self_convention)]
@@ -66,48 +186,125 @@ impl ColumnToVal<String> for Column {
 
             Integer(i) => Ok(i.to_string()),
 
+            Int128(i) => Ok(i.to_string()),
+
             Floating(f) => Ok(f.to_string()),
 
+            Decimal(d) => Ok(d.to_string()),
+
+            DecimalFloat(d) => Ok(d.to_string()),
+
             Timestamp(ts) => Ok(ts.to_string()),
 
+            Date(d) => Ok(d.to_string()),
+
+            Time(t) => Ok(t.to_string()),
+
+            TimestampTz(ts, None) => Ok(ts.to_string()),
+
+            TimestampTz(ts, Some(region)) => Ok(date_time::timestamp_tz_text(&ts, &region)),
+
+            TimeTz(t) => Ok(t.to_string()),
+
+            // The char and varchar with the `OCTETS` charset, returned as text before
+            Binary(b) if matches!(self.raw_type & !1, ibase::SQL_TEXT | ibase::SQL_VARYING) => {
+                Ok(String::from_utf8(b)?)
+            }
+
             Binary(_) => Err("This is a binary column. You cannot use string to access".into()),
 
             Boolean(bo) => Ok(bo.to_string()),
 
+            Array(_) => Err("This is an array column. You cannot use string to access".into()),
+
+            Blob(_) => Err("This is a blob id. Open the blob to read the contents".into()),
+
+            col @ List(_) => err_type_conv(col, "String"),
+
+            LazyBlob(_) => {
+                Err("This is a lazy blob. Read the contents with `Transaction::read_blob`".into())
+            }
+
             Null => Err(err_column_null("String")),
         }
     }
 }
 
-impl ColumnToVal<i64> for Column {
-    fn to_val(self) -> Result<i64, FbError> {
-        match self.value {
-            Integer(i) => Ok(i),
+impl Column {
+    /// Converts the integer columns, and the floating point and decimal columns
+    /// without a fractional part, checking if the value fits in the type
+    fn into_int<T>(self, type_name: &str) -> Result<T, FbError>
+    where
+        T: TryFrom<i128>,
+    {
+        let name = self.name;
+        let err_fract = |value: &dyn std::fmt::Display| {
+            FbError::Other(format!(
+                "The value {} of the column {} has a fractional part and can't be converted to {}",
+                value, name, type_name
+            ))
+        };
+        let err_range = |value: &dyn std::fmt::Display| {
+            FbError::Other(format!(
+                "The value {} of the column {} is out of the range of {}",
+                value, name, type_name
+            ))
+        };
+
+        let value = match self.value {
+            Integer(i) => i as i128,
+
+            Int128(i) => i,
+
+            // A positive scale only fails by overflow, a negative one by the fractional part
+            Decimal(d) if d.scale > 0 => d.rescale(0).map_err(|_| err_range(&d))?.value,
+            Decimal(d) => d.rescale(0).map_err(|_| err_fract(&d))?.value,
+
+            Floating(f) => {
+                if !f.is_finite() || f.fract() != 0.0 {
+                    return Err(err_fract(&f));
+                }
+                // `i128::MAX as f64` is rounded up to 2^127, so is out of the range
+                if f < i128::MIN as f64 || f >= i128::MAX as f64 {
+                    return Err(err_range(&f));
+                }
+
+                f as i128
+            }
 
-            Null => Err(err_column_null("i64")),
+            Null => return Err(err_column_null(type_name)),
 
-            col => err_type_conv(col, "i64"),
-        }
-    }
-}
+            col => return err_type_conv(col, type_name),
+        };
 
-impl ColumnToVal<i32> for Column {
-    fn to_val(self) -> Result<i32, FbError> {
-        ColumnToVal::<i64>::to_val(self).map(|i| i as i32)
+        T::try_from(value).map_err(|_| err_range(&value))
     }
 }
 
-impl ColumnToVal<i16> for Column {
-    fn to_val(self) -> Result<i16, FbError> {
-        ColumnToVal::<i64>::to_val(self).map(|i| i as i16)
-    }
+/// Generates the range checked ColumnToVal implementations for the integers
+macro_rules! impl_column_int {
+    ($($t: ty),+) => {
+        $(
+            impl ColumnToVal<$t> for Column {
+                fn to_val(self) -> Result<$t, FbError> {
+                    self.into_int(stringify!($t))
+                }
+            }
+        )+
+    };
 }
 
+impl_column_int!(i128, i64, i32, i16, i8, isize, u128, u64, u32, u16, u8, usize);
+
 impl ColumnToVal<f64> for Column {
     fn to_val(self) -> Result<f64, FbError> {
         match self.value {
             Floating(f) => Ok(f),
 
+            Decimal(d) => Ok(d.to_f64()),
+
+            DecimalFloat(d) => Ok(d.to_f64()),
+
             Null => Err(err_column_null("f64")),
 
             col => err_type_conv(col, "f64"),
@@ -126,6 +323,10 @@ impl ColumnToVal<Vec<u8>> for Column {
         match self.value {
             Binary(b) => Ok(b),
 
+            LazyBlob(_) => {
+                Err("This is a lazy blob. Read the contents with `Transaction::read_blob`".into())
+            }
+
             Null => Err(err_column_null("Vec<u8>")),
 
             col => err_type_conv(col, "Vec<u8>"),
@@ -187,6 +388,55 @@ impl FromRow for () {
     }
 }
 
+#[doc(hidden)]
+/// Columns of a row accessed by the name, used by the `FromRow` derive
+pub struct NamedColumns(Vec<Option<Column>>);
+
+impl NamedColumns {
+    pub fn new(row: Vec<Column>) -> Self {
+        NamedColumns(row.into_iter().map(Some).collect())
+    }
+
+    /// Take the value of the first column not yet taken with the name, ignoring the case
+    /// and accepting the relation qualified names.
+    ///
+    /// Returns `None` if the column was not found
+    pub fn take<T>(&mut self, name: &str) -> Option<Result<T, FbError>>
+    where
+        Column: ColumnToVal<T>,
+    {
+        let col = self
+            .0
+            .iter_mut()
+            .find(|col| matches!(col, Some(col) if col.is_named(name)))?
+            .take()?;
+
+        let col_name = col.name.clone();
+
+        Some(col.to_val().map_err(|e| match e {
+            FbError::Other(msg) => FbError::Other(format!("Column {}: {}", col_name, msg)),
+            e => e,
+        }))
+    }
+
+    /// Error for a column needed by a field that was not found in the row
+    pub fn err_missing(&self, name: &str, field: &str) -> FbError {
+        let available: Vec<&str> = self
+            .0
+            .iter()
+            .flatten()
+            .map(|col| col.name.as_str())
+            .collect();
+
+        FbError::Other(format!(
+            "Column {} not found in the row, needed by the field {}. Available columns: {}",
+            name,
+            field,
+            available.join(", ")
+        ))
+    }
+}
+
 /// Generates FromRow implementations for a tuple
 macro_rules! impl_from_row {
     ($($t: ident),+) => {
@@ -228,3 +478,142 @@ macro_rules! impls_from_row {
 }
 
 impls_from_row!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+
+#[cfg(feature = "uuid")]
+mod uuid_conv {
+    use super::*;
+    use crate::IntoParam;
+    use uuid::Uuid;
+
+    /// Sent as the 16 bytes, for the `CHAR(16) CHARACTER SET OCTETS` columns,
+    /// like the ones filled by `GEN_UUID()`. To use with `CHAR_TO_UUID(?)`,
+    /// send the text form with `uuid.hyphenated().to_string()`
+    impl IntoParam for Uuid {
+        fn into_param(self) -> SqlType {
+            Binary(self.as_bytes().to_vec())
+        }
+    }
+
+    /// From the 16 bytes of the `CHAR(16) CHARACTER SET OCTETS` columns,
+    /// or from the text form, as returned by `UUID_TO_CHAR`
+    impl ColumnToVal<Uuid> for Column {
+        fn to_val(self) -> Result<Uuid, FbError> {
+            match self.value {
+                Binary(b) => Uuid::from_slice(&b)
+                    .map_err(|e| FbError::from(format!("Invalid uuid bytes: {}", e))),
+
+                Text(t) => Uuid::parse_str(t.trim())
+                    .map_err(|e| FbError::from(format!("'{}' isn't an uuid: {}", t, e))),
+
+                Null => Err(err_column_null("Uuid")),
+
+                col => err_type_conv(col, "Uuid"),
+            }
+        }
+    }
+}
+
+#[test]
+fn column_named() {
+    let meta = ColumnMeta {
+        alias: "ALIAS".to_string(),
+        field: "NAME".to_string(),
+        relation: "USERS".to_string(),
+        ..ColumnMeta::from_type(ibase::SQL_VARYING as i16 + 1, 0, 4, 40)
+    };
+    assert_eq!(Some(4), meta.charset_id);
+    assert!(meta.nullable);
+
+    let col = Column::new("ALIAS".to_string(), ibase::SQL_VARYING, SqlType::Null)
+        .with_meta(Arc::new(meta));
+
+    assert!(col.is_named("alias"));
+    assert!(col.is_named("users.name"));
+    assert!(col.is_named("USERS.ALIAS"));
+    assert!(!col.is_named("NAME"));
+    assert!(!col.is_named("OTHER.NAME"));
+}
+
+#[test]
+#[cfg(feature = "uuid")]
+fn uuid_columns() {
+    use uuid::Uuid;
+
+    let uuid = Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+
+    let col = Column::new(
+        "ID".to_string(),
+        ibase::SQL_TEXT,
+        Binary(uuid.as_bytes().to_vec()),
+    );
+    assert_eq!(uuid, ColumnToVal::<Uuid>::to_val(col).unwrap());
+
+    let col = Column::new(
+        "ID".to_string(),
+        ibase::SQL_VARYING,
+        Text("01234567-89AB-CDEF-0123-456789ABCDEF".to_string()),
+    );
+    assert_eq!(uuid, ColumnToVal::<Uuid>::to_val(col).unwrap());
+
+    let col = Column::new("ID".to_string(), ibase::SQL_TEXT, Binary(vec![1, 2]));
+    assert!(ColumnToVal::<Uuid>::to_val(col).is_err());
+
+    assert!(matches!(crate::IntoParam::into_param(uuid), Binary(b) if b.len() == 16));
+
+    let meta = ColumnMeta::from_type(ibase::SQL_TEXT as i16, 0, charset::OCTETS_ID as i16, 16);
+    assert!(meta.is_octets());
+}
+
+#[test]
+fn column_octets_text() {
+    let col = Column::new(
+        "CODE".to_string(),
+        ibase::SQL_VARYING,
+        Binary("abc".as_bytes().to_vec()),
+    );
+    assert_eq!(Some("abc".to_string()), col.clone().to_val().ok());
+    assert_eq!(Some(b"abc".to_vec()), col.to_val().ok());
+
+    let col = Column::new("CODE".to_string(), ibase::SQL_TEXT, Binary(vec![0xFF]));
+    assert!(ColumnToVal::<String>::to_val(col).is_err());
+
+    // Binary blobs are not converted
+    let col = Column::new("DATA".to_string(), ibase::SQL_BLOB, Binary(vec![1, 2]));
+    assert!(ColumnToVal::<String>::to_val(col).is_err());
+}
+
+#[test]
+fn column_integers() {
+    let col = |value| Column::new("ID".to_string(), ibase::SQL_INT64, value);
+
+    assert_eq!(Some(100i32), col(Integer(100)).to_val().ok());
+    assert_eq!(Some(u64::MAX), col(Int128(u64::MAX as i128)).to_val().ok());
+    assert_eq!(Some(3usize), col(Floating(3.0)).to_val().ok());
+    assert_eq!(
+        Some(-2i8),
+        col(Decimal(crate::Numeric::new(-200, -2))).to_val().ok()
+    );
+
+    let err = ColumnToVal::<i16>::to_val(col(Integer(100_000))).unwrap_err();
+    assert_eq!(
+        "error: The value 100000 of the column ID is out of the range of i16",
+        err.to_string()
+    );
+    let err = ColumnToVal::<u32>::to_val(col(Integer(-1))).unwrap_err();
+    assert_eq!(
+        "error: The value -1 of the column ID is out of the range of u32",
+        err.to_string()
+    );
+    let err = ColumnToVal::<i64>::to_val(col(Floating(1.5))).unwrap_err();
+    assert_eq!(
+        "error: The value 1.5 of the column ID has a fractional part and can't be converted to i64",
+        err.to_string()
+    );
+    assert!(ColumnToVal::<i64>::to_val(col(Decimal(crate::Numeric::new(150, -2)))).is_err());
+    let err = ColumnToVal::<i128>::to_val(col(Decimal(crate::Numeric::new(i128::MAX / 10, 2))))
+        .unwrap_err();
+    assert!(err.to_string().contains("is out of the range of i128"));
+    assert!(ColumnToVal::<i128>::to_val(col(Floating(f64::NAN))).is_err());
+    assert!(ColumnToVal::<i128>::to_val(col(Floating(1e39))).is_err());
+    assert!(ColumnToVal::<u8>::to_val(col(Text("1".to_string()))).is_err());
+}
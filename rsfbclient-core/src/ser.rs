@@ -0,0 +1,943 @@
+//! Serialization of the rows and parameters with serde
+
+use serde::ser::{
+    self, Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct,
+    SerializeStructVariant, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+    Serializer,
+};
+use serde_json::value::Serializer as JsonSerializer;
+use std::{collections::HashMap, fmt};
+
+use crate::{Column, FbError, FromRow, IntoParams, ParamsType, Row, SqlType};
+
+/// Format of the timestamps converted to text, ISO 8601 without the offset
+pub(crate) const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+impl ser::Error for FbError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        FbError::Other(msg.to_string())
+    }
+}
+
+/// Serialized as a map of the column names to the values
+impl Serialize for Row {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.cols.len()))?;
+        for col in &self.cols {
+            map.serialize_entry(&col.name, &col.value)?;
+        }
+        map.end()
+    }
+}
+
+/// The decimals, and the integers that don't fit in a `i64`, are serialized
+/// as text to not lose the precision. The dates and times are serialized as
+/// text in the ISO 8601 formats
+impl Serialize for SqlType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            SqlType::Text(s) => serializer.serialize_str(s),
+
+            SqlType::Integer(i) => serializer.serialize_i64(*i),
+
+            SqlType::Int128(i) => match i64::try_from(*i) {
+                Ok(i) => serializer.serialize_i64(i),
+                Err(_) => serializer.collect_str(i),
+            },
+
+            SqlType::Floating(f) => serializer.serialize_f64(*f),
+
+            SqlType::Decimal(d) => serializer.collect_str(d),
+
+            SqlType::DecimalFloat(d) => serializer.collect_str(d),
+
+            SqlType::Timestamp(ts) => serializer.collect_str(&ts.format(TIMESTAMP_FORMAT)),
+
+            SqlType::Date(d) => serializer.collect_str(d),
+
+            SqlType::Time(t) => serializer.collect_str(t),
+
+            SqlType::TimestampTz(ts, _) => serializer.serialize_str(&ts.to_rfc3339()),
+
+            SqlType::TimeTz(t) => serializer.collect_str(t),
+
+            SqlType::Binary(b) => serializer.serialize_bytes(b),
+
+            SqlType::Boolean(b) => serializer.serialize_bool(*b),
+
+            SqlType::Array(a) => serializer.collect_seq(&a.elements),
+
+            SqlType::List(l) => serializer.collect_seq(l),
+
+            SqlType::Blob(_) => Err(ser::Error::custom(
+                "This is a blob id. Open the blob to read the contents",
+            )),
+
+            SqlType::LazyBlob(_) => Err(ser::Error::custom(
+                "This is a lazy blob. Read the contents with `Transaction::read_blob`",
+            )),
+
+            SqlType::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Row {
+    /// Convert the row to a json object, using the column names as keys.
+    ///
+    /// If more than one column has the same name, only the last one is kept
+    pub fn to_json(&self) -> Result<serde_json::Value, FbError> {
+        serde_json::to_value(self).map_err(err_json)
+    }
+}
+
+/// Json object with the column names as keys, see `Row::to_json`
+impl FromRow for serde_json::Value {
+    fn try_from(row: Vec<Column>) -> Result<Self, FbError> {
+        Row { cols: row }.to_json()
+    }
+}
+
+/// Parameters from a value implementing `serde::Serialize`.
+///
+/// Structs and maps are sent as named parameters, using the field names or
+/// keys, and sequences and tuples as positional parameters. The values are
+/// converted as:
+/// - Sequences of `u8`, like `Vec<u8>`, to binary data
+/// - Other sequences and tuples to a `ParamList`, to be used in `IN (:values)`
+/// - Nested structs, maps and enums with data to json text
+/// - Enum variants without data to the variant name
+/// - Unit and `None` to `NULL`
+///
+/// If the value can't be serialized, the error is returned when executing
+/// the statement. Calling `to_params` directly panics in this case
+pub struct Serialized<T>(pub T);
+
+impl<T> IntoParams for Serialized<T>
+where
+    T: Serialize,
+{
+    fn to_params(self) -> ParamsType {
+        serialize_params(&self.0).expect("Error serializing the parameters")
+    }
+
+    fn try_to_params(self) -> Result<ParamsType, FbError> {
+        serialize_params(&self.0)
+    }
+}
+
+/// Convert a value implementing `serde::Serialize` to the parameters, see `Serialized`
+pub fn serialize_params<T>(value: &T) -> Result<ParamsType, FbError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(ParamsSerializer)
+}
+
+fn err_json(e: serde_json::Error) -> FbError {
+    FbError::Other(e.to_string())
+}
+
+fn err_not_params() -> FbError {
+    FbError::from("The parameters must be a struct, a map or a sequence")
+}
+
+/// Serializes the top level value to the parameters
+struct ParamsSerializer;
+
+/// Rejects the scalar values, returning the error
+macro_rules! reject_scalars {
+    ( $ok: ty, $err: expr; $( $method: ident ( $t: ty ) ),+ ) => {
+        $(
+            fn $method(self, _: $t) -> Result<$ok, FbError> {
+                Err($err)
+            }
+        )+
+    };
+}
+
+impl Serializer for ParamsSerializer {
+    type Ok = ParamsType;
+    type Error = FbError;
+
+    type SerializeSeq = PositionalSerializer;
+    type SerializeTuple = PositionalSerializer;
+    type SerializeTupleStruct = PositionalSerializer;
+    type SerializeTupleVariant = Impossible<ParamsType, FbError>;
+    type SerializeMap = NamedSerializer;
+    type SerializeStruct = NamedSerializer;
+    type SerializeStructVariant = Impossible<ParamsType, FbError>;
+
+    reject_scalars!(
+        ParamsType, err_not_params();
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8])
+    );
+
+    fn serialize_none(self) -> Result<ParamsType, FbError> {
+        Err(err_not_params())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<ParamsType, FbError> {
+        Err(err_not_params())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<ParamsType, FbError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<ParamsType, FbError> {
+        Ok(ParamsType::Positional(vec![]))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<ParamsType, FbError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<ParamsType, FbError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<ParamsType, FbError> {
+        Err(err_not_params())
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<PositionalSerializer, FbError> {
+        Ok(PositionalSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<PositionalSerializer, FbError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<PositionalSerializer, FbError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, FbError> {
+        Err(err_not_params())
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<NamedSerializer, FbError> {
+        Ok(NamedSerializer {
+            params: HashMap::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<NamedSerializer, FbError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, FbError> {
+        Err(err_not_params())
+    }
+}
+
+/// Collects the positional parameters
+struct PositionalSerializer(Vec<SqlType>);
+
+impl PositionalSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FbError> {
+        self.0.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> ParamsType {
+        ParamsType::Positional(self.0)
+    }
+}
+
+/// Collects the list parameter values
+struct ListSerializer {
+    values: Vec<SqlType>,
+    /// Values collected while all are `u8`, to send as binary data
+    bytes: Option<Vec<u8>>,
+}
+
+impl ListSerializer {
+    fn new(len: Option<usize>) -> Self {
+        ListSerializer {
+            values: vec![],
+            bytes: Some(Vec::with_capacity(len.unwrap_or(0))),
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FbError> {
+        if let Some(bytes) = &mut self.bytes {
+            if let Ok(b) = value.serialize(ByteSerializer) {
+                bytes.push(b);
+                return Ok(());
+            }
+
+            // Not a byte sequence, the bytes already collected are integers
+            self.values = bytes
+                .drain(..)
+                .map(|b| SqlType::Integer(b as i64))
+                .collect();
+            self.bytes = None;
+        }
+
+        self.values.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> SqlType {
+        match self.bytes {
+            Some(bytes) if !bytes.is_empty() => SqlType::Binary(bytes),
+            _ => SqlType::List(self.values),
+        }
+    }
+}
+
+/// Implements the sequence serializers, collecting the values
+macro_rules! impl_serialize_values {
+    ($ty: ident, $ok: ty) => {
+        impl SerializeSeq for $ty {
+            type Ok = $ok;
+            type Error = FbError;
+
+            fn serialize_element<T: Serialize + ?Sized>(
+                &mut self,
+                value: &T,
+            ) -> Result<(), FbError> {
+                self.push(value)
+            }
+
+            fn end(self) -> Result<$ok, FbError> {
+                Ok(self.finish())
+            }
+        }
+
+        impl SerializeTuple for $ty {
+            type Ok = $ok;
+            type Error = FbError;
+
+            fn serialize_element<T: Serialize + ?Sized>(
+                &mut self,
+                value: &T,
+            ) -> Result<(), FbError> {
+                SerializeSeq::serialize_element(self, value)
+            }
+
+            fn end(self) -> Result<$ok, FbError> {
+                SerializeSeq::end(self)
+            }
+        }
+
+        impl SerializeTupleStruct for $ty {
+            type Ok = $ok;
+            type Error = FbError;
+
+            fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FbError> {
+                SerializeSeq::serialize_element(self, value)
+            }
+
+            fn end(self) -> Result<$ok, FbError> {
+                SerializeSeq::end(self)
+            }
+        }
+    };
+}
+
+impl_serialize_values!(PositionalSerializer, ParamsType);
+impl_serialize_values!(ListSerializer, SqlType);
+
+/// Collects the named parameters
+struct NamedSerializer {
+    params: HashMap<String, SqlType>,
+    /// Key waiting for the value
+    key: Option<String>,
+}
+
+impl SerializeMap for NamedSerializer {
+    type Ok = ParamsType;
+    type Error = FbError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), FbError> {
+        self.key = Some(match key.serialize(ValueSerializer)? {
+            SqlType::Text(s) => s,
+            SqlType::Integer(i) => i.to_string(),
+            _ => return Err("The parameter names must be strings".into()),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FbError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| FbError::from("Parameter value serialized without the name"))?;
+
+        self.params.insert(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ParamsType, FbError> {
+        Ok(ParamsType::Named(self.params))
+    }
+}
+
+impl SerializeStruct for NamedSerializer {
+    type Ok = ParamsType;
+    type Error = FbError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FbError> {
+        self.params
+            .insert(key.to_string(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ParamsType, FbError> {
+        Ok(ParamsType::Named(self.params))
+    }
+}
+
+/// Serializes a value of the parameters
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = SqlType;
+    type Error = FbError;
+
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = Json<<JsonSerializer as Serializer>::SerializeTupleVariant>;
+    type SerializeMap = Json<<JsonSerializer as Serializer>::SerializeMap>;
+    type SerializeStruct = Json<<JsonSerializer as Serializer>::SerializeStruct>;
+    type SerializeStructVariant = Json<<JsonSerializer as Serializer>::SerializeStructVariant>;
+
+    fn serialize_bool(self, v: bool) -> Result<SqlType, FbError> {
+        Ok(SqlType::Boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<SqlType, FbError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<SqlType, FbError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<SqlType, FbError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<SqlType, FbError> {
+        Ok(SqlType::Integer(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<SqlType, FbError> {
+        Ok(SqlType::Int128(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<SqlType, FbError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<SqlType, FbError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<SqlType, FbError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<SqlType, FbError> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => self.serialize_i128(v as i128),
+        }
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<SqlType, FbError> {
+        i128::try_from(v)
+            .map(SqlType::Int128)
+            .map_err(|_| format!("The value {} is out of range", v).into())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<SqlType, FbError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<SqlType, FbError> {
+        Ok(SqlType::Floating(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<SqlType, FbError> {
+        Ok(SqlType::Text(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<SqlType, FbError> {
+        Ok(SqlType::Text(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<SqlType, FbError> {
+        Ok(SqlType::Binary(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<SqlType, FbError> {
+        Ok(SqlType::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<SqlType, FbError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<SqlType, FbError> {
+        Ok(SqlType::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<SqlType, FbError> {
+        Ok(SqlType::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<SqlType, FbError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<SqlType, FbError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<SqlType, FbError> {
+        json_text(JsonSerializer.serialize_newtype_variant(name, variant_index, variant, value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ListSerializer, FbError> {
+        Ok(ListSerializer::new(len))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer, FbError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<ListSerializer, FbError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, FbError> {
+        JsonSerializer
+            .serialize_tuple_variant(name, variant_index, variant, len)
+            .map(Json)
+            .map_err(err_json)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, FbError> {
+        JsonSerializer
+            .serialize_map(len)
+            .map(Json)
+            .map_err(err_json)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, FbError> {
+        JsonSerializer
+            .serialize_struct(name, len)
+            .map(Json)
+            .map_err(err_json)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, FbError> {
+        JsonSerializer
+            .serialize_struct_variant(name, variant_index, variant, len)
+            .map(Json)
+            .map_err(err_json)
+    }
+}
+
+/// Serializes a nested value to json text
+struct Json<S>(S);
+
+fn json_text(value: Result<serde_json::Value, serde_json::Error>) -> Result<SqlType, FbError> {
+    Ok(SqlType::Text(value.map_err(err_json)?.to_string()))
+}
+
+impl<S> SerializeTupleVariant for Json<S>
+where
+    S: SerializeTupleVariant<Ok = serde_json::Value, Error = serde_json::Error>,
+{
+    type Ok = SqlType;
+    type Error = FbError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FbError> {
+        self.0.serialize_field(value).map_err(err_json)
+    }
+
+    fn end(self) -> Result<SqlType, FbError> {
+        json_text(self.0.end())
+    }
+}
+
+impl<S> SerializeMap for Json<S>
+where
+    S: SerializeMap<Ok = serde_json::Value, Error = serde_json::Error>,
+{
+    type Ok = SqlType;
+    type Error = FbError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), FbError> {
+        self.0.serialize_key(key).map_err(err_json)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FbError> {
+        self.0.serialize_value(value).map_err(err_json)
+    }
+
+    fn end(self) -> Result<SqlType, FbError> {
+        json_text(self.0.end())
+    }
+}
+
+impl<S> SerializeStruct for Json<S>
+where
+    S: SerializeStruct<Ok = serde_json::Value, Error = serde_json::Error>,
+{
+    type Ok = SqlType;
+    type Error = FbError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FbError> {
+        self.0.serialize_field(key, value).map_err(err_json)
+    }
+
+    fn end(self) -> Result<SqlType, FbError> {
+        json_text(self.0.end())
+    }
+}
+
+impl<S> SerializeStructVariant for Json<S>
+where
+    S: SerializeStructVariant<Ok = serde_json::Value, Error = serde_json::Error>,
+{
+    type Ok = SqlType;
+    type Error = FbError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FbError> {
+        self.0.serialize_field(key, value).map_err(err_json)
+    }
+
+    fn end(self) -> Result<SqlType, FbError> {
+        json_text(self.0.end())
+    }
+}
+
+/// Serializes only the `u8` values, to find the byte sequences
+struct ByteSerializer;
+
+fn err_not_byte() -> FbError {
+    FbError::from("Not a byte")
+}
+
+impl Serializer for ByteSerializer {
+    type Ok = u8;
+    type Error = FbError;
+
+    type SerializeSeq = Impossible<u8, FbError>;
+    type SerializeTuple = Impossible<u8, FbError>;
+    type SerializeTupleStruct = Impossible<u8, FbError>;
+    type SerializeTupleVariant = Impossible<u8, FbError>;
+    type SerializeMap = Impossible<u8, FbError>;
+    type SerializeStruct = Impossible<u8, FbError>;
+    type SerializeStructVariant = Impossible<u8, FbError>;
+
+    fn serialize_u8(self, v: u8) -> Result<u8, FbError> {
+        Ok(v)
+    }
+
+    reject_scalars!(
+        u8, err_not_byte();
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str)
+    );
+
+    fn serialize_none(self) -> Result<u8, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<u8, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_unit(self) -> Result<u8, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<u8, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<u8, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<u8, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, FbError> {
+        Err(err_not_byte())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, FbError> {
+        Err(err_not_byte())
+    }
+}
+
+#[test]
+fn serialize_params_struct() {
+    #[derive(serde::Serialize)]
+    struct Address {
+        city: &'static str,
+    }
+
+    #[derive(serde::Serialize)]
+    enum Kind {
+        Admin,
+    }
+
+    #[derive(serde::Serialize)]
+    struct User {
+        id: u64,
+        name: Option<&'static str>,
+        kind: Kind,
+        ids: Vec<i32>,
+        photo: Vec<u8>,
+        codes: (u8, i32),
+        address: Address,
+    }
+
+    let user = User {
+        id: 10,
+        name: None,
+        kind: Kind::Admin,
+        ids: vec![1, 2],
+        photo: vec![1, 2, 3],
+        codes: (1, 300),
+        address: Address { city: "Blumenau" },
+    };
+
+    let params = match Serialized(&user).to_params() {
+        ParamsType::Named(params) => params,
+        _ => panic!("Expected named params"),
+    };
+
+    assert!(matches!(params["id"], SqlType::Integer(10)));
+    assert!(matches!(params["name"], SqlType::Null));
+    assert!(matches!(&params["kind"], SqlType::Text(s) if s == "Admin"));
+    assert!(matches!(
+        &params["ids"],
+        SqlType::List(l) if matches!(l[..], [SqlType::Integer(1), SqlType::Integer(2)])
+    ));
+    assert!(matches!(&params["photo"], SqlType::Binary(b) if b == &[1, 2, 3]));
+    assert!(matches!(
+        &params["codes"],
+        SqlType::List(l) if matches!(l[..], [SqlType::Integer(1), SqlType::Integer(300)])
+    ));
+    assert!(matches!(&params["address"], SqlType::Text(s) if s == r#"{"city":"Blumenau"}"#));
+
+    let params = serialize_params(&(1, "a")).unwrap();
+    assert!(matches!(
+        params,
+        ParamsType::Positional(p) if matches!(p[..], [SqlType::Integer(1), SqlType::Text(_)])
+    ));
+
+    assert!(serialize_params(&10).is_err());
+    assert!(serialize_params(&(u128::MAX,)).is_err());
+
+    // The errors are returned when executing, instead of panicking
+    assert!(Serialized(10).try_to_params().is_err());
+    assert!(Serialized(None::<User>).try_to_params().is_err());
+    assert!(Serialized((u128::MAX,)).try_to_params().is_err());
+}
+
+#[test]
+fn row_to_json() {
+    let row = Row {
+        cols: vec![
+            Column::new("ID".to_string(), 0, SqlType::Integer(1)),
+            Column::new(
+                "VALUE".to_string(),
+                0,
+                SqlType::Decimal(crate::Numeric::new(1050, -2)),
+            ),
+            Column::new(
+                "CREATED".to_string(),
+                0,
+                SqlType::Timestamp(
+                    chrono::NaiveDate::from_ymd_opt(2020, 1, 2)
+                        .unwrap()
+                        .and_hms_opt(10, 20, 30)
+                        .unwrap(),
+                ),
+            ),
+            Column::new("NAME".to_string(), 0, SqlType::Null),
+        ],
+    };
+
+    assert_eq!(
+        serde_json::json!({
+            "ID": 1,
+            "VALUE": "10.50",
+            "CREATED": "2020-01-02T10:20:30",
+            "NAME": null,
+        }),
+        row.to_json().unwrap()
+    );
+}
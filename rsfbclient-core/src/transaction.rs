@@ -116,3 +116,66 @@ impl Default for TransactionConfiguration {
         }
     }
 }
+
+/// Items requested to list the transactions in limbo
+pub const LIMBO_INFO: [u8; 1] = [ibase::db_info_types_isc_info_limbo as u8];
+
+/// Size of the buffer for the response of a database info request with
+/// the `LIMBO_INFO`, the maximum allowed by the native client
+pub const LIMBO_INFO_BUFFER_LENGTH: usize = i16::MAX as usize;
+
+/// Parse the response of a database info request with the `LIMBO_INFO`,
+/// returning the ids of the transactions in limbo.
+///
+/// If the response was truncated, returns only the ids that fit in it
+pub fn parse_limbo_transactions(mut info: &[u8]) -> Result<Vec<u64>, FbError> {
+    let err = || FbError::from("Invalid limbo info response");
+
+    let mut ids = Vec::new();
+
+    while let [item, rest @ ..] = info {
+        if *item == ibase::isc_info_end as u8 || *item == ibase::isc_info_truncated as u8 {
+            break;
+        }
+
+        if rest.len() < 2 {
+            return Err(err());
+        }
+        let len = u16::from_le_bytes([rest[0], rest[1]]) as usize;
+        let value = rest.get(2..2 + len).ok_or_else(err)?;
+
+        if *item == ibase::db_info_types_isc_info_limbo as u8 {
+            // Little endian integer with `len` bytes
+            ids.push(value.iter().rev().fold(0, |acc, b| (acc << 8) | *b as u64));
+        }
+
+        info = &rest[2 + len..];
+    }
+
+    Ok(ids)
+}
+
+/// Id of a transaction in the format used to reconnect to it,
+/// a little endian integer with 4 bytes, or 8 if it doesn't fit
+pub fn reconnect_transaction_id(id: u64) -> Vec<u8> {
+    match u32::try_from(id) {
+        Ok(id) => id.to_le_bytes().to_vec(),
+        Err(_) => id.to_le_bytes().to_vec(),
+    }
+}
+
+#[test]
+fn limbo_info() {
+    assert_eq!(
+        vec![0x1234, 0x0001_0000_0000],
+        parse_limbo_transactions(&[
+            16, 4, 0, 0x34, 0x12, 0, 0, 16, 8, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1
+        ])
+        .unwrap()
+    );
+    assert_eq!(Vec::<u64>::new(), parse_limbo_transactions(&[1]).unwrap());
+    assert!(parse_limbo_transactions(&[16, 4, 0, 1]).is_err());
+
+    assert_eq!(vec![0x34, 0x12, 0, 0], reconnect_transaction_id(0x1234));
+    assert_eq!(8, reconnect_transaction_id(u32::MAX as u64 + 1).len());
+}
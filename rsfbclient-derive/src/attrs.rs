@@ -0,0 +1,83 @@
+//! Parsing of the `#[rsfbclient(...)]` attributes
+
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+#[derive(Default)]
+/// Options of a field
+pub struct FieldAttrs {
+    /// Column or parameter name to use instead of the field name
+    pub rename: Option<String>,
+    /// Use `Default::default()` if the column is not found
+    pub default: bool,
+    /// Don't use the field as a parameter
+    pub skip: bool,
+    /// Use the parameters of the field, a type that implements `IntoParams`
+    pub flatten: bool,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut parsed = FieldAttrs::default();
+
+        for meta in rsfbclient_metas(attrs) {
+            match meta {
+                Meta::NameValue(nv) if nv.path.is_ident("rename") => match nv.lit {
+                    Lit::Str(name) => parsed.rename = Some(name.value()),
+                    _ => panic!("expected a string in #[rsfbclient(rename = \"...\")]"),
+                },
+
+                Meta::Path(path) if path.is_ident("default") => parsed.default = true,
+
+                Meta::Path(path) if path.is_ident("skip") => parsed.skip = true,
+
+                Meta::Path(path) if path.is_ident("flatten") => parsed.flatten = true,
+
+                _ => panic!("unknown rsfbclient field attribute"),
+            }
+        }
+
+        parsed
+    }
+}
+
+#[derive(Default)]
+/// Options of an enum variant
+pub struct VariantAttrs {
+    /// Text stored in the column for the variant
+    pub text: Option<String>,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut parsed = VariantAttrs::default();
+
+        for meta in rsfbclient_metas(attrs) {
+            match meta {
+                Meta::NameValue(nv) if nv.path.is_ident("text") => match nv.lit {
+                    Lit::Str(text) => parsed.text = Some(text.value()),
+                    _ => panic!("expected a string in #[rsfbclient(text = \"...\")]"),
+                },
+
+                _ => panic!("unknown rsfbclient variant attribute"),
+            }
+        }
+
+        parsed
+    }
+}
+
+/// Items of all `#[rsfbclient(...)]` attributes
+fn rsfbclient_metas(attrs: &[Attribute]) -> Vec<Meta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("rsfbclient"))
+        .flat_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.into_iter(),
+            _ => panic!("expected #[rsfbclient(...)]"),
+        })
+        .map(|nested| match nested {
+            NestedMeta::Meta(meta) => meta,
+            NestedMeta::Lit(_) => panic!("unexpected literal in #[rsfbclient(...)]"),
+        })
+        .collect()
+}
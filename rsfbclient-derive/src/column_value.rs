@@ -0,0 +1,185 @@
+//! `IntoParam` and `FromColumn` derives, for types stored in a single column
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields, Generics, Ident, Member, Type};
+
+use crate::attrs::VariantAttrs;
+
+/// Kinds of types supported
+enum Shape {
+    /// Struct with a single field, stored as the field value
+    Newtype { member: Member, ty: Type },
+    /// Enum without fields, stored as the discriminant
+    IntEnum { variants: Vec<Ident> },
+    /// Enum without fields, stored as the text of each variant
+    TextEnum { variants: Vec<(Ident, String)> },
+}
+
+impl Shape {
+    fn parse(input: &DeriveInput) -> Self {
+        match &input.data {
+            Data::Struct(data) => {
+                let mut fields = data.fields.iter();
+
+                match (fields.next(), fields.next()) {
+                    (Some(field), None) => Shape::Newtype {
+                        member: match &field.ident {
+                            Some(ident) => Member::Named(ident.clone()),
+                            None => Member::Unnamed(0.into()),
+                        },
+                        ty: field.ty.clone(),
+                    },
+                    _ => panic!("expected a struct with a single field"),
+                }
+            }
+
+            Data::Enum(data) => {
+                let mut int_variants = vec![];
+                let mut text_variants = vec![];
+
+                for variant in &data.variants {
+                    if !matches!(variant.fields, Fields::Unit) {
+                        panic!("expected an enum without fields");
+                    }
+
+                    match VariantAttrs::parse(&variant.attrs).text {
+                        Some(text) => text_variants.push((variant.ident.clone(), text)),
+                        None => int_variants.push(variant.ident.clone()),
+                    }
+                }
+
+                if text_variants.is_empty() {
+                    Shape::IntEnum {
+                        variants: int_variants,
+                    }
+                } else if int_variants.is_empty() {
+                    Shape::TextEnum {
+                        variants: text_variants,
+                    }
+                } else {
+                    panic!("all the variants must have a #[rsfbclient(text = \"...\")] attribute, or none of them");
+                }
+            }
+
+            Data::Union(_) => panic!("expected a struct or enum"),
+        }
+    }
+}
+
+/// Add the bound to the generics of a newtype
+fn bounded_generics(input: &DeriveInput, bound: syn::WherePredicate) -> Generics {
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.push(bound);
+
+    generics
+}
+
+pub fn derive_into_param(input: DeriveInput) -> TokenStream {
+    let name = &input.ident;
+
+    match Shape::parse(&input) {
+        Shape::Newtype { member, ty } => {
+            let generics = bounded_generics(&input, parse_quote! { #ty: rsfbclient::IntoParam });
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+            quote! {
+                impl #impl_generics rsfbclient::IntoParam for #name #ty_generics #where_clause {
+                    fn into_param(self) -> rsfbclient::SqlType {
+                        rsfbclient::IntoParam::into_param(self.#member)
+                    }
+                }
+            }
+        }
+
+        Shape::IntEnum { .. } => quote! {
+            impl rsfbclient::IntoParam for #name {
+                fn into_param(self) -> rsfbclient::SqlType {
+                    rsfbclient::SqlType::Integer(self as i64)
+                }
+            }
+        },
+
+        Shape::TextEnum { variants } => {
+            let (idents, texts): (Vec<_>, Vec<_>) = variants.into_iter().unzip();
+
+            quote! {
+                impl rsfbclient::IntoParam for #name {
+                    fn into_param(self) -> rsfbclient::SqlType {
+                        rsfbclient::SqlType::Text(
+                            match self {
+                                #(#name::#idents => #texts,)*
+                            }
+                            .to_string(),
+                        )
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn derive_from_column(input: DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let name_str = name.to_string();
+
+    match Shape::parse(&input) {
+        Shape::Newtype { member, ty } => {
+            let generics = bounded_generics(
+                &input,
+                parse_quote! { rsfbclient::Column: rsfbclient::ColumnToVal<#ty> },
+            );
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+            quote! {
+                impl #impl_generics rsfbclient::ColumnToVal<#name #ty_generics> for rsfbclient::Column #where_clause {
+                    fn to_val(self) -> Result<#name #ty_generics, rsfbclient::FbError> {
+                        Ok(#name {
+                            #member: rsfbclient::ColumnToVal::<#ty>::to_val(self)?,
+                        })
+                    }
+                }
+            }
+        }
+
+        Shape::IntEnum { variants } => quote! {
+            impl rsfbclient::ColumnToVal<#name> for rsfbclient::Column {
+                fn to_val(self) -> Result<#name, rsfbclient::FbError> {
+                    let val: i64 = rsfbclient::ColumnToVal::to_val(self)?;
+
+                    #(
+                        if val == #name::#variants as i64 {
+                            return Ok(#name::#variants);
+                        }
+                    )*
+
+                    Err(rsfbclient::FbError::from(format!(
+                        "Invalid value {} for the enum {}",
+                        val, #name_str
+                    )))
+                }
+            }
+        },
+
+        Shape::TextEnum { variants } => {
+            let (idents, texts): (Vec<_>, Vec<_>) = variants.into_iter().unzip();
+
+            quote! {
+                impl rsfbclient::ColumnToVal<#name> for rsfbclient::Column {
+                    fn to_val(self) -> Result<#name, rsfbclient::FbError> {
+                        let val: String = rsfbclient::ColumnToVal::to_val(self)?;
+
+                        // The char columns are padded with spaces
+                        match val.trim_end() {
+                            #(#texts => Ok(#name::#idents),)*
+                            val => Err(rsfbclient::FbError::from(format!(
+                                "Invalid value '{}' for the enum {}",
+                                val, #name_str
+                            ))),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! `FromRow` derive
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DataStruct, DeriveInput, Fields};
+
+use crate::attrs::FieldAttrs;
+
+pub fn derive(input: DeriveInput) -> TokenStream {
+    let st_name = &input.ident;
+    let st_fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("expected a struct with named fields"),
+    };
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    let mut st_fields_vals = Vec::with_capacity(st_fields.len());
+
+    for field in st_fields {
+        let ident = field.ident.as_ref().expect("Field name required");
+        let ty = &field.ty;
+        let attrs = FieldAttrs::parse(&field.attrs);
+        if attrs.skip || attrs.flatten {
+            panic!("skip and flatten are not supported in FromRow");
+        }
+
+        let col_name = attrs
+            .rename
+            .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+        let field_desc = format!("{}::{}", st_name, ident);
+
+        where_clause
+            .predicates
+            .push(parse_quote! { rsfbclient::Column: rsfbclient::ColumnToVal<#ty> });
+
+        let missing = if attrs.default {
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: std::default::Default });
+
+            quote! { std::default::Default::default() }
+        } else {
+            quote! { return Err(cols.err_missing(#col_name, #field_desc)) }
+        };
+
+        st_fields_vals.push(quote! {
+            #ident: match cols.take::<#ty>(#col_name) {
+                Some(val) => val?,
+                None => #missing,
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics rsfbclient::FromRow for #st_name #ty_generics #where_clause {
+            fn try_from(row: Vec<rsfbclient::Column>) -> Result<Self, rsfbclient::FbError> {
+                let mut cols = rsfbclient::NamedColumns::new(row);
+
+                Ok(Self {
+                    #(#st_fields_vals),*
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! `IntoParams` derive
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DataStruct, DeriveInput, Fields, Index};
+
+use crate::attrs::FieldAttrs;
+
+pub fn derive(input: DeriveInput) -> TokenStream {
+    let st_name = &input.ident;
+    let (st_fields, named) = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => (&fields.named, true),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) => (&fields.unnamed, false),
+        _ => panic!("expected a struct with fields"),
+    };
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    let mut st_fields_params = Vec::with_capacity(st_fields.len());
+
+    for (idx, field) in st_fields.iter().enumerate() {
+        let ty = &field.ty;
+        let attrs = FieldAttrs::parse(&field.attrs);
+
+        if attrs.skip {
+            continue;
+        }
+        if attrs.default {
+            panic!("default is not supported in IntoParams");
+        }
+
+        let member = match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let idx = Index::from(idx);
+                quote! { #idx }
+            }
+        };
+
+        if attrs.flatten {
+            if attrs.rename.is_some() {
+                panic!("rename can't be used with flatten");
+            }
+
+            // The kind of the parameters is checked by the trait bound,
+            // so a mismatch fails to compile
+            let extend = if named {
+                where_clause
+                    .predicates
+                    .push(parse_quote! { #ty: rsfbclient::IntoNamedParams });
+
+                quote! {
+                    params.extend(rsfbclient::IntoNamedParams::to_named_params(self.#member));
+                }
+            } else {
+                where_clause
+                    .predicates
+                    .push(parse_quote! { #ty: rsfbclient::IntoPositionalParams });
+
+                quote! {
+                    params.extend(rsfbclient::IntoPositionalParams::to_positional_params(
+                        self.#member,
+                    ));
+                }
+            };
+
+            st_fields_params.push(extend);
+            continue;
+        }
+
+        where_clause
+            .predicates
+            .push(parse_quote! { #ty: rsfbclient::IntoParam });
+
+        if named {
+            let param_name = attrs.rename.unwrap_or_else(|| {
+                field
+                    .ident
+                    .as_ref()
+                    .expect("Field name required")
+                    .to_string()
+                    .trim_start_matches("r#")
+                    .to_string()
+            });
+
+            st_fields_params.push(quote! {
+                params.insert(
+                    #param_name.to_string(),
+                    rsfbclient::IntoParam::into_param(self.#member),
+                );
+            });
+        } else {
+            if attrs.rename.is_some() {
+                panic!("rename can't be used in tuple structs, as the parameters are positional");
+            }
+
+            st_fields_params.push(quote! {
+                params.push(rsfbclient::IntoParam::into_param(self.#member));
+            });
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (params_trait, params_fn, params_type, new_params, into_params_type) = if named {
+        (
+            quote! { IntoNamedParams },
+            quote! { to_named_params },
+            quote! { std::collections::HashMap<String, rsfbclient::SqlType> },
+            quote! { std::collections::HashMap::new() },
+            quote! { ParamsType::Named },
+        )
+    } else {
+        (
+            quote! { IntoPositionalParams },
+            quote! { to_positional_params },
+            quote! { Vec<rsfbclient::SqlType> },
+            quote! { Vec::new() },
+            quote! { ParamsType::Positional },
+        )
+    };
+
+    quote! {
+        impl #impl_generics rsfbclient::IntoParams for #st_name #ty_generics #where_clause {
+            fn to_params(self) -> rsfbclient::ParamsType {
+                rsfbclient::#into_params_type(rsfbclient::#params_trait::#params_fn(self))
+            }
+        }
+
+        impl #impl_generics rsfbclient::#params_trait for #st_name #ty_generics #where_clause {
+            #[allow(unused_mut)]
+            fn #params_fn(self) -> #params_type {
+                let mut params = #new_params;
+
+                #(#st_fields_params)*
+
+                params
+            }
+        }
+    }
+}
@@ -2,14 +2,19 @@
 
 extern crate proc_macro;
 
+mod attrs;
+mod column_value;
+mod from_row;
+mod into_params;
+
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Data, DataStruct, DeriveInput, Fields};
+use syn::DeriveInput;
 
 /// Derive an [IntoParams<T>](../trait.IntoParams.html) implementation for structs.
 ///
 /// This enables passing an instance of such a struct in places where named parameters
 /// are expected, using the field labels to associate field values with parameter names.
+/// Tuple structs are passed as positional parameters, in the order of the fields.
 ///
 /// The fields' types must implement the [IntoParam<T>](../trait.IntoParam.html) trait.
 ///
@@ -17,40 +22,59 @@ use syn::{Data, DataStruct, DeriveInput, Fields};
 ///
 /// Providing an instance of the struct with value `None` for such a field corresponds to
 /// passing a `null` value for that field.
-#[proc_macro_derive(IntoParams)]
+///
+/// Field attributes:
+/// - `#[rsfbclient(rename = "PARAM")]`: use other name for the parameter
+/// - `#[rsfbclient(skip)]`: don't pass the field as a parameter
+/// - `#[rsfbclient(flatten)]`: pass the parameters of a field that implements `IntoNamedParams`
+///   in structs with named fields, or `IntoPositionalParams` in tuple structs, like
+///   other structs with this derive
+#[proc_macro_derive(IntoParams, attributes(rsfbclient))]
 pub fn into_params_derive(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as DeriveInput);
 
-    let st_name = &input.ident;
-    let st_fields = match &input.data {
-        Data::Struct(DataStruct {
-            fields: Fields::Named(fields),
-            ..
-        }) => &fields.named,
-        _ => panic!("expected a struct with named fields"),
-    };
-    let st_fields_params = st_fields
-        .iter()
-        .map(|field| field.ident.as_ref().expect("Field name required"))
-        .map(|field| {
-            let field_str = field.to_string();
-            quote! { #field_str.to_string(), self.#field.into_param() }
-        });
-
-    let st_impl = quote! {
-        impl rsfbclient::IntoParams for #st_name {
-            fn to_params(self) -> rsfbclient::ParamsType {
-                use std::collections::HashMap;
-                use rsfbclient::{IntoParams, IntoParam, ParamsType};
-
-                let mut params = HashMap::new();
-
-                #(params.insert(#st_fields_params));*;
-
-                ParamsType::Named(params)
-            }
-        }
-    };
-
-    TokenStream::from(st_impl)
+    TokenStream::from(into_params::derive(input))
+}
+
+/// Derive a [FromRow](../trait.FromRow.html) implementation for structs.
+///
+/// Each field is read from the column with the same name, ignoring the case,
+/// so the order of the columns in the sql doesn't matter.
+///
+/// The fields' types must implement `ColumnToVal<T>`, and `Option<T>` can be used
+/// for nullable columns. A column not found in the row returns an error, unless
+/// the field has the `default` attribute.
+///
+/// Field attributes:
+/// - `#[rsfbclient(rename = "COLUMN")]`: read from a column with other name
+/// - `#[rsfbclient(default)]`: use `Default::default()` if the column is not in the row
+#[proc_macro_derive(FromRow, attributes(rsfbclient))]
+pub fn from_row_derive(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    TokenStream::from(from_row::derive(input))
+}
+
+/// Derive an [IntoParam](../trait.IntoParam.html) implementation, to use the type as a parameter.
+///
+/// Supports:
+/// - Structs with a single field, passed as the field value
+/// - Enums without fields, passed as the discriminant integer, or as text
+///   if all variants have a `#[rsfbclient(text = "A")]` attribute
+#[proc_macro_derive(IntoParam, attributes(rsfbclient))]
+pub fn into_param_derive(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    TokenStream::from(column_value::derive_into_param(input))
+}
+
+/// Derive a `ColumnToVal<T>` implementation, to read the type from a column.
+///
+/// Supports the same types of the [IntoParam](derive.IntoParam.html) derive,
+/// returning an error if the column value doesn't match any enum variant.
+#[proc_macro_derive(FromColumn, attributes(rsfbclient))]
+pub fn from_column_derive(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    TokenStream::from(column_value::derive_from_column(input))
 }
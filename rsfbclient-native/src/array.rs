@@ -0,0 +1,182 @@
+//!
+//! Rust Firebird Client
+//!
+//! Array slices read / write
+//!
+
+use rsfbclient_core::{
+    array::{ArrayBound, ArrayDesc, SqlArray},
+    Charset, FbError,
+};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    ffi::CString,
+    ptr,
+};
+
+use crate::{ibase, ibase::IBase, status::Status, xsqlda::xsqlvar_name};
+
+/// Get the table and column names of a XSQLVAR, used to find the array descriptor
+pub fn xsqlvar_names(var: &ibase::XSQLVAR) -> Result<(String, String), FbError> {
+    Ok((
+        xsqlvar_name(&var.relname, var.relname_length)?,
+        xsqlvar_name(&var.sqlname, var.sqlname_length)?,
+    ))
+}
+
+/// Descriptors of the array columns, by the table and column names
+pub type ArrayDescs = HashMap<(String, String), ArrayDesc>;
+
+/// Descriptor of an array column, looked up only in the first use in the statement
+pub fn lookup_desc_cached<'d, T: IBase>(
+    array_descs: &'d mut ArrayDescs,
+    relation: &str,
+    field: &str,
+    db: &mut ibase::isc_db_handle,
+    tr: &mut ibase::isc_tr_handle,
+    ibase: &T,
+) -> Result<&'d ArrayDesc, FbError> {
+    Ok(
+        match array_descs.entry((relation.to_string(), field.to_string())) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(lookup_desc(relation, field, db, tr, ibase)?),
+        },
+    )
+}
+
+/// Lookup the element type and bounds of an array column
+fn lookup_desc<T: IBase>(
+    relation: &str,
+    field: &str,
+    db: &mut ibase::isc_db_handle,
+    tr: &mut ibase::isc_tr_handle,
+    ibase: &T,
+) -> Result<ArrayDesc, FbError> {
+    let mut status = Status::default();
+
+    let mut desc = ibase::ISC_ARRAY_DESC {
+        array_desc_dtype: 0,
+        array_desc_scale: 0,
+        array_desc_length: 0,
+        array_desc_field_name: [0; 32],
+        array_desc_relation_name: [0; 32],
+        array_desc_dimensions: 0,
+        array_desc_flags: 0,
+        array_desc_bounds: [ibase::ISC_ARRAY_BOUND {
+            array_bound_lower: 0,
+            array_bound_upper: 0,
+        }; 16],
+    };
+
+    let c_relation = CString::new(relation)
+        .map_err(|_| FbError::from("The table name can't contain null characters"))?;
+    let c_field = CString::new(field)
+        .map_err(|_| FbError::from("The column name can't contain null characters"))?;
+
+    unsafe {
+        if ibase.isc_array_lookup_bounds()(
+            &mut status[0],
+            db,
+            tr,
+            c_relation.as_ptr(),
+            c_field.as_ptr(),
+            &mut desc,
+        ) != 0
+        {
+            return Err(status.as_error(ibase));
+        }
+    }
+
+    let dimensions = usize::min(desc.array_desc_dimensions as usize, 16);
+
+    Ok(ArrayDesc {
+        dtype: desc.array_desc_dtype,
+        scale: desc.array_desc_scale,
+        length: desc.array_desc_length,
+        relation: relation.to_string(),
+        field: field.to_string(),
+        bounds: desc.array_desc_bounds[..dimensions]
+            .iter()
+            .map(|b| ArrayBound::new(b.array_bound_lower as i32, b.array_bound_upper as i32))
+            .collect(),
+    })
+}
+
+/// Read all elements of an array
+pub fn read_array<T: IBase>(
+    mut array_id: ibase::ISC_QUAD,
+    desc: &ArrayDesc,
+    db: &mut ibase::isc_db_handle,
+    tr: &mut ibase::isc_tr_handle,
+    ibase: &T,
+    charset: &Charset,
+) -> Result<SqlArray, FbError> {
+    let mut status = Status::default();
+
+    let sdl = desc.sdl(&desc.bounds)?;
+    let mut data = vec![0_u8; desc.slice_length(&desc.bounds)?];
+    let mut returned = 0;
+
+    unsafe {
+        if ibase.isc_get_slice()(
+            &mut status[0],
+            db,
+            tr,
+            &mut array_id,
+            sdl.len() as i16,
+            sdl.as_ptr() as _,
+            0,
+            ptr::null(),
+            data.len() as ibase::ISC_LONG,
+            data.as_mut_ptr() as _,
+            &mut returned,
+        ) != 0
+        {
+            return Err(status.as_error(ibase));
+        }
+    }
+
+    data.truncate(returned as usize);
+
+    desc.decode_slice(desc.bounds.clone(), &data, charset)
+}
+
+/// Write the elements to a new array, returning the array id
+pub fn write_array<T: IBase>(
+    array: &SqlArray,
+    desc: &ArrayDesc,
+    db: &mut ibase::isc_db_handle,
+    tr: &mut ibase::isc_tr_handle,
+    ibase: &T,
+    charset: &Charset,
+) -> Result<ibase::ISC_QUAD, FbError> {
+    let mut status = Status::default();
+
+    let (bounds, mut data) = desc.encode_slice(array, charset)?;
+    let sdl = desc.sdl(&bounds)?;
+
+    let mut array_id = ibase::ISC_QUAD {
+        gds_quad_high: 0,
+        gds_quad_low: 0,
+    };
+
+    unsafe {
+        if ibase.isc_put_slice()(
+            &mut status[0],
+            db,
+            tr,
+            &mut array_id,
+            sdl.len() as i16,
+            sdl.as_ptr() as _,
+            0,
+            ptr::null(),
+            data.len() as ibase::ISC_LONG,
+            data.as_mut_ptr() as _,
+        ) != 0
+        {
+            return Err(status.as_error(ibase));
+        }
+    }
+
+    Ok(array_id)
+}
@@ -1,20 +1,25 @@
 //! `FirebirdConnection` implementation for the native fbclient
 
 use crate::{
+    array::ArrayDescs,
     ibase::{self, IBase},
     params::Params,
     row::ColumnBuffer,
     status::Status,
-    xsqlda::XSqlDa,
+    xsqlda::{xsqlvar_meta, XSqlDa},
 };
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use rsfbclient_core::*;
+use rsfbclient_core::{
+    date_time::{TimeZoneNames, TIME_ZONES_SQL},
+    *,
+};
 use std::ffi::CString;
 use std::os::raw::c_char;
 use std::{convert::TryFrom, io::Cursor, ptr, str};
 
 type NativeDbHandle = ibase::isc_db_handle;
 type NativeTrHandle = ibase::isc_tr_handle;
+type NativeBlobHandle = ibase::isc_blob_handle;
 type NativeStmtHandle = ibase::isc_stmt_handle;
 
 /// Client that wraps the native fbclient library
@@ -22,6 +27,12 @@ pub struct NativeFbClient<T: LinkageMarker> {
     ibase: T::L,
     status: Status,
     charset: Charset,
+    /// Fetch the blob columns without the data
+    lazy_blobs: bool,
+    /// Warnings returned by the server, not yet taken
+    warnings: Vec<FbWarning>,
+    /// Names of the time zone regions, loaded in the first value with a region
+    time_zones: Option<TimeZoneNames>,
 }
 
 /// The remote part of native client configuration
@@ -40,6 +51,8 @@ pub struct StmtHandleData {
     xsqlda: XSqlDa,
     /// Buffers for the output xsqlda
     col_buffers: Vec<ColumnBuffer>,
+    /// Descriptors of the array columns and parameters, looked up in the first use
+    array_descs: ArrayDescs,
 }
 
 ///The common part of native client configuration (for both embedded/remote)
@@ -73,6 +86,9 @@ impl DynLink {
             ibase: ibase::IBaseLinking,
             status: Default::default(),
             charset: self.0.clone(),
+            lazy_blobs: false,
+            warnings: Vec::new(),
+            time_zones: None,
         };
         result
     }
@@ -100,6 +116,9 @@ impl DynLoad {
             ibase: load_result,
             status: Default::default(),
             charset: self.charset.clone(),
+            lazy_blobs: false,
+            warnings: Vec::new(),
+            time_zones: None,
         };
 
         Ok(result)
@@ -137,6 +156,7 @@ impl<T: LinkageMarker> FirebirdClientDbOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             }
         }
+        self.keep_warnings();
 
         // Assert that the handle is valid
         debug_assert_ne!(handle, 0);
@@ -193,18 +213,24 @@ impl<T: LinkageMarker> FirebirdClientDbOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             }
         }
+        self.keep_warnings();
 
         // Assert that the handle is valid
         debug_assert_ne!(handle, 0);
 
         Ok(handle)
     }
+
+    fn take_warnings(&mut self) -> Vec<FbWarning> {
+        std::mem::take(&mut self.warnings)
+    }
 }
 
 impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
     type DbHandle = NativeDbHandle;
     type TrHandle = NativeTrHandle;
     type StmtHandle = StmtHandleData;
+    type BlobHandle = NativeBlobHandle;
 
     fn begin_transaction(
         &mut self,
@@ -252,6 +278,7 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             }
         }
+        self.keep_warnings();
 
         // Assert that the handle is valid
         debug_assert_ne!(handle, 0);
@@ -282,9 +309,83 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             }
         }
+        self.keep_warnings();
+
+        Ok(())
+    }
+
+    fn prepare_transaction(
+        &mut self,
+        tr_handle: &mut Self::TrHandle,
+        message: &[u8],
+    ) -> Result<(), FbError> {
+        let len = u16::try_from(message.len())
+            .map_err(|_| FbError::from("The prepare message is too long"))?;
+
+        unsafe {
+            if self.ibase.isc_prepare_transaction2()(
+                &mut self.status[0],
+                tr_handle,
+                len,
+                message.as_ptr(),
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+        self.keep_warnings();
+
         Ok(())
     }
 
+    fn limbo_transactions(&mut self, db_handle: &mut Self::DbHandle) -> Result<Vec<u64>, FbError> {
+        let mut buffer = vec![0_u8; LIMBO_INFO_BUFFER_LENGTH];
+
+        unsafe {
+            if self.ibase.isc_database_info()(
+                &mut self.status[0],
+                db_handle,
+                LIMBO_INFO.len() as i16,
+                LIMBO_INFO.as_ptr() as *const c_char,
+                buffer.len() as i16,
+                buffer.as_mut_ptr() as *mut c_char,
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+
+        parse_limbo_transactions(&buffer)
+    }
+
+    fn reconnect_transaction(
+        &mut self,
+        db_handle: &mut Self::DbHandle,
+        id: u64,
+    ) -> Result<Self::TrHandle, FbError> {
+        let mut handle = 0;
+        let id = reconnect_transaction_id(id);
+
+        unsafe {
+            if self.ibase.isc_reconnect_transaction()(
+                &mut self.status[0],
+                db_handle,
+                &mut handle,
+                id.len() as i16,
+                id.as_ptr() as *const c_char,
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+        self.keep_warnings();
+
+        // Assert that the handle is valid
+        debug_assert_ne!(handle, 0);
+
+        Ok(handle)
+    }
+
     fn exec_immediate(
         &mut self,
         db_handle: &mut Self::DbHandle,
@@ -308,6 +409,8 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             }
         }
+        self.keep_warnings();
+
         Ok(())
     }
 
@@ -345,6 +448,7 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
             {
                 return Err(self.status.as_error(&self.ibase));
             }
+            self.keep_warnings();
 
             let row_count = xsqlda.sqld;
 
@@ -404,10 +508,68 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
                 handle,
                 xsqlda,
                 col_buffers,
+                array_descs: Default::default(),
             },
         ))
     }
 
+    fn describe_columns(
+        &mut self,
+        stmt_handle: &mut Self::StmtHandle,
+    ) -> Result<Vec<ColumnMeta>, FbError> {
+        Ok(stmt_handle
+            .col_buffers
+            .iter()
+            .map(|col| col.meta().clone())
+            .collect())
+    }
+
+    fn describe_params(
+        &mut self,
+        stmt_handle: &mut Self::StmtHandle,
+    ) -> Result<Vec<ColumnMeta>, FbError> {
+        let mut xsqlda = XSqlDa::new(1);
+
+        unsafe {
+            if self.ibase.isc_dsql_describe_bind()(
+                &mut self.status[0],
+                &mut stmt_handle.handle,
+                1,
+                &mut *xsqlda,
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+
+            let param_count = xsqlda.sqld;
+
+            if param_count > xsqlda.sqln {
+                // Need more XSQLVARs
+                xsqlda = XSqlDa::new(param_count);
+
+                if self.ibase.isc_dsql_describe_bind()(
+                    &mut self.status[0],
+                    &mut stmt_handle.handle,
+                    1,
+                    &mut *xsqlda,
+                ) != 0
+                {
+                    return Err(self.status.as_error(&self.ibase));
+                }
+            }
+        }
+
+        (0..xsqlda.sqld as usize)
+            .map(|col| {
+                let var = xsqlda
+                    .get_xsqlvar_mut(col)
+                    .ok_or_else(|| FbError::from("Error getting the xsqlvar"))?;
+
+                xsqlvar_meta(var)
+            })
+            .collect()
+    }
+
     fn free_statement(
         &mut self,
         stmt_handle: &mut Self::StmtHandle,
@@ -438,10 +600,10 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
             db_handle,
             tr_handle,
             &self.ibase,
-            &mut self.status,
             &mut stmt_handle.handle,
             params,
             &self.charset,
+            &mut stmt_handle.array_descs,
         )?;
 
         unsafe {
@@ -460,6 +622,7 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             }
         }
+        self.keep_warnings();
 
         // Just to make sure the params are not dropped too soon
         drop(params);
@@ -540,12 +703,23 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             };
         }
+        self.keep_warnings();
 
-        let cols = stmt_handle
+        let mut cols = stmt_handle
             .col_buffers
             .iter()
-            .map(|cb| cb.to_column(db_handle, tr_handle, &self.ibase, &self.charset))
-            .collect::<Result<_, _>>()?;
+            .map(|cb| {
+                cb.to_column(
+                    db_handle,
+                    tr_handle,
+                    &self.ibase,
+                    &self.charset,
+                    self.lazy_blobs,
+                    &mut stmt_handle.array_descs,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.set_time_zone_names(db_handle, tr_handle, &stmt_handle.col_buffers, &mut cols)?;
 
         Ok(Some(cols))
     }
@@ -561,10 +735,10 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
             db_handle,
             tr_handle,
             &self.ibase,
-            &mut self.status,
             &mut stmt_handle.handle,
             params,
             &self.charset,
+            &mut stmt_handle.array_descs,
         )?;
 
         unsafe {
@@ -584,18 +758,200 @@ impl<T: LinkageMarker> FirebirdClientSqlOps for NativeFbClient<T> {
                 return Err(self.status.as_error(&self.ibase));
             }
         }
+        self.keep_warnings();
 
         // Just to make sure the params are not dropped too soon
         drop(params);
 
-        let rcol = stmt_handle
+        let mut rcol = stmt_handle
             .col_buffers
             .iter()
-            .map(|cb| cb.to_column(db_handle, tr_handle, &self.ibase, &self.charset))
-            .collect::<Result<_, _>>()?;
+            .map(|cb| {
+                cb.to_column(
+                    db_handle,
+                    tr_handle,
+                    &self.ibase,
+                    &self.charset,
+                    self.lazy_blobs,
+                    &mut stmt_handle.array_descs,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.set_time_zone_names(db_handle, tr_handle, &stmt_handle.col_buffers, &mut rcol)?;
 
         Ok(rcol)
     }
+
+    fn set_lazy_blobs(&mut self, lazy_blobs: bool) {
+        self.lazy_blobs = lazy_blobs;
+    }
+
+    fn create_blob(
+        &mut self,
+        db_handle: &mut Self::DbHandle,
+        tr_handle: &mut Self::TrHandle,
+    ) -> Result<(Self::BlobHandle, BlobId), FbError> {
+        let mut handle = 0;
+        let mut blob_id = ibase::ISC_QUAD {
+            gds_quad_high: 0,
+            gds_quad_low: 0,
+        };
+
+        unsafe {
+            if self.ibase.isc_create_blob2()(
+                &mut self.status[0],
+                db_handle,
+                tr_handle,
+                &mut handle,
+                &mut blob_id,
+                blob::STREAM_BLOB_BPB.len() as i16,
+                blob::STREAM_BLOB_BPB.as_ptr() as *const c_char,
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+
+        // Assert that the handle is valid
+        debug_assert_ne!(handle, 0);
+
+        Ok((handle, blob_id.into()))
+    }
+
+    fn open_blob(
+        &mut self,
+        db_handle: &mut Self::DbHandle,
+        tr_handle: &mut Self::TrHandle,
+        blob_id: BlobId,
+    ) -> Result<Self::BlobHandle, FbError> {
+        let mut handle = 0;
+        let mut blob_id = ibase::ISC_QUAD::from(blob_id);
+
+        unsafe {
+            if self.ibase.isc_open_blob()(
+                &mut self.status[0],
+                db_handle,
+                tr_handle,
+                &mut handle,
+                &mut blob_id,
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+
+        // Assert that the handle is valid
+        debug_assert_ne!(handle, 0);
+
+        Ok(handle)
+    }
+
+    fn get_segment(
+        &mut self,
+        blob_handle: &mut Self::BlobHandle,
+    ) -> Result<(Vec<u8>, bool), FbError> {
+        let mut buffer = vec![0_u8; u16::MAX as usize];
+        let mut loaded = 0;
+
+        let blob_stat = unsafe {
+            self.ibase.isc_get_segment()(
+                &mut self.status[0],
+                blob_handle,
+                &mut loaded,
+                buffer.len() as u16,
+                buffer.as_mut_ptr() as *mut c_char,
+            )
+        };
+
+        // A partially read segment is not an error, the rest is returned in the next call
+        if blob_stat != 0
+            && self.status[1] != ibase::isc_segment as isize
+            && self.status[1] != ibase::isc_segstr_eof as isize
+        {
+            return Err(self.status.as_error(&self.ibase));
+        }
+
+        buffer.truncate(loaded as usize);
+
+        Ok((buffer, self.status[1] == ibase::isc_segstr_eof as isize))
+    }
+
+    fn put_segment(
+        &mut self,
+        blob_handle: &mut Self::BlobHandle,
+        data: &[u8],
+    ) -> Result<(), FbError> {
+        // Max segment size: 65535
+        for b in data.chunks(u16::MAX as usize) {
+            unsafe {
+                if self.ibase.isc_put_segment()(
+                    &mut self.status[0],
+                    blob_handle,
+                    b.len() as u16,
+                    b.as_ptr() as *const c_char,
+                ) != 0
+                {
+                    return Err(self.status.as_error(&self.ibase));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn seek_blob(
+        &mut self,
+        blob_handle: &mut Self::BlobHandle,
+        mode: BlobSeekMode,
+        offset: i32,
+    ) -> Result<(), FbError> {
+        let mut position = 0;
+
+        unsafe {
+            if self.ibase.isc_seek_blob()(
+                &mut self.status[0],
+                blob_handle,
+                mode as i16,
+                offset as ibase::ISC_LONG,
+                &mut position,
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn blob_length(&mut self, blob_handle: &mut Self::BlobHandle) -> Result<u64, FbError> {
+        let mut buffer = [0_u8; 32];
+
+        unsafe {
+            if self.ibase.isc_blob_info()(
+                &mut self.status[0],
+                blob_handle,
+                blob::BLOB_LENGTH_INFO.len() as i16,
+                blob::BLOB_LENGTH_INFO.as_ptr() as *const c_char,
+                buffer.len() as i16,
+                buffer.as_mut_ptr() as *mut c_char,
+            ) != 0
+            {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+
+        blob::parse_blob_length(&buffer)
+    }
+
+    fn close_blob(&mut self, blob_handle: &mut Self::BlobHandle) -> Result<(), FbError> {
+        unsafe {
+            if self.ibase.isc_close_blob()(&mut self.status[0], blob_handle) != 0 {
+                return Err(self.status.as_error(&self.ibase));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: LinkageMarker> FirebirdClientDbEvents for NativeFbClient<T> {
@@ -673,6 +1029,62 @@ impl<T: LinkageMarker> FirebirdClientDbEvents for NativeFbClient<T> {
 }
 
 impl<T: LinkageMarker> NativeFbClient<T> {
+    /// Keep the warning returned by the last successful call, if any
+    fn keep_warnings(&mut self) {
+        if let Some(warning) = self.status.warning(&self.ibase) {
+            add_warnings(&mut self.warnings, Some(warning));
+        }
+    }
+
+    /// Set the names of the time zone regions in the timestamp columns,
+    /// loading the names from the database in the first value with a region
+    fn set_time_zone_names(
+        &mut self,
+        db_handle: &mut NativeDbHandle,
+        tr_handle: &mut NativeTrHandle,
+        col_buffers: &[ColumnBuffer],
+        cols: &mut [Column],
+    ) -> Result<(), FbError> {
+        for (cb, col) in col_buffers.iter().zip(cols.iter_mut()) {
+            let time_zone = match cb.time_zone_region() {
+                Some(time_zone) => time_zone,
+                None => continue,
+            };
+
+            if self.time_zones.is_none() {
+                let (_, mut stmt_handle) =
+                    self.prepare_statement(db_handle, tr_handle, Dialect::D3, TIME_ZONES_SQL)?;
+
+                let rows = self
+                    .execute(db_handle, tr_handle, &mut stmt_handle, vec![])
+                    .and_then(|_| {
+                        let mut rows = vec![];
+                        while let Some(row) = self.fetch(db_handle, tr_handle, &mut stmt_handle)? {
+                            rows.push(row);
+                        }
+                        Ok(rows)
+                    });
+
+                // Freed before returning any error, to not leak the statement
+                let freed = self.free_statement(&mut stmt_handle, FreeStmtOp::Drop);
+                let rows = rows?;
+                freed?;
+
+                self.time_zones = Some(TimeZoneNames::from_rows(rows)?);
+            }
+
+            if let SqlType::TimestampTz(_, region) = &mut col.value {
+                *region = self
+                    .time_zones
+                    .as_ref()
+                    .and_then(|names| names.get(time_zone))
+                    .map(str::to_string);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Build the dpb and the connection string
     ///
     /// Used by attach database operations
@@ -135,16 +135,16 @@ parse_functions! {
     //         arg7: *mut ISC_LONG,
     //     ) -> ISC_STATUS;
     // }
-    // extern "C" {
-    //     pub fn isc_array_lookup_bounds(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_db_handle,
-    //         arg3: *mut isc_tr_handle,
-    //         arg4: *const ISC_SCHAR,
-    //         arg5: *const ISC_SCHAR,
-    //         arg6: *mut ISC_ARRAY_DESC,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_array_lookup_bounds(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_db_handle,
+            arg3: *mut isc_tr_handle,
+            arg4: *const ISC_SCHAR,
+            arg5: *const ISC_SCHAR,
+            arg6: *mut ISC_ARRAY_DESC,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_array_lookup_desc(
     //         arg1: *mut ISC_STATUS,
@@ -194,16 +194,16 @@ parse_functions! {
     //         arg6: *mut ::std::os::raw::c_ushort,
     //     ) -> ISC_STATUS;
     // }
-    // extern "C" {
-    //     pub fn isc_blob_info(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_blob_handle,
-    //         arg3: ::std::os::raw::c_short,
-    //         arg4: *const ISC_SCHAR,
-    //         arg5: ::std::os::raw::c_short,
-    //         arg6: *mut ISC_SCHAR,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_blob_info(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_blob_handle,
+            arg3: ::std::os::raw::c_short,
+            arg4: *const ISC_SCHAR,
+            arg5: ::std::os::raw::c_short,
+            arg6: *mut ISC_SCHAR,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_blob_lookup_desc(
     //         arg1: *mut ISC_STATUS,
@@ -254,17 +254,17 @@ parse_functions! {
             arg5: *mut ISC_QUAD,
         ) -> ISC_STATUS;
     }
-    // extern "C" {
-    //     pub fn isc_create_blob2(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_db_handle,
-    //         arg3: *mut isc_tr_handle,
-    //         arg4: *mut isc_blob_handle,
-    //         arg5: *mut ISC_QUAD,
-    //         arg6: ::std::os::raw::c_short,
-    //         arg7: *const ISC_SCHAR,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_create_blob2(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_db_handle,
+            arg3: *mut isc_tr_handle,
+            arg4: *mut isc_blob_handle,
+            arg5: *mut ISC_QUAD,
+            arg6: ::std::os::raw::c_short,
+            arg7: *const ISC_SCHAR,
+        ) -> ISC_STATUS;
+    }
     extern "C" {
         pub fn isc_create_database(
            arg1: *mut ISC_STATUS,
@@ -276,16 +276,16 @@ parse_functions! {
            arg7: ::std::os::raw::c_short,
         ) -> ISC_STATUS;
     }
-    // extern "C" {
-    //     pub fn isc_database_info(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_db_handle,
-    //         arg3: ::std::os::raw::c_short,
-    //         arg4: *const ISC_SCHAR,
-    //         arg5: ::std::os::raw::c_short,
-    //         arg6: *mut ISC_SCHAR,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_database_info(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_db_handle,
+            arg3: ::std::os::raw::c_short,
+            arg4: *const ISC_SCHAR,
+            arg5: ::std::os::raw::c_short,
+            arg6: *mut ISC_SCHAR,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_decode_date(arg1: *const ISC_QUAD, arg2: *mut ::std::os::raw::c_void);
     // }
@@ -500,21 +500,21 @@ parse_functions! {
             arg5: *mut ISC_SCHAR,
         ) -> ISC_STATUS;
     }
-    // extern "C" {
-    //     pub fn isc_get_slice(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_db_handle,
-    //         arg3: *mut isc_tr_handle,
-    //         arg4: *mut ISC_QUAD,
-    //         arg5: ::std::os::raw::c_short,
-    //         arg6: *const ISC_SCHAR,
-    //         arg7: ::std::os::raw::c_short,
-    //         arg8: *const ISC_LONG,
-    //         arg9: ISC_LONG,
-    //         arg10: *mut ::std::os::raw::c_void,
-    //         arg11: *mut ISC_LONG,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_get_slice(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_db_handle,
+            arg3: *mut isc_tr_handle,
+            arg4: *mut ISC_QUAD,
+            arg5: ::std::os::raw::c_short,
+            arg6: *const ISC_SCHAR,
+            arg7: ::std::os::raw::c_short,
+            arg8: *const ISC_LONG,
+            arg9: ISC_LONG,
+            arg10: *mut ::std::os::raw::c_void,
+            arg11: *mut ISC_LONG,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_interprete(arg1: *mut ISC_SCHAR, arg2: *mut *mut ISC_STATUS) -> ISC_LONG;
     // }
@@ -545,14 +545,14 @@ parse_functions! {
     //         arg7: *const ISC_UCHAR,
     //     ) -> ISC_STATUS;
     // }
-    // extern "C" {
-    //     pub fn isc_prepare_transaction2(
-    //         arg1: *mut ISC_STATUS_ARRAY,
-    //         arg2: *mut isc_tr_handle,
-    //         arg3: ISC_USHORT,
-    //         arg4: *const ISC_UCHAR,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_prepare_transaction2(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_tr_handle,
+            arg3: ISC_USHORT,
+            arg4: *const ISC_UCHAR,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_print_sqlerror(arg1: ISC_SHORT, arg2: *const ISC_STATUS);
     // }
@@ -567,20 +567,20 @@ parse_functions! {
             arg4: *const ISC_SCHAR,
         ) -> ISC_STATUS;
     }
-    // extern "C" {
-    //     pub fn isc_put_slice(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_db_handle,
-    //         arg3: *mut isc_tr_handle,
-    //         arg4: *mut ISC_QUAD,
-    //         arg5: ::std::os::raw::c_short,
-    //         arg6: *const ISC_SCHAR,
-    //         arg7: ::std::os::raw::c_short,
-    //         arg8: *const ISC_LONG,
-    //         arg9: ISC_LONG,
-    //         arg10: *mut ::std::os::raw::c_void,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_put_slice(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_db_handle,
+            arg3: *mut isc_tr_handle,
+            arg4: *mut ISC_QUAD,
+            arg5: ::std::os::raw::c_short,
+            arg6: *const ISC_SCHAR,
+            arg7: ::std::os::raw::c_short,
+            arg8: *const ISC_LONG,
+            arg9: ISC_LONG,
+            arg10: *mut ::std::os::raw::c_void,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_que_events(
     //         arg1: *mut ISC_STATUS,
@@ -713,15 +713,15 @@ parse_functions! {
     //         arg6: ::std::os::raw::c_short,
     //     ) -> ISC_STATUS;
     // }
-    // extern "C" {
-    //     pub fn isc_reconnect_transaction(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_db_handle,
-    //         arg3: *mut isc_tr_handle,
-    //         arg4: ::std::os::raw::c_short,
-    //         arg5: *const ISC_SCHAR,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_reconnect_transaction(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_db_handle,
+            arg3: *mut isc_tr_handle,
+            arg4: ::std::os::raw::c_short,
+            arg5: *const ISC_SCHAR,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_release_request(arg1: *mut ISC_STATUS, arg2: *mut isc_req_handle) -> ISC_STATUS;
     // }
@@ -736,15 +736,15 @@ parse_functions! {
     //         arg7: *mut ISC_SCHAR,
     //     ) -> ISC_STATUS;
     // }
-    // extern "C" {
-    //     pub fn isc_seek_blob(
-    //         arg1: *mut ISC_STATUS,
-    //         arg2: *mut isc_blob_handle,
-    //         arg3: ::std::os::raw::c_short,
-    //         arg4: ISC_LONG,
-    //         arg5: *mut ISC_LONG,
-    //     ) -> ISC_STATUS;
-    // }
+    extern "C" {
+        pub fn isc_seek_blob(
+            arg1: *mut ISC_STATUS,
+            arg2: *mut isc_blob_handle,
+            arg3: ::std::os::raw::c_short,
+            arg4: ISC_LONG,
+            arg5: *mut ISC_LONG,
+        ) -> ISC_STATUS;
+    }
     // extern "C" {
     //     pub fn isc_send(
     //         arg1: *mut ISC_STATUS,
@@ -1,5 +1,6 @@
 //! `FirebirdConnection` implementation for the native fbclient
 
+pub(crate) mod array;
 mod connection;
 pub(crate) mod ibase;
 pub(crate) mod params;
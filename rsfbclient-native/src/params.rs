@@ -1,11 +1,15 @@
 use std::{mem, ptr};
 
 use crate::{
+    array,
     ibase::{self, IBase},
     status::Status,
     xsqlda::XSqlDa,
 };
-use rsfbclient_core::{Charset, FbError, SqlType, MAX_TEXT_LENGTH};
+use rsfbclient_core::{
+    charset::OCTETS_ID, coerce_param, BlobRef, Charset, ColumnMeta, FbError, SqlType,
+    MAX_TEXT_LENGTH,
+};
 
 use ParamBufferData::*;
 
@@ -24,12 +28,13 @@ impl Params {
         db_handle: &mut ibase::isc_db_handle,
         tr_handle: &mut ibase::isc_tr_handle,
         ibase: &T,
-        status: &mut Status,
         stmt_handle: &mut ibase::isc_stmt_handle,
         infos: Vec<SqlType>,
         charset: &Charset,
+        array_descs: &mut array::ArrayDescs,
     ) -> Result<Self, FbError> {
         let params = if !infos.is_empty() {
+            let mut status = Status::default();
             let mut xsqlda = XSqlDa::new(infos.len() as i16);
 
             let ok = unsafe {
@@ -50,15 +55,23 @@ impl Params {
             let mut buffers = vec![];
 
             for (col, info) in infos.into_iter().enumerate() {
+                let var = xsqlda
+                    .get_xsqlvar_mut(col)
+                    .ok_or_else(|| FbError::from("Error getting the xsqlvar"))?;
+
+                // Convert to the described type, before it is overwritten
+                let meta =
+                    ColumnMeta::from_type(var.sqltype, var.sqlscale, var.sqlsubtype, var.sqllen);
+                let info = coerce_param(col, info, &meta)?;
+
                 buffers.push(ParamBuffer::from_parameter(
                     info,
-                    xsqlda
-                        .get_xsqlvar_mut(col)
-                        .ok_or_else(|| FbError::from("Error getting the xsqlvar"))?,
+                    var,
                     db_handle,
                     tr_handle,
                     ibase,
                     charset,
+                    array_descs,
                 )?);
             }
 
@@ -120,16 +133,38 @@ enum ParamBufferData {
 
     Integer(Box<i64>),
 
+    /// Only works in fb >= 4.0
+    Int128(Box<i128>),
+
+    /// Decimal128 in the DPD encoding. Only works in fb >= 4.0
+    DecFloat34(Box<u128>),
+
     Floating(Box<f64>),
 
     Timestamp(Box<ibase::ISC_TIMESTAMP>),
 
+    Date(Box<ibase::ISC_DATE>),
+
+    Time(Box<ibase::ISC_TIME>),
+
+    /// Only works in fb >= 4.0
+    TimestampTz(Box<ibase::ISC_TIMESTAMP_TZ>),
+
+    /// Only works in fb >= 4.0
+    TimeTz(Box<ibase::ISC_TIME_TZ>),
+
     Null,
 
     Binary(Box<[u8]>),
 
     /// Only works in fb >= 3.0
     Boolean(Box<i8>),
+
+    /// Array id
+    Array(Box<ibase::GDS_QUAD_t>),
+
+    /// Blob id
+    BlobId(Box<ibase::GDS_QUAD_t>),
 }
 
 impl ParamBufferData {
@@ -138,11 +173,19 @@ impl ParamBufferData {
         match self {
             Text(s) => s.as_ptr() as _,
             Integer(i) => &**i as *const _ as _,
+            Int128(i) => &**i as *const _ as _,
+            DecFloat34(d) => &**d as *const _ as _,
             Floating(f) => &**f as *const _ as _,
             Timestamp(ts) => &**ts as *const _ as _,
+            Date(d) => &**d as *const _ as _,
+            Time(t) => &**t as *const _ as _,
+            TimestampTz(ts) => &**ts as *const _ as _,
+            TimeTz(t) => &**t as *const _ as _,
             Null => ptr::null_mut(),
             Binary(b) => b.as_ptr() as _,
             Boolean(b) => &**b as *const _ as _,
+            Array(id) => &**id as *const _ as _,
+            BlobId(id) => &**id as *const _ as _,
         }
     }
 }
@@ -156,9 +199,31 @@ impl ParamBuffer {
         tr: &mut ibase::isc_tr_handle,
         ibase: &T,
         charset: &Charset,
+        array_descs: &mut array::ArrayDescs,
     ) -> Result<Self, FbError> {
         let mut null = 0;
 
+        // Column related to the parameter, before the type is overwritten
+        let array_desc = if let SqlType::Array(_) = info {
+            let (relation, field) = array::xsqlvar_names(var)?;
+
+            Some(array::lookup_desc_cached(
+                array_descs,
+                &relation,
+                &field,
+                db,
+                tr,
+                ibase,
+            )?)
+        } else {
+            None
+        };
+
+        // The binary data is sent as text to the `OCTETS` parameters, instead of a blob
+        let octets = matches!(&info, SqlType::Binary(b) if b.len() <= MAX_TEXT_LENGTH)
+            && ColumnMeta::from_type(var.sqltype, var.sqlscale, var.sqlsubtype, var.sqllen)
+                .is_octets();
+
         let (sqltype, sqlsubtype) = info.sql_type_and_subtype();
         var.sqltype = sqltype as i16;
         var.sqlsubtype = sqlsubtype as i16;
@@ -181,23 +246,95 @@ impl ParamBuffer {
 
             SqlType::Floating(f) => (mem::size_of_val(&f), Floating(Box::new(f))),
 
+            SqlType::Int128(i) => (mem::size_of_val(&i), Int128(Box::new(i))),
+
+            SqlType::Decimal(d) => {
+                var.sqlscale = d.scale;
+
+                match i64::try_from(d.value) {
+                    Ok(i) => (mem::size_of_val(&i), Integer(Box::new(i))),
+                    Err(_) => (mem::size_of_val(&d.value), Int128(Box::new(d.value))),
+                }
+            }
+
+            SqlType::DecimalFloat(d) => {
+                let bits = d.to_dpd128()?;
+
+                (mem::size_of_val(&bits), DecFloat34(Box::new(bits)))
+            }
+
             SqlType::Timestamp(dt) => {
                 let ts = rsfbclient_core::date_time::encode_timestamp(dt);
 
                 (mem::size_of_val(&ts), Timestamp(Box::new(ts)))
             }
 
+            SqlType::Date(d) => {
+                let date = rsfbclient_core::date_time::encode_date(d);
+
+                (mem::size_of_val(&date), Date(Box::new(date)))
+            }
+
+            SqlType::Time(t) => {
+                let time = rsfbclient_core::date_time::encode_time(t);
+
+                (mem::size_of_val(&time), Time(Box::new(time)))
+            }
+
+            SqlType::TimestampTz(_, Some(_)) => {
+                // Converted to text by `coerce_param`, as the region ids are not known here
+                return Err("A timestamp with a time zone region must be sent as text".into());
+            }
+
+            SqlType::TimestampTz(dt, None) => {
+                let ts = rsfbclient_core::date_time::encode_timestamp_tz(dt);
+
+                (mem::size_of_val(&ts), TimestampTz(Box::new(ts)))
+            }
+
+            SqlType::TimeTz(t) => {
+                let time = rsfbclient_core::date_time::encode_time_tz(t);
+
+                (mem::size_of_val(&time), TimeTz(Box::new(time)))
+            }
+
             SqlType::Null => {
                 null = -1;
                 (0, Null)
             }
 
+            SqlType::Binary(bin) if octets => {
+                var.sqltype = ibase::SQL_TEXT as i16 + 1;
+                var.sqlsubtype = OCTETS_ID as i16;
+
+                (bin.len(), Text(bin.into_boxed_slice()))
+            }
+
             SqlType::Binary(bin) => {
                 let bytes = binary_to_blob(&bin, db, tr, ibase)?;
                 (bytes.len(), Binary(bytes.into_boxed_slice()))
             }
 
             SqlType::Boolean(bo) => (mem::size_of::<i8>(), Boolean(Box::new(bo as i8))),
+
+            SqlType::Array(arr) => {
+                let desc = array_desc.ok_or_else(|| FbError::from("Array descriptor not found"))?;
+                let id = array::write_array(&arr, desc, db, tr, ibase, charset)?;
+
+                (mem::size_of_val(&id), Array(Box::new(id)))
+            }
+
+            SqlType::List(_) => {
+                return Err(
+                    "List parameters must be expanded before preparing the statement".into(),
+                )
+            }
+
+            SqlType::Blob(id) | SqlType::LazyBlob(BlobRef { id, .. }) => {
+                let id = ibase::ISC_QUAD::from(id);
+
+                (mem::size_of_val(&id), BlobId(Box::new(id)))
+            }
         };
 
         let mut nullind = Box::new(null);
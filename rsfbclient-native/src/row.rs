@@ -4,10 +4,10 @@
 //! Representation of a fetched row
 //!
 
-use rsfbclient_core::{Charset, Column, FbError, SqlType};
-use std::{mem, result::Result};
+use rsfbclient_core::{BlobRef, Charset, Column, ColumnMeta, DecFloat, FbError, Numeric, SqlType};
+use std::{mem, result::Result, sync::Arc};
 
-use crate::{ibase, ibase::IBase, status::Status, varchar::Varchar};
+use crate::{array, ibase, ibase::IBase, status::Status, varchar::Varchar, xsqlda::xsqlvar_meta};
 
 use ColumnBufferData::*;
 
@@ -16,30 +16,59 @@ use ColumnBufferData::*;
 pub enum ColumnBufferData {
     /// Coerces to Varchar
     Text(Varchar),
+    /// Coerces to Varchar, with the binary data of the `OCTETS` charset
+    Octets(Varchar),
     /// Coerces to Int64
     Integer(Box<i64>),
+    /// Coerces to Int64, keeping the scale
+    Decimal(Box<i64>, i16),
+    /// Int128, keeping the scale. Fb >= 4
+    Int128(Box<i128>, i16),
+    /// Decimal64 in the DPD encoding. Fb >= 4
+    DecFloat16(Box<u64>),
+    /// Decimal128 in the DPD encoding. Fb >= 4
+    DecFloat34(Box<u128>),
     /// Coerces to Double
     Float(Box<f64>),
     /// Coerces to Timestamp
     Timestamp(Box<ibase::ISC_TIMESTAMP>),
-    /// Coerces to Blob sub_type 1
-    BlobText(Box<ibase::GDS_QUAD_t>),
-    /// Coerces to Blob sub_type 0
+    /// Date only
+    Date(Box<ibase::ISC_DATE>),
+    /// Time only
+    Time(Box<ibase::ISC_TIME>),
+    /// Coerces to Blob sub_type 1, with the charset of the column if known
+    BlobText(Box<ibase::GDS_QUAD_t>, Option<Charset>),
+    /// Coerces to Blob of the other sub_types
     BlobBinary(Box<ibase::GDS_QUAD_t>),
+    /// Coerces to the extended timestamp with time zone. Fb >= 4
+    TimestampTz(Box<ibase::ISC_TIMESTAMP_TZ_EX>),
+    /// Coerces to the extended time with time zone. Fb >= 4
+    TimeTz(Box<ibase::ISC_TIME_TZ_EX>),
     /// Coerces to boolean. Fb >= 3
     Boolean(Box<i8>),
+    /// Array id, with the table and column names to find the array descriptor
+    Array(Box<ibase::GDS_QUAD_t>, String, String),
 }
 
 impl ColumnBufferData {
     fn as_mut_ptr(&mut self) -> *mut ibase::ISC_SCHAR {
         match self {
-            Text(v) => v.as_ptr() as _,
+            Text(v) | Octets(v) => v.as_ptr() as _,
             Integer(i) => &**i as *const _ as _,
+            Decimal(i, _) => &**i as *const _ as _,
+            Int128(i, _) => &**i as *const _ as _,
+            DecFloat16(d) => &**d as *const _ as _,
+            DecFloat34(d) => &**d as *const _ as _,
             Float(f) => &**f as *const _ as _,
             Timestamp(ts) => &**ts as *const _ as _,
-            BlobText(bid) => &**bid as *const _ as _,
+            Date(d) => &**d as *const _ as _,
+            Time(t) => &**t as *const _ as _,
+            TimestampTz(ts) => &**ts as *const _ as _,
+            TimeTz(t) => &**t as *const _ as _,
+            BlobText(bid, _) => &**bid as *const _ as _,
             BlobBinary(bid) => &**bid as *const _ as _,
             Boolean(b) => &**b as *const _ as _,
+            Array(id, ..) => &**id as *const _ as _,
         }
     }
 }
@@ -53,19 +82,34 @@ pub struct ColumnBuffer {
     /// Null indicator
     nullind: Box<i16>,
 
-    /// Column name
-    col_name: String,
-
-    raw_type: i16,
+    /// Description of the column
+    meta: Arc<ColumnMeta>,
 }
 
 impl ColumnBuffer {
+    /// Id of the time zone region of the value, if it is a
+    /// timestamp with time zone in a region
+    pub fn time_zone_region(&self) -> Option<u16> {
+        match &self.buffer {
+            TimestampTz(ts)
+                if *self.nullind == 0
+                    && rsfbclient_core::date_time::is_time_zone_region(ts.time_zone) =>
+            {
+                Some(ts.time_zone)
+            }
+            _ => None,
+        }
+    }
+
     /// Allocate a buffer from an output (column) XSQLVAR, coercing the data types as necessary
     pub fn from_xsqlvar(var: &mut ibase::XSQLVAR) -> Result<Self, FbError> {
         // Remove nullable type indicator
         let sqltype = var.sqltype & (!1);
         let sqlsubtype = var.sqlsubtype;
 
+        // Read before the coercions
+        let meta = Arc::new(xsqlvar_meta(var)?);
+
         let mut nullind = Box::new(0);
         var.sqlind = &mut *nullind;
 
@@ -78,7 +122,7 @@ impl ColumnBuffer {
                 Boolean(Box::new(0))
             }
 
-            ibase::SQL_BLOB if (sqlsubtype <= 1) => {
+            ibase::SQL_BLOB => {
                 let blob_id = Box::new(ibase::GDS_QUAD_t {
                     gds_quad_high: 0,
                     gds_quad_low: 0,
@@ -86,36 +130,64 @@ impl ColumnBuffer {
 
                 var.sqltype = ibase::SQL_BLOB as i16 + 1;
 
+                // subtype 1: text, with the charset id in the scale
                 // subtype 0: binary
                 // subtype <= -1: custom
-                if sqlsubtype <= 0 {
-                    BlobBinary(blob_id)
+                // subtype >= 2: internal
+                if sqlsubtype == 1 {
+                    BlobText(blob_id, Charset::from_id(var.sqlscale as u8))
                 } else {
-                    BlobText(blob_id)
+                    BlobBinary(blob_id)
                 }
             }
 
             ibase::SQL_TEXT | ibase::SQL_VARYING => {
                 var.sqltype = ibase::SQL_VARYING as i16 + 1;
 
-                Text(Varchar::new(var.sqllen as u16))
+                if meta.is_octets() {
+                    Octets(Varchar::new(var.sqllen as u16))
+                } else {
+                    Text(Varchar::new(var.sqllen as u16))
+                }
             }
 
             ibase::SQL_SHORT | ibase::SQL_LONG | ibase::SQL_INT64 => {
                 var.sqllen = mem::size_of::<i64>() as i16;
 
-                if var.sqlscale == 0 {
-                    var.sqltype = ibase::SQL_INT64 as i16 + 1;
+                var.sqltype = ibase::SQL_INT64 as i16 + 1;
 
+                if var.sqlscale == 0 {
                     Integer(Box::new(0))
                 } else {
-                    var.sqlscale = 0;
-                    var.sqltype = ibase::SQL_DOUBLE as i16 + 1;
-
-                    Float(Box::new(0.0))
+                    // Decimal or numeric value, keep the scale to return an exact value
+                    Decimal(Box::new(0), var.sqlscale)
                 }
             }
 
+            ibase::SQL_INT128 => {
+                var.sqllen = mem::size_of::<i128>() as i16;
+
+                var.sqltype = ibase::SQL_INT128 as i16 + 1;
+
+                Int128(Box::new(0), var.sqlscale)
+            }
+
+            ibase::SQL_DEC16 => {
+                var.sqllen = mem::size_of::<u64>() as i16;
+
+                var.sqltype = ibase::SQL_DEC16 as i16 + 1;
+
+                DecFloat16(Box::new(0))
+            }
+
+            ibase::SQL_DEC34 => {
+                var.sqllen = mem::size_of::<u128>() as i16;
+
+                var.sqltype = ibase::SQL_DEC34 as i16 + 1;
+
+                DecFloat34(Box::new(0))
+            }
+
             ibase::SQL_FLOAT | ibase::SQL_DOUBLE => {
                 var.sqllen = mem::size_of::<i64>() as i16;
 
@@ -124,7 +196,7 @@ impl ColumnBuffer {
                 Float(Box::new(0.0))
             }
 
-            ibase::SQL_TIMESTAMP | ibase::SQL_TYPE_DATE | ibase::SQL_TYPE_TIME => {
+            ibase::SQL_TIMESTAMP => {
                 var.sqllen = mem::size_of::<ibase::ISC_TIMESTAMP>() as i16;
 
                 var.sqltype = ibase::SQL_TIMESTAMP as i16 + 1;
@@ -135,6 +207,65 @@ impl ColumnBuffer {
                 }))
             }
 
+            ibase::SQL_TYPE_DATE => {
+                var.sqllen = mem::size_of::<ibase::ISC_DATE>() as i16;
+
+                var.sqltype = ibase::SQL_TYPE_DATE as i16 + 1;
+
+                Date(Box::new(0))
+            }
+
+            ibase::SQL_TYPE_TIME => {
+                var.sqllen = mem::size_of::<ibase::ISC_TIME>() as i16;
+
+                var.sqltype = ibase::SQL_TYPE_TIME as i16 + 1;
+
+                Time(Box::new(0))
+            }
+
+            ibase::SQL_TIMESTAMP_TZ | ibase::SQL_TIMESTAMP_TZ_EX => {
+                // The extended type includes the offset of the region time zones
+                var.sqllen = mem::size_of::<ibase::ISC_TIMESTAMP_TZ_EX>() as i16;
+
+                var.sqltype = ibase::SQL_TIMESTAMP_TZ_EX as i16 + 1;
+
+                TimestampTz(Box::new(ibase::ISC_TIMESTAMP_TZ_EX {
+                    utc_timestamp: ibase::ISC_TIMESTAMP {
+                        timestamp_date: 0,
+                        timestamp_time: 0,
+                    },
+                    time_zone: 0,
+                    ext_offset: 0,
+                }))
+            }
+
+            ibase::SQL_TIME_TZ | ibase::SQL_TIME_TZ_EX => {
+                var.sqllen = mem::size_of::<ibase::ISC_TIME_TZ_EX>() as i16;
+
+                var.sqltype = ibase::SQL_TIME_TZ_EX as i16 + 1;
+
+                TimeTz(Box::new(ibase::ISC_TIME_TZ_EX {
+                    utc_time: 0,
+                    time_zone: 0,
+                    ext_offset: 0,
+                }))
+            }
+
+            ibase::SQL_ARRAY => {
+                let (relation, field) = array::xsqlvar_names(var)?;
+
+                var.sqltype = ibase::SQL_ARRAY as i16 + 1;
+
+                Array(
+                    Box::new(ibase::GDS_QUAD_t {
+                        gds_quad_high: 0,
+                        gds_quad_low: 0,
+                    }),
+                    relation,
+                    field,
+                )
+            }
+
             sqltype => {
                 return Err(format!("Unsupported column type ({} {})", sqltype, sqlsubtype).into())
             }
@@ -142,21 +273,10 @@ impl ColumnBuffer {
 
         var.sqldata = buffer.as_mut_ptr();
 
-        let col_name = {
-            let len = usize::min(var.aliasname_length as usize, var.aliasname.len());
-            let bname = var.aliasname[..len]
-                .iter()
-                .map(|b| *b as u8)
-                .collect::<Vec<u8>>();
-
-            String::from_utf8(bname)
-        }?;
-
         Ok(ColumnBuffer {
             buffer,
             nullind,
-            col_name,
-            raw_type: sqltype,
+            meta,
         })
     }
 
@@ -167,36 +287,84 @@ impl ColumnBuffer {
         tr: &mut ibase::isc_tr_handle,
         ibase: &T,
         charset: &Charset,
+        lazy_blobs: bool,
+        array_descs: &mut array::ArrayDescs,
     ) -> Result<Column, FbError> {
         if *self.nullind != 0 {
-            return Ok(Column::new(
-                self.col_name.clone(),
-                self.raw_type as u32,
-                SqlType::Null,
-            ));
+            return Ok(self.column(SqlType::Null));
         }
 
         let col_type = match &self.buffer {
             Text(varchar) => SqlType::Text(charset.decode(varchar.as_bytes())?),
 
+            Octets(varchar) => SqlType::Binary(varchar.as_bytes().to_vec()),
+
             Integer(i) => SqlType::Integer(**i),
 
+            Decimal(i, scale) => SqlType::Decimal(Numeric::new(**i as i128, *scale)),
+
+            Int128(i, 0) => SqlType::Int128(**i),
+
+            Int128(i, scale) => SqlType::Decimal(Numeric::new(**i, *scale)),
+
+            DecFloat16(d) => SqlType::DecimalFloat(DecFloat::from_dpd64(**d)),
+
+            DecFloat34(d) => SqlType::DecimalFloat(DecFloat::from_dpd128(**d)),
+
             Float(f) => SqlType::Floating(**f),
 
             Timestamp(ts) => SqlType::Timestamp(rsfbclient_core::date_time::decode_timestamp(**ts)),
 
-            BlobText(b) => SqlType::Text(blobtext_to_string(**b, db, tr, ibase, charset)?),
+            Date(d) => SqlType::Date(rsfbclient_core::date_time::decode_date(**d)),
+
+            Time(t) => SqlType::Time(rsfbclient_core::date_time::decode_time(**t)),
+
+            TimestampTz(ts) => {
+                SqlType::TimestampTz(rsfbclient_core::date_time::decode_timestamp_tz(**ts)?, None)
+            }
+
+            TimeTz(t) => SqlType::TimeTz(rsfbclient_core::date_time::decode_time_tz(**t)?),
+
+            BlobText(b, col_charset) if lazy_blobs => SqlType::LazyBlob(BlobRef {
+                id: (**b).into(),
+                charset: Some(col_charset.as_ref().unwrap_or(charset).clone()),
+            }),
+
+            BlobBinary(b) if lazy_blobs => SqlType::LazyBlob(BlobRef {
+                id: (**b).into(),
+                charset: None,
+            }),
+
+            BlobText(b, col_charset) => SqlType::Text(blobtext_to_string(
+                **b,
+                db,
+                tr,
+                ibase,
+                col_charset.as_ref().unwrap_or(charset),
+            )?),
 
             BlobBinary(b) => SqlType::Binary(blobbinary_to_vec(**b, db, tr, ibase)?),
 
             Boolean(b) => SqlType::Boolean(**b != 0),
+
+            Array(id, relation, field) => {
+                let desc = array::lookup_desc_cached(array_descs, relation, field, db, tr, ibase)?;
+
+                SqlType::Array(array::read_array(**id, desc, db, tr, ibase, charset)?)
+            }
         };
 
-        Ok(Column::new(
-            self.col_name.clone(),
-            self.raw_type as u32,
-            col_type,
-        ))
+        Ok(self.column(col_type))
+    }
+
+    /// Description of the column
+    pub fn meta(&self) -> &ColumnMeta {
+        &self.meta
+    }
+
+    /// Column with the value and the description of this buffer
+    fn column(&self, value: SqlType) -> Column {
+        Column::new(self.meta.alias.clone(), self.meta.sql_type, value).with_meta(self.meta.clone())
     }
 }
 
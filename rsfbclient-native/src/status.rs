@@ -1,15 +1,27 @@
 //! Status of connetions, transactions...
 
 pub use rsfbclient_core::FbError;
+use rsfbclient_core::{FbWarning, StatusArg, StatusEntry, StatusVector};
 use std::{
+    ffi::CStr,
     fmt::Write,
     ops::{Deref, DerefMut},
+    os::raw::c_char,
 };
 
 use crate::ibase::{self, IBase};
 
 pub struct Status(Box<ibase::ISC_STATUS_ARRAY>);
 
+#[derive(Default)]
+/// Items of the status vector
+struct ParsedStatus {
+    errors: StatusVector,
+    warnings: StatusVector,
+    /// Index of the first warning item
+    warning_start: Option<usize>,
+}
+
 impl Default for Status {
     fn default() -> Self {
         Status(Box::new([0; 20]))
@@ -37,10 +49,15 @@ impl Status {
     }
 
     pub fn message<T: IBase>(&self, ibase: &T) -> String {
+        self.interpret(ibase, 0)
+    }
+
+    /// Build the message of the items starting at `start`
+    fn interpret<T: IBase>(&self, ibase: &T, start: usize) -> String {
         let mut buffer: Vec<u8> = Vec::with_capacity(256);
         let mut msg = String::new();
 
-        let mut ptr = self.0.as_ptr();
+        let mut ptr = self.0[start..].as_ptr();
 
         loop {
             unsafe {
@@ -69,10 +86,117 @@ impl Status {
         msg
     }
 
+    /// Parse the error codes and arguments of the status vector
+    pub fn status_vector(&self) -> StatusVector {
+        self.parse().errors
+    }
+
+    /// Warning returned by the last successful call, if any
+    pub fn warning<T: IBase>(&self, ibase: &T) -> Option<FbWarning> {
+        let parsed = self.parse();
+
+        parsed.warning_start.map(|start| FbWarning {
+            msg: self.interpret(ibase, start),
+            status: parsed.warnings,
+        })
+    }
+
+    /// Split the items of the status vector in errors and warnings
+    fn parse(&self) -> ParsedStatus {
+        let mut parsed = ParsedStatus::default();
+        // True after the first warning item
+        let mut in_warning = false;
+
+        // Read a C string pointed by an item
+        let read_str = |ptr: ibase::ISC_STATUS| {
+            if ptr == 0 {
+                String::new()
+            } else {
+                unsafe { CStr::from_ptr(ptr as *const c_char) }
+                    .to_string_lossy()
+                    .into_owned()
+            }
+        };
+
+        let items = &self.0[..];
+        let mut i = 0;
+
+        while i + 1 < items.len() {
+            let (kind, value) = (items[i] as u32, items[i + 1]);
+
+            if kind == ibase::isc_arg_warning && !in_warning {
+                in_warning = true;
+                parsed.warning_start = Some(i);
+            }
+            i += 2;
+
+            let vector = if in_warning {
+                &mut parsed.warnings
+            } else {
+                &mut parsed.errors
+            };
+
+            match kind {
+                ibase::isc_arg_end => break,
+
+                ibase::isc_arg_gds | ibase::isc_arg_warning => {
+                    // A zero code only marks a success
+                    if value != 0 {
+                        vector.errors.push(StatusEntry {
+                            gds_code: value as u32,
+                            args: vec![],
+                        })
+                    }
+                }
+
+                ibase::isc_arg_interpreted => vector.errors.push(StatusEntry {
+                    gds_code: 0,
+                    args: vec![StatusArg::Text(read_str(value))],
+                }),
+
+                ibase::isc_arg_sql_state => vector.sql_state = Some(read_str(value)),
+
+                ibase::isc_arg_string => {
+                    if let Some(err) = vector.errors.last_mut() {
+                        err.args.push(StatusArg::Text(read_str(value)));
+                    }
+                }
+
+                ibase::isc_arg_cstring => {
+                    // Length, then the pointer
+                    let len = value as usize;
+                    let ptr = items.get(i).copied().unwrap_or(0);
+                    i += 1;
+
+                    if let Some(err) = vector.errors.last_mut() {
+                        let text = if ptr == 0 {
+                            String::new()
+                        } else {
+                            let bytes =
+                                unsafe { std::slice::from_raw_parts(ptr as *const u8, len) };
+                            String::from_utf8_lossy(bytes).into_owned()
+                        };
+                        err.args.push(StatusArg::Text(text));
+                    }
+                }
+
+                // Numbers and os error codes
+                _ => {
+                    if let Some(err) = vector.errors.last_mut() {
+                        err.args.push(StatusArg::Number(value as i32));
+                    }
+                }
+            }
+        }
+
+        parsed
+    }
+
     pub fn as_error<T: IBase>(&self, ibase: &T) -> FbError {
         FbError::Sql {
             code: self.sql_code(ibase),
             msg: self.message(ibase),
+            status: self.status_vector(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use rsfbclient_core::{ColumnMeta, FbError};
 use std::{
     alloc,
     ops::{Deref, DerefMut},
@@ -69,6 +70,26 @@ impl Drop for XSqlDa {
     }
 }
 
+/// Read a name of a XSQLVAR
+pub fn xsqlvar_name(bytes: &[ibase::ISC_SCHAR], len: ibase::ISC_SHORT) -> Result<String, FbError> {
+    let len = usize::min(len as usize, bytes.len());
+
+    Ok(String::from_utf8(
+        bytes[..len].iter().map(|b| *b as u8).collect(),
+    )?)
+}
+
+/// Description of a XSQLVAR, must be read before the coercions
+pub fn xsqlvar_meta(var: &ibase::XSQLVAR) -> Result<ColumnMeta, FbError> {
+    Ok(ColumnMeta {
+        alias: xsqlvar_name(&var.aliasname, var.aliasname_length)?,
+        field: xsqlvar_name(&var.sqlname, var.sqlname_length)?,
+        relation: xsqlvar_name(&var.relname, var.relname_length)?,
+        owner: xsqlvar_name(&var.ownname, var.ownname_length)?,
+        ..ColumnMeta::from_type(var.sqltype, var.sqlscale, var.sqlsubtype, var.sqllen)
+    })
+}
+
 /// Calculates the memory layout (size and alignment) for a xsqlda
 fn xsqlda_layout(len: i16) -> alloc::Layout {
     let (xsqlda_layout, _) = alloc::Layout::new::<ibase::XSQLDA>()
@@ -1,6 +1,9 @@
-use crate::{client::FirebirdWireConnection, consts};
+use crate::{
+    client::{ArrayDescs, FirebirdWireConnection},
+    consts,
+};
 use bytes::{BufMut, Bytes, BytesMut};
-use rsfbclient_core::{FbError, SqlType};
+use rsfbclient_core::{charset::OCTETS_ID, coerce_param, BlobRef, ColumnMeta, FbError, SqlType};
 
 /// Maximum parameter data length
 pub const MAX_DATA_LENGTH: usize = 32767;
@@ -14,12 +17,26 @@ pub struct ParamsBlr {
     pub(crate) values: Bytes,
 }
 
-/// Convert the parameters to a blr (binary representation)
+/// Convert the parameters to a blr (binary representation),
+/// coercing to the types described by the server
 pub fn params_to_blr(
     conn: &mut FirebirdWireConnection,
     tr_handle: &mut crate::TrHandle,
-    params: &[SqlType],
+    params: Vec<SqlType>,
+    described: &[ColumnMeta],
+    array_descs: &mut ArrayDescs,
 ) -> Result<ParamsBlr, FbError> {
+    if params.len() != described.len() {
+        return Err("Error getting the parameters description".into());
+    }
+
+    let params = params
+        .into_iter()
+        .zip(described)
+        .enumerate()
+        .map(|(i, (p, meta))| coerce_param(i, p, meta))
+        .collect::<Result<Vec<_>, _>>()?;
+
     let mut blr = BytesMut::with_capacity(256);
     let mut values = BytesMut::with_capacity(256);
 
@@ -34,14 +51,15 @@ pub fn params_to_blr(
 
     if conn.version >= consts::ProtocolVersion::V13 {
         // Insert a null indicator bitmap
-        null_bitmap(&mut values, params);
+        null_bitmap(&mut values, &params);
     }
 
     // Handle blob creation and blr conversion
-    let mut handle_blob = |conn: &mut FirebirdWireConnection,
-                           blr: &mut BytesMut,
-                           values: &mut BytesMut,
-                           data: &[u8]| {
+    let handle_blob = |conn: &mut FirebirdWireConnection,
+                       tr_handle: &mut crate::TrHandle,
+                       blr: &mut BytesMut,
+                       values: &mut BytesMut,
+                       data: &[u8]| {
         let (blob_handle, id) = conn.create_blob(tr_handle)?;
 
         conn.put_segments(blob_handle, data)?;
@@ -56,13 +74,13 @@ pub fn params_to_blr(
         Ok::<_, FbError>(())
     };
 
-    for p in params {
+    for (i, p) in params.iter().enumerate() {
         match p {
             SqlType::Text(s) => {
                 let bytes = conn.charset.encode(s)?;
                 if bytes.len() > MAX_DATA_LENGTH {
                     // Data too large, send as blob
-                    handle_blob(conn, &mut blr, &mut values, &bytes)?;
+                    handle_blob(conn, tr_handle, &mut blr, &mut values, &bytes)?;
                 } else {
                     blr.put_u8(consts::blr::TEXT);
                     blr.put_u16_le(bytes.len() as u16);
@@ -75,7 +93,20 @@ pub fn params_to_blr(
                 }
             }
 
-            SqlType::Binary(data) => handle_blob(conn, &mut blr, &mut values, data)?,
+            // Sent as text to the `OCTETS` parameters, instead of a blob
+            SqlType::Binary(data) if described[i].is_octets() && data.len() <= MAX_DATA_LENGTH => {
+                blr.put_u8(consts::blr::TEXT2);
+                blr.put_u16_le(OCTETS_ID as u16);
+                blr.put_u16_le(data.len() as u16);
+
+                values.put_slice(data);
+                if data.len() % 4 != 0 {
+                    // 4 byte align
+                    values.put_slice(&[0; 4][..4 - (data.len() % 4)])
+                }
+            }
+
+            SqlType::Binary(data) => handle_blob(conn, tr_handle, &mut blr, &mut values, data)?,
 
             SqlType::Integer(i) => {
                 blr.put_slice(&[
@@ -86,6 +117,35 @@ pub fn params_to_blr(
                 values.put_i64(*i);
             }
 
+            SqlType::Int128(i) => {
+                blr.put_slice(&[
+                    consts::blr::INT128,
+                    0, // Scale
+                ]);
+
+                values.put_i128(*i);
+            }
+
+            SqlType::Decimal(d) => match i64::try_from(d.value) {
+                Ok(i) => {
+                    blr.put_slice(&[consts::blr::INT64, d.scale as u8]);
+
+                    values.put_i64(i);
+                }
+                Err(_) => {
+                    // Only fits in the fb 4.0 `NUMERIC(38, x)`
+                    blr.put_slice(&[consts::blr::INT128, d.scale as u8]);
+
+                    values.put_i128(d.value);
+                }
+            },
+
+            SqlType::DecimalFloat(d) => {
+                blr.put_u8(consts::blr::DEC128);
+
+                values.put_u128(d.to_dpd128()?);
+            }
+
             SqlType::Floating(f) => {
                 blr.put_u8(consts::blr::DOUBLE);
 
@@ -100,12 +160,70 @@ pub fn params_to_blr(
                 values.put_u32(ts.timestamp_time);
             }
 
+            SqlType::Date(d) => {
+                blr.put_u8(consts::blr::SQL_DATE);
+
+                values.put_i32(rsfbclient_core::date_time::encode_date(*d));
+            }
+
+            SqlType::Time(t) => {
+                blr.put_u8(consts::blr::SQL_TIME);
+
+                values.put_u32(rsfbclient_core::date_time::encode_time(*t));
+            }
+
+            SqlType::TimestampTz(_, Some(_)) => {
+                // Converted to text by `coerce_param`, as the region ids are not known here
+                return Err("A timestamp with a time zone region must be sent as text".into());
+            }
+
+            SqlType::TimestampTz(dt, None) => {
+                blr.put_u8(consts::blr::TIMESTAMP_TZ);
+
+                let ts = rsfbclient_core::date_time::encode_timestamp_tz(*dt);
+                values.put_i32(ts.utc_timestamp.timestamp_date);
+                values.put_u32(ts.utc_timestamp.timestamp_time);
+                values.put_u32(ts.time_zone as u32);
+            }
+
+            SqlType::TimeTz(t) => {
+                blr.put_u8(consts::blr::SQL_TIME_TZ);
+
+                let time = rsfbclient_core::date_time::encode_time_tz(*t);
+                values.put_u32(time.utc_time);
+                values.put_u32(time.time_zone as u32);
+            }
+
             SqlType::Boolean(b) => {
                 blr.put_u8(consts::blr::BOOL);
 
                 values.put_slice(if *b { &[1, 0, 0, 0] } else { &[0, 0, 0, 0] });
             }
 
+            SqlType::Array(array) => {
+                let meta = &described[i];
+
+                let desc =
+                    conn.array_desc_cached(tr_handle, array_descs, &meta.relation, &meta.field)?;
+                let id = conn.put_array(tr_handle, desc, array)?;
+
+                blr.put_slice(&[consts::blr::QUAD, 0]);
+
+                values.put_u64(id.0);
+            }
+
+            SqlType::List(_) => {
+                return Err(
+                    "List parameters must be expanded before preparing the statement".into(),
+                )
+            }
+
+            SqlType::Blob(id) | SqlType::LazyBlob(BlobRef { id, .. }) => {
+                blr.put_slice(&[consts::blr::QUAD, 0]);
+
+                values.put_u64(id.0);
+            }
+
             SqlType::Null => {
                 // Represent as empty text
                 blr.put_u8(consts::blr::TEXT);
@@ -2,10 +2,11 @@
 
 use bytes::{BufMut, Bytes, BytesMut};
 use std::{
-    collections::VecDeque,
+    collections::{hash_map::Entry, HashMap, VecDeque},
     env,
     io::{Read, Write},
     net::TcpStream,
+    sync::Arc,
 };
 
 use crate::{
@@ -15,13 +16,21 @@ use crate::{
     srp::*,
     util::*,
     wire::*,
-    xsqlda::{parse_xsqlda, xsqlda_to_blr, PrepareInfo, XSqlVar, XSQLDA_DESCRIBE_VARS},
+    xsqlda::{
+        parse_select_items, parse_xsqlda, xsqlda_to_blr, PrepareInfo, XSqlVar,
+        XSQLDA_DESCRIBE_PARAMS, XSQLDA_DESCRIBE_VARS,
+    },
+};
+use rsfbclient_core::{
+    array::{ArrayDesc, SqlArray},
+    date_time::{TimeZoneNames, TIME_ZONES_SQL},
+    *,
 };
-use rsfbclient_core::*;
 
 type RustDbHandle = DbHandle;
 type RustTrHandle = TrHandle;
 type RustStmtHandle = StmtHandle;
+type RustBlobHandle = BlobHandle;
 
 /// How many rows to request per op_fetch (round-trip). Configurable via
 /// FB_FETCH_BATCH; defaults to 200. The crate originally used 1 (one row per round-trip).
@@ -55,6 +64,7 @@ enum FetchErr {
 pub struct RustFbClient {
     conn: Option<FirebirdWireConnection>,
     charset: Charset,
+    lazy_blobs: bool,
 }
 
 /// Required configuration for an attachment with the pure rust client
@@ -83,18 +93,39 @@ pub struct FirebirdWireConnection {
     lazy_count: u32,
 
     pub(crate) charset: Charset,
+
+    /// Handle of the attached database, used for the internal queries
+    db_handle: DbHandle,
+
+    /// Fetch the blob columns without the data
+    pub(crate) lazy_blobs: bool,
+
+    /// Warnings returned by the server, not yet taken
+    warnings: Vec<FbWarning>,
+
+    /// Names of the time zone regions, loaded in the first value with a region
+    time_zones: Option<TimeZoneNames>,
 }
 
+/// Descriptors of the array columns, by the table and column names
+pub type ArrayDescs = HashMap<(String, String), ArrayDesc>;
+
 /// Data to keep track about a prepared statement
 pub struct StmtHandleData {
     /// Statement handle
     handle: RustStmtHandle,
     /// Output xsqlda
     xsqlda: Vec<XSqlVar>,
+    /// Description of the output columns
+    columns: Vec<Arc<ColumnMeta>>,
     /// Blr representation of the above
     blr: Bytes,
     /// Number of parameters
     param_count: usize,
+    /// Description of the parameters, requested in the first execution
+    params: Option<Vec<ColumnMeta>>,
+    /// Descriptors of the array columns and parameters, looked up in the first use
+    array_descs: ArrayDescs,
     /// Rows already fetched in a batch but not yet delivered (batch fetch).
     prefetched: VecDeque<Vec<Column>>,
     /// Cursor exhausted on the server (do not request more batches).
@@ -107,6 +138,7 @@ impl RustFbClient {
         Self {
             conn: None,
             charset,
+            lazy_blobs: false,
         }
     }
 }
@@ -147,6 +179,8 @@ impl FirebirdClientDbOps for RustFbClient {
         let attach_result =
             conn.attach_database(db_name, user, pass, role, dialect, no_db_triggers);
 
+        conn.lazy_blobs = self.lazy_blobs;
+
         // Put the connection back
         self.conn.replace(conn);
 
@@ -198,17 +232,27 @@ impl FirebirdClientDbOps for RustFbClient {
 
         let attach_result = conn.create_database(db_name, user, pass, page_size, role, dialect);
 
+        conn.lazy_blobs = self.lazy_blobs;
+
         // Put the connection back
         self.conn.replace(conn);
 
         attach_result
     }
+
+    fn take_warnings(&mut self) -> Vec<FbWarning> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.take_warnings())
+            .unwrap_or_default()
+    }
 }
 
 impl FirebirdClientSqlOps for RustFbClient {
     type DbHandle = RustDbHandle;
     type TrHandle = RustTrHandle;
     type StmtHandle = StmtHandleData;
+    type BlobHandle = RustBlobHandle;
 
     fn begin_transaction(
         &mut self,
@@ -232,6 +276,35 @@ impl FirebirdClientSqlOps for RustFbClient {
             .unwrap_or_else(err_client_not_connected)
     }
 
+    fn prepare_transaction(
+        &mut self,
+        tr_handle: &mut Self::TrHandle,
+        message: &[u8],
+    ) -> Result<(), FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.prepare_transaction(tr_handle, message))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn limbo_transactions(&mut self, db_handle: &mut Self::DbHandle) -> Result<Vec<u64>, FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.limbo_transactions(db_handle))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn reconnect_transaction(
+        &mut self,
+        db_handle: &mut Self::DbHandle,
+        id: u64,
+    ) -> Result<Self::TrHandle, FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.reconnect_transaction(db_handle, id))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
     fn exec_immediate(
         &mut self,
         _db_handle: &mut Self::DbHandle,
@@ -258,6 +331,27 @@ impl FirebirdClientSqlOps for RustFbClient {
             .unwrap_or_else(err_client_not_connected)
     }
 
+    fn describe_columns(
+        &mut self,
+        stmt_handle: &mut Self::StmtHandle,
+    ) -> Result<Vec<ColumnMeta>, FbError> {
+        Ok(stmt_handle
+            .columns
+            .iter()
+            .map(|meta| ColumnMeta::clone(meta))
+            .collect())
+    }
+
+    fn describe_params(
+        &mut self,
+        stmt_handle: &mut Self::StmtHandle,
+    ) -> Result<Vec<ColumnMeta>, FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.describe_params_cached(stmt_handle).map(<[_]>::to_vec))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
     fn free_statement(
         &mut self,
         stmt_handle: &mut Self::StmtHandle,
@@ -278,7 +372,7 @@ impl FirebirdClientSqlOps for RustFbClient {
     ) -> Result<usize, FbError> {
         self.conn
             .as_mut()
-            .map(|conn| conn.execute(tr_handle, stmt_handle, &params))
+            .map(|conn| conn.execute(tr_handle, stmt_handle, params))
             .unwrap_or_else(err_client_not_connected)
     }
 
@@ -291,7 +385,7 @@ impl FirebirdClientSqlOps for RustFbClient {
     ) -> Result<Vec<Column>, FbError> {
         self.conn
             .as_mut()
-            .map(|conn| conn.execute2(tr_handle, stmt_handle, &params))
+            .map(|conn| conn.execute2(tr_handle, stmt_handle, params))
             .unwrap_or_else(err_client_not_connected)
     }
 
@@ -306,6 +400,87 @@ impl FirebirdClientSqlOps for RustFbClient {
             .map(|conn| conn.fetch(tr_handle, stmt_handle))
             .unwrap_or_else(err_client_not_connected)
     }
+
+    fn set_lazy_blobs(&mut self, lazy_blobs: bool) {
+        self.lazy_blobs = lazy_blobs;
+
+        if let Some(conn) = self.conn.as_mut() {
+            conn.lazy_blobs = lazy_blobs;
+        }
+    }
+
+    fn create_blob(
+        &mut self,
+        _db_handle: &mut Self::DbHandle,
+        tr_handle: &mut Self::TrHandle,
+    ) -> Result<(Self::BlobHandle, BlobId), FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.create_stream_blob(tr_handle))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn open_blob(
+        &mut self,
+        _db_handle: &mut Self::DbHandle,
+        tr_handle: &mut Self::TrHandle,
+        blob_id: BlobId,
+    ) -> Result<Self::BlobHandle, FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.open_blob(tr_handle, blob_id))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn get_segment(
+        &mut self,
+        blob_handle: &mut Self::BlobHandle,
+    ) -> Result<(Vec<u8>, bool), FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| {
+                conn.get_segment(*blob_handle)
+                    .map(|(data, end)| (data.to_vec(), end))
+            })
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn put_segment(
+        &mut self,
+        blob_handle: &mut Self::BlobHandle,
+        data: &[u8],
+    ) -> Result<(), FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.put_segments(*blob_handle, data))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn seek_blob(
+        &mut self,
+        blob_handle: &mut Self::BlobHandle,
+        mode: BlobSeekMode,
+        offset: i32,
+    ) -> Result<(), FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.seek_blob(*blob_handle, mode, offset))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn blob_length(&mut self, blob_handle: &mut Self::BlobHandle) -> Result<u64, FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.blob_length(*blob_handle))
+            .unwrap_or_else(err_client_not_connected)
+    }
+
+    fn close_blob(&mut self, blob_handle: &mut Self::BlobHandle) -> Result<(), FbError> {
+        self.conn
+            .as_mut()
+            .map(|conn| conn.close_blob(*blob_handle))
+            .unwrap_or_else(err_client_not_connected)
+    }
 }
 
 fn err_client_not_connected<T>() -> Result<T, FbError> {
@@ -417,6 +592,10 @@ impl FirebirdWireConnection {
             buff,
             lazy_count: 0,
             charset,
+            db_handle: DbHandle(0),
+            lazy_blobs: false,
+            warnings: Vec::new(),
+            time_zones: None,
         })
     }
 
@@ -444,7 +623,9 @@ impl FirebirdWireConnection {
 
         let resp = self.read_response()?;
 
-        Ok(DbHandle(resp.handle))
+        self.db_handle = DbHandle(resp.handle);
+
+        Ok(self.db_handle)
     }
 
     /// Connect to a database, returning a database handle
@@ -471,7 +652,9 @@ impl FirebirdWireConnection {
 
         let resp = self.read_response()?;
 
-        Ok(DbHandle(resp.handle))
+        self.db_handle = DbHandle(resp.handle);
+
+        Ok(self.db_handle)
     }
 
     /// Disconnect from the database
@@ -539,6 +722,52 @@ impl FirebirdWireConnection {
         Ok(())
     }
 
+    /// Prepare the transaction for the two-phase commit
+    pub fn prepare_transaction(
+        &mut self,
+        tr_handle: &mut TrHandle,
+        message: &[u8],
+    ) -> Result<(), FbError> {
+        self.socket
+            .write_all(&prepare_transaction(tr_handle.0, message))?;
+        self.socket.flush()?;
+
+        self.read_response()?;
+
+        Ok(())
+    }
+
+    /// Ids of the transactions in limbo in the database
+    pub fn limbo_transactions(&mut self, db_handle: &mut DbHandle) -> Result<Vec<u64>, FbError> {
+        self.socket.write_all(&info_database(
+            db_handle.0,
+            &LIMBO_INFO,
+            LIMBO_INFO_BUFFER_LENGTH as u32,
+        ))?;
+        self.socket.flush()?;
+
+        let resp = self.read_response()?;
+
+        parse_limbo_transactions(&resp.data)
+    }
+
+    /// Reconnect to a transaction in limbo
+    pub fn reconnect_transaction(
+        &mut self,
+        db_handle: &mut DbHandle,
+        id: u64,
+    ) -> Result<TrHandle, FbError> {
+        self.socket.write_all(&reconnect_transaction(
+            db_handle.0,
+            &reconnect_transaction_id(id),
+        ))?;
+        self.socket.flush()?;
+
+        let resp = self.read_response()?;
+
+        Ok(TrHandle(resp.handle))
+    }
+
     /// Execute a sql immediately, without returning rows
     pub fn exec_immediate(
         &mut self,
@@ -589,7 +818,7 @@ impl FirebirdWireConnection {
                 return err_conn_rejected(op_code);
             }
             self.lazy_count -= 1;
-            parse_response(&mut resp)?;
+            self.check_response(&mut resp)?;
 
             op_code = resp.get_u32()?;
         }
@@ -599,7 +828,7 @@ impl FirebirdWireConnection {
             return err_conn_rejected(op_code);
         }
 
-        let stmt_handle = StmtHandle(parse_response(&mut resp)?.handle);
+        let stmt_handle = StmtHandle(self.check_response(&mut resp)?.handle);
 
         // Prepare resp
         let op_code = resp.get_u32()?;
@@ -610,7 +839,7 @@ impl FirebirdWireConnection {
 
         let mut xsqlda = Vec::new();
 
-        let mut resp = parse_response(&mut resp)?;
+        let mut resp = self.check_response(&mut resp)?;
         let PrepareInfo {
             stmt_type,
             mut param_count,
@@ -643,6 +872,8 @@ impl FirebirdWireConnection {
             param_count = parse_resp.param_count;
         }
 
+        let columns = xsqlda.iter().map(|var| Arc::new(var.to_meta())).collect();
+
         // Coerce the output columns and transform to blr
         for var in xsqlda.iter_mut() {
             var.coerce()?;
@@ -654,8 +885,11 @@ impl FirebirdWireConnection {
             StmtHandleData {
                 handle: stmt_handle,
                 xsqlda,
+                columns,
                 blr,
                 param_count,
+                params: None,
+                array_descs: HashMap::new(),
                 prefetched: VecDeque::new(),
                 cursor_eof: false,
             },
@@ -682,7 +916,7 @@ impl FirebirdWireConnection {
         &mut self,
         tr_handle: &mut TrHandle,
         stmt_handle: &mut StmtHandleData,
-        params: &[SqlType],
+        params: Vec<SqlType>,
     ) -> Result<usize, FbError> {
         if params.len() != stmt_handle.param_count {
             return Err(format!(
@@ -700,7 +934,14 @@ impl FirebirdWireConnection {
         stmt_handle.cursor_eof = false;
 
         // Execute
-        let params = blr::params_to_blr(self, tr_handle, params)?;
+        self.describe_params_cached(stmt_handle)?;
+        let params = blr::params_to_blr(
+            self,
+            tr_handle,
+            params,
+            stmt_handle.params.as_deref().unwrap_or_default(),
+            &mut stmt_handle.array_descs,
+        )?;
 
         self.socket.write_all(&execute(
             tr_handle.0,
@@ -729,7 +970,7 @@ impl FirebirdWireConnection {
         &mut self,
         tr_handle: &mut TrHandle,
         stmt_handle: &mut StmtHandleData,
-        params: &[SqlType],
+        params: Vec<SqlType>,
     ) -> Result<Vec<Column>, FbError> {
         if params.len() != stmt_handle.param_count {
             return Err(format!(
@@ -745,7 +986,14 @@ impl FirebirdWireConnection {
         stmt_handle.prefetched.clear();
         stmt_handle.cursor_eof = false;
 
-        let params = blr::params_to_blr(self, tr_handle, params)?;
+        self.describe_params_cached(stmt_handle)?;
+        let params = blr::params_to_blr(
+            self,
+            tr_handle,
+            params,
+            stmt_handle.params.as_deref().unwrap_or_default(),
+            &mut stmt_handle.array_descs,
+        )?;
 
         self.socket.write_all(&execute2(
             tr_handle.0,
@@ -764,14 +1012,14 @@ impl FirebirdWireConnection {
                 return err_conn_rejected(op_code);
             }
             self.lazy_count -= 1;
-            parse_response(&mut resp)?;
+            self.check_response(&mut resp)?;
 
             op_code = resp.get_u32()?;
         }
 
         if op_code == WireOp::Response as u32 {
             // An error ocurred
-            parse_response(&mut resp)?;
+            self.check_response(&mut resp)?;
         }
 
         if op_code != WireOp::SqlResponse as u32 {
@@ -781,12 +1029,15 @@ impl FirebirdWireConnection {
         let parsed_cols =
             parse_sql_response(&mut resp, &stmt_handle.xsqlda, self.version, &self.charset)?;
 
-        parse_response(&mut resp)?;
+        self.check_response(&mut resp)?;
 
         let mut cols = Vec::with_capacity(parsed_cols.len());
 
-        for pc in parsed_cols {
-            cols.push(pc.into_column(self, tr_handle)?);
+        for (pc, meta) in parsed_cols.into_iter().zip(&stmt_handle.columns) {
+            cols.push(
+                pc.into_column(self, tr_handle, &mut stmt_handle.array_descs)?
+                    .with_meta(meta.clone()),
+            );
         }
 
         Ok(cols)
@@ -839,7 +1090,13 @@ impl FirebirdWireConnection {
             loop {
                 let snapshot = view.clone(); // O(1): Bytes shares the underlying buffer
                 let saved_lazy = self.lazy_count;
-                match self.parse_one_fetch_response(&mut view, &stmt_handle.xsqlda, tr_handle) {
+                match self.parse_one_fetch_response(
+                    &mut view,
+                    &stmt_handle.xsqlda,
+                    &stmt_handle.columns,
+                    &mut stmt_handle.array_descs,
+                    tr_handle,
+                ) {
                     Ok(FetchOne::Row(cols)) => {
                         stmt_handle.prefetched.push_back(cols);
                         got += 1;
@@ -889,6 +1146,8 @@ impl FirebirdWireConnection {
         &mut self,
         view: &mut Bytes,
         xsqlda: &[XSqlVar],
+        columns: &[Arc<ColumnMeta>],
+        array_descs: &mut ArrayDescs,
         tr_handle: &mut TrHandle,
     ) -> Result<FetchOne, FetchErr> {
         // op_code, skipping Dummy packets
@@ -919,7 +1178,7 @@ impl FirebirdWireConnection {
 
         if op_code == WireOp::Response as u32 {
             // Error reported by the server
-            parse_response(view).map_err(FetchErr::Fatal)?;
+            self.check_response(view).map_err(FetchErr::Fatal)?;
         }
 
         if op_code != WireOp::FetchResponse as u32 {
@@ -965,8 +1224,12 @@ impl FirebirdWireConnection {
             Ok(None) => Ok(FetchOne::End),
             Ok(Some(parsed)) => {
                 let mut cols = Vec::with_capacity(parsed.len());
-                for pc in parsed {
-                    cols.push(pc.into_column(self, tr_handle).map_err(FetchErr::Fatal)?);
+                for (pc, meta) in parsed.into_iter().zip(columns) {
+                    cols.push(
+                        pc.into_column(self, tr_handle, array_descs)
+                            .map_err(FetchErr::Fatal)?
+                            .with_meta(meta.clone()),
+                    );
                 }
                 Ok(FetchOne::Row(cols))
             }
@@ -998,6 +1261,20 @@ impl FirebirdWireConnection {
         Ok((BlobHandle(resp.handle), BlobId(resp.object_id)))
     }
 
+    /// Create a new stream blob, returning the blob handle and id
+    pub fn create_stream_blob(
+        &mut self,
+        tr_handle: &mut TrHandle,
+    ) -> Result<(BlobHandle, BlobId), FbError> {
+        self.socket
+            .write_all(&create_blob2(tr_handle.0, &blob::STREAM_BLOB_BPB))?;
+        self.socket.flush()?;
+
+        let resp = self.read_response()?;
+
+        Ok((BlobHandle(resp.handle), BlobId(resp.object_id)))
+    }
+
     /// Put blob segments
     pub fn put_segments(&mut self, blob_handle: BlobHandle, data: &[u8]) -> Result<(), FbError> {
         for segment in data.chunks(crate::blr::MAX_DATA_LENGTH) {
@@ -1025,7 +1302,7 @@ impl FirebirdWireConnection {
         Ok(BlobHandle(resp.handle))
     }
 
-    /// Get a blob segment, returns the bytes and true if there is more data
+    /// Get a blob segment, returns the bytes and true if the end of the blob was reached
     pub fn get_segment(&mut self, blob_handle: BlobHandle) -> Result<(Bytes, bool), FbError> {
         self.socket.write_all(&get_segment(blob_handle.0))?;
         self.socket.flush()?;
@@ -1050,6 +1327,33 @@ impl FirebirdWireConnection {
         Ok((blob_data.freeze(), resp.handle == 2))
     }
 
+    /// Move the read position of a stream blob
+    pub fn seek_blob(
+        &mut self,
+        blob_handle: BlobHandle,
+        mode: BlobSeekMode,
+        offset: i32,
+    ) -> Result<(), FbError> {
+        self.socket
+            .write_all(&seek_blob(blob_handle.0, mode, offset))?;
+        self.socket.flush()?;
+
+        self.read_response()?;
+
+        Ok(())
+    }
+
+    /// Get the total length of a blob
+    pub fn blob_length(&mut self, blob_handle: BlobHandle) -> Result<u64, FbError> {
+        self.socket
+            .write_all(&info_blob(blob_handle.0, &blob::BLOB_LENGTH_INFO))?;
+        self.socket.flush()?;
+
+        let resp = self.read_response()?;
+
+        blob::parse_blob_length(&resp.data)
+    }
+
     /// Closes a blob handle
     pub fn close_blob(&mut self, blob_handle: BlobHandle) -> Result<(), FbError> {
         self.socket.write_all(&close_blob(blob_handle.0))?;
@@ -1060,9 +1364,252 @@ impl FirebirdWireConnection {
         Ok(())
     }
 
+    /// Describe the parameters of a statement, only requesting
+    /// the description from the server in the first call
+    pub fn describe_params_cached<'s>(
+        &mut self,
+        stmt_handle: &'s mut StmtHandleData,
+    ) -> Result<&'s [ColumnMeta], FbError> {
+        if stmt_handle.params.is_none() {
+            let vars = if stmt_handle.param_count > 0 {
+                self.describe_params(stmt_handle.handle)?
+            } else {
+                Vec::new()
+            };
+
+            stmt_handle.params = Some(vars.iter().map(XSqlVar::to_meta).collect());
+        }
+
+        Ok(stmt_handle.params.as_deref().unwrap_or_default())
+    }
+
+    /// Describe the parameters of a statement
+    pub fn describe_params(&mut self, stmt_handle: StmtHandle) -> Result<Vec<XSqlVar>, FbError> {
+        let mut vars = Vec::new();
+
+        loop {
+            let next_index = (vars.len() as u16).to_le_bytes();
+
+            self.socket.write_all(&info_sql(
+                stmt_handle.0,
+                &[
+                    &[
+                        ibase::isc_info_sql_sqlda_start as u8, // Describe a xsqlda
+                        2,
+                        next_index[0], // Index, first byte
+                        next_index[1], // Index, second byte
+                    ],
+                    &XSQLDA_DESCRIBE_PARAMS[..], // Data to be returned
+                ]
+                .concat(),
+            ))?;
+            self.socket.flush()?;
+
+            let mut data = self.read_response()?.data;
+
+            if data.remaining() < 8
+                || data[..2]
+                    != [
+                        ibase::isc_info_sql_bind as u8,
+                        ibase::isc_info_sql_describe_vars as u8,
+                    ]
+            {
+                return err_invalid_response();
+            }
+            // Assume 0x04 0x00 after the item codes
+            data.advance(4)?;
+
+            let param_count = data.get_u32_le()? as usize;
+            // The index of the params is an u16, so more would be an invalid response
+            if param_count > u16::MAX as usize {
+                return err_invalid_response();
+            }
+            let described = vars.len();
+            vars.reserve(param_count.saturating_sub(described));
+
+            if !parse_select_items(&mut data, &mut vars)? {
+                break;
+            }
+            if vars.len() == described {
+                // Truncated without any new param, would never end
+                return err_invalid_response();
+            }
+        }
+
+        Ok(vars)
+    }
+
+    /// Lookup the element type and bounds of an array column
+    pub fn array_desc(
+        &mut self,
+        tr_handle: &mut TrHandle,
+        relation: &str,
+        field: &str,
+    ) -> Result<ArrayDesc, FbError> {
+        let mut db_handle = self.db_handle;
+
+        let (_, mut stmt_handle) = self.prepare_statement(
+            &mut db_handle,
+            tr_handle,
+            Dialect::D3,
+            ArrayDesc::LOOKUP_SQL,
+        )?;
+
+        self.execute(
+            tr_handle,
+            &mut stmt_handle,
+            vec![
+                SqlType::Text(relation.to_string()),
+                SqlType::Text(field.to_string()),
+            ],
+        )?;
+
+        let mut rows = Vec::new();
+        while let Some(row) = self.fetch(tr_handle, &mut stmt_handle)? {
+            rows.push(row);
+        }
+
+        self.free_statement(&mut stmt_handle, FreeStmtOp::Drop)?;
+
+        ArrayDesc::from_lookup(relation, field, rows)
+    }
+
+    /// Descriptor of an array column, looked up only in the first use in the statement
+    pub fn array_desc_cached<'d>(
+        &mut self,
+        tr_handle: &mut TrHandle,
+        array_descs: &'d mut ArrayDescs,
+        relation: &str,
+        field: &str,
+    ) -> Result<&'d ArrayDesc, FbError> {
+        Ok(
+            match array_descs.entry((relation.to_string(), field.to_string())) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.array_desc(tr_handle, relation, field)?),
+            },
+        )
+    }
+
+    /// Name of the time zone region with the id, loading
+    /// the names from the database in the first call
+    pub fn time_zone_name(
+        &mut self,
+        tr_handle: &mut TrHandle,
+        time_zone: u16,
+    ) -> Result<Option<String>, FbError> {
+        if self.time_zones.is_none() {
+            let mut db_handle = self.db_handle;
+
+            let (_, mut stmt_handle) =
+                self.prepare_statement(&mut db_handle, tr_handle, Dialect::D3, TIME_ZONES_SQL)?;
+
+            self.execute(tr_handle, &mut stmt_handle, vec![])?;
+
+            let mut rows = Vec::new();
+            while let Some(row) = self.fetch(tr_handle, &mut stmt_handle)? {
+                rows.push(row);
+            }
+
+            self.free_statement(&mut stmt_handle, FreeStmtOp::Drop)?;
+
+            self.time_zones = Some(TimeZoneNames::from_rows(rows)?);
+        }
+
+        Ok(self
+            .time_zones
+            .as_ref()
+            .and_then(|names| names.get(time_zone))
+            .map(str::to_string))
+    }
+
+    /// Read all elements of an array
+    pub fn get_array(
+        &mut self,
+        tr_handle: &mut TrHandle,
+        array_id: ArrayId,
+        desc: &ArrayDesc,
+    ) -> Result<SqlArray, FbError> {
+        let slice_type = desc.slice_type()?;
+
+        self.socket.write_all(&get_slice(
+            tr_handle.0,
+            array_id.0,
+            &desc.sdl(&desc.bounds)?,
+            desc.slice_length(&desc.bounds)? as u32,
+        ))?;
+        self.socket.flush()?;
+
+        let (mut op_code, mut resp) = self.read_packet()?;
+
+        // Read lazy responses
+        for _ in 0..self.lazy_count {
+            if op_code != WireOp::Response as u32 {
+                return err_conn_rejected(op_code);
+            }
+            self.lazy_count -= 1;
+            self.check_response(&mut resp)?;
+
+            op_code = resp.get_u32()?;
+        }
+
+        if op_code == WireOp::Response as u32 {
+            // An error ocurred
+            self.check_response(&mut resp)?;
+        }
+
+        if op_code != WireOp::Slice as u32 {
+            return err_conn_rejected(op_code);
+        }
+
+        let data = parse_slice_response(&mut resp, slice_type)?;
+
+        desc.decode_slice(desc.bounds.clone(), &data, &self.charset)
+    }
+
+    /// Write the elements to a new array, returning the array id
+    pub fn put_array(
+        &mut self,
+        tr_handle: &mut TrHandle,
+        desc: &ArrayDesc,
+        array: &SqlArray,
+    ) -> Result<ArrayId, FbError> {
+        let (bounds, data) = desc.encode_slice(array, &self.charset)?;
+
+        self.socket.write_all(&put_slice(
+            tr_handle.0,
+            &desc.sdl(&bounds)?,
+            data.len() as u32,
+            &slice_to_xdr(desc.slice_type()?, &data),
+        ))?;
+        self.socket.flush()?;
+
+        let resp = self.read_response()?;
+
+        Ok(ArrayId(resp.object_id))
+    }
+
+    /// Take the warnings returned by the server since the last call
+    pub fn take_warnings(&mut self) -> Vec<FbWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
     /// Read a server response
     fn read_response(&mut self) -> Result<Response, FbError> {
-        read_response(&mut self.socket, &mut self.buff, &mut self.lazy_count)
+        read_response(
+            &mut self.socket,
+            &mut self.buff,
+            &mut self.lazy_count,
+            &mut self.warnings,
+        )
+    }
+
+    /// Parse a server response already read, keeping the warning
+    fn check_response(&mut self, resp: &mut Bytes) -> Result<Response, FbError> {
+        let resp = parse_response(resp)?;
+
+        add_warnings(&mut self.warnings, resp.warning.clone());
+
+        Ok(resp)
     }
 
     /// Reads a packet from the socket
@@ -1076,6 +1623,7 @@ fn read_response(
     socket: &mut impl Read,
     buff: &mut [u8],
     lazy_count: &mut u32,
+    warnings: &mut Vec<FbWarning>,
 ) -> Result<Response, FbError> {
     let (mut op_code, mut resp) = read_packet(socket, buff)?;
 
@@ -1085,7 +1633,7 @@ fn read_response(
             return err_conn_rejected(op_code);
         }
         *lazy_count -= 1;
-        parse_response(&mut resp)?;
+        add_warnings(warnings, parse_response(&mut resp)?.warning);
 
         op_code = resp.get_u32()?;
     }
@@ -1094,7 +1642,10 @@ fn read_response(
         return err_conn_rejected(op_code);
     }
 
-    parse_response(&mut resp)
+    let resp = parse_response(&mut resp)?;
+    add_warnings(warnings, resp.warning.clone());
+
+    Ok(resp)
 }
 
 /// Reads a packet from the socket
@@ -1157,7 +1708,7 @@ where
     ))?;
     socket.flush()?;
 
-    read_response(&mut socket, buff, &mut 0)?;
+    read_response(&mut socket, buff, &mut 0, &mut Vec::new())?;
 
     // Enable wire encryption
     socket.write_all(&crypt("Arc4", "Symmetric"))?;
@@ -1172,7 +1723,7 @@ where
         buff.len(),
     ));
 
-    read_response(&mut socket, buff, &mut 0)?;
+    read_response(&mut socket, buff, &mut 0, &mut Vec::new())?;
 
     Ok(socket)
 }
@@ -1194,8 +1745,8 @@ pub struct StmtHandle(u32);
 pub struct BlobHandle(u32);
 
 #[derive(Debug, Clone, Copy)]
-/// A blob Identificator
-pub struct BlobId(pub(crate) u64);
+/// An array Identificator
+pub struct ArrayId(pub(crate) u64);
 
 /// Firebird tcp stream, may be encrypted
 enum FbStream {
@@ -1278,7 +1829,7 @@ fn connection_test() {
         _ => unreachable!(),
     };
 
-    conn.execute(&mut tr_handle, &mut stmt_handle, &params)
+    conn.execute(&mut tr_handle, &mut stmt_handle, params)
         .unwrap();
 
     loop {
@@ -43,6 +43,8 @@ pub enum WireOp {
     Commit = 30,
     /// Rollback transaction
     Rollback = 31,
+    /// Reconnect to a transaction in limbo
+    Reconnect = 33,
 
     /// Create a blob
     CreateBlob = 34,
@@ -61,6 +63,8 @@ pub enum WireOp {
     InfoDatabase = 40,
     /// Get informations of the transaction
     InfoTransaction = 42,
+    /// Get informations of the blob
+    InfoBlob = 43,
 
     /// Put multiple blob segments
     BatchSegments = 44,
@@ -70,6 +74,8 @@ pub enum WireOp {
     CancelEvents = 49,
     /// Commit transaction, allowing to reuse it
     CommitRetaining = 50,
+    /// Prepare transaction for the two-phase commit, with a message
+    Prepare2 = 51,
     /// Completed event request (asynchronous)
     Event = 52,
     /// Request to establish connection
@@ -78,6 +84,14 @@ pub enum WireOp {
     OpenBlob2 = 56,
     /// Create blob v2
     CreateBlob2 = 57,
+    /// Read an array slice
+    GetSlice = 58,
+    /// Write an array slice
+    PutSlice = 59,
+    /// Response for the array slice read
+    Slice = 60,
+    /// Move the read position of a stream blob
+    SeekBlob = 61,
 
     /// Allocate a statment handle
     AllocateStatement = 62,
@@ -1669,6 +1683,13 @@ pub mod blr {
     pub const COLUMN_NAME: u8 = 21;
     pub const COLUMN_NAME2: u8 = 22;
     pub const BOOL: u8 = 23;
+    pub const DEC64: u8 = 24;
+    pub const DEC128: u8 = 25;
+    pub const INT128: u8 = 26;
+    pub const SQL_TIME_TZ: u8 = 28;
+    pub const TIMESTAMP_TZ: u8 = 29;
+    pub const EX_TIME_TZ: u8 = 30;
+    pub const EX_TIMESTAMP_TZ: u8 = 31;
     // first sub parameter for domain_name[2]
     pub const DOMAIN_TYPE_OF: u8 = 0;
     pub const DOMAIN_FULL: u8 = 1;
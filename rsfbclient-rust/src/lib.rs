@@ -9,7 +9,9 @@ mod util;
 mod wire;
 mod xsqlda;
 
-pub use client::{DbHandle, RustFbClient, RustFbClientAttachmentConfig, StmtHandle, TrHandle};
+pub use client::{
+    BlobHandle, DbHandle, RustFbClient, RustFbClientAttachmentConfig, StmtHandle, TrHandle,
+};
 
 #[cfg(feature = "fuzz_testing")]
 pub use self::{blr::*, wire::*, xsqlda::*};
@@ -62,6 +62,12 @@ pub trait BytesWireExt {
     /// Gets an signed 64 bit integer from `self` in the big-endian byte order
     fn get_i64(&mut self) -> Result<i64, FbError>;
 
+    /// Gets an signed 128 bit integer from `self` in the big-endian byte order
+    fn get_i128(&mut self) -> Result<i128, FbError>;
+
+    /// Gets an unsigned 128 bit integer from `self` in the big-endian byte order
+    fn get_u128(&mut self) -> Result<u128, FbError>;
+
     /// Gets an IEEE754 double-precision (8 bytes) floating point number from `self` in big-endian byte order
     fn get_f64(&mut self) -> Result<f64, FbError>;
 
@@ -159,6 +165,20 @@ impl BytesWireExt for Bytes {
         Ok(bytes::Buf::get_i64(self))
     }
 
+    fn get_i128(&mut self) -> Result<i128, FbError> {
+        if self.remaining() < 16 {
+            return err_invalid_response();
+        }
+        Ok(bytes::Buf::get_i128(self))
+    }
+
+    fn get_u128(&mut self) -> Result<u128, FbError> {
+        if self.remaining() < 16 {
+            return err_invalid_response();
+        }
+        Ok(bytes::Buf::get_u128(self))
+    }
+
     fn get_f64(&mut self) -> Result<f64, FbError> {
         if self.remaining() < 8 {
             return err_invalid_response();
@@ -6,13 +6,17 @@ use bytes::{BufMut, Bytes, BytesMut};
 use std::{convert::TryFrom, str};
 
 use crate::{
-    client::{BlobId, FirebirdWireConnection},
+    client::{ArrayDescs, ArrayId, FirebirdWireConnection},
     consts::{gds_to_msg, AuthPluginType, Cnct, ProtocolVersion, WireOp},
     srp::*,
     util::*,
     xsqlda::{XSqlVar, XSQLDA_DESCRIBE_VARS},
 };
-use rsfbclient_core::{ibase, Charset, Column, Dialect, FbError, FreeStmtOp, SqlType, TrOp};
+use rsfbclient_core::{
+    array::SliceType, charset::OCTETS_ID, date_time, ibase, BlobId, BlobRef, BlobSeekMode, Charset,
+    Column, DecFloat, Dialect, FbError, FbWarning, FreeStmtOp, Numeric, SqlType, StatusArg,
+    StatusEntry, StatusVector, TrOp,
+};
 
 /// Buffer length to use in the connection
 pub const BUFFER_LENGTH: u32 = 1024;
@@ -305,6 +309,41 @@ pub fn transaction_operation(tr_handle: u32, op: TrOp) -> Bytes {
     tr.freeze()
 }
 
+/// Prepare transaction for the two-phase commit request
+pub fn prepare_transaction(tr_handle: u32, message: &[u8]) -> Bytes {
+    let mut req = BytesMut::with_capacity(12 + message.len());
+
+    req.put_u32(WireOp::Prepare2 as u32);
+    req.put_u32(tr_handle);
+    req.put_wire_bytes(message);
+
+    req.freeze()
+}
+
+/// Reconnect to a transaction in limbo request
+pub fn reconnect_transaction(db_handle: u32, id: &[u8]) -> Bytes {
+    let mut req = BytesMut::with_capacity(16 + id.len());
+
+    req.put_u32(WireOp::Reconnect as u32);
+    req.put_u32(db_handle);
+    req.put_wire_bytes(id);
+
+    req.freeze()
+}
+
+/// Database information request
+pub fn info_database(db_handle: u32, requested_items: &[u8], buffer_length: u32) -> Bytes {
+    let mut req = BytesMut::with_capacity(24 + requested_items.len());
+
+    req.put_u32(WireOp::InfoDatabase as u32);
+    req.put_u32(db_handle);
+    req.put_u32(0); // Incarnation of object
+    req.put_wire_bytes(requested_items);
+    req.put_u32(buffer_length);
+
+    req.freeze()
+}
+
 /// Execute immediate request
 pub fn exec_immediate(
     tr_handle: u32,
@@ -452,6 +491,18 @@ pub fn create_blob(tr_handle: u32) -> Bytes {
     req.freeze()
 }
 
+/// Create blob request, with a blob parameter buffer
+pub fn create_blob2(tr_handle: u32, bpb: &[u8]) -> Bytes {
+    let mut req = BytesMut::with_capacity(20 + bpb.len());
+
+    req.put_u32(WireOp::CreateBlob2 as u32);
+    req.put_wire_bytes(bpb);
+    req.put_u32(tr_handle);
+    req.put_u64(0); // Blob id
+
+    req.freeze()
+}
+
 /// Open blob request
 pub fn open_blob(tr_handle: u32, blob_id: u64) -> Bytes {
     let mut req = BytesMut::with_capacity(16);
@@ -497,12 +548,147 @@ pub fn close_blob(blob_handle: u32) -> Bytes {
     req.freeze()
 }
 
+/// Seek blob request
+pub fn seek_blob(blob_handle: u32, mode: BlobSeekMode, offset: i32) -> Bytes {
+    let mut req = BytesMut::with_capacity(16);
+
+    req.put_u32(WireOp::SeekBlob as u32);
+    req.put_u32(blob_handle);
+    req.put_u32(mode as u32);
+    req.put_i32(offset);
+
+    req.freeze()
+}
+
+/// Blob information request
+pub fn info_blob(blob_handle: u32, requested_items: &[u8]) -> Bytes {
+    let mut req = BytesMut::with_capacity(24 + requested_items.len());
+
+    req.put_u32(WireOp::InfoBlob as u32);
+    req.put_u32(blob_handle);
+    req.put_u32(0); // Incarnation of object
+    req.put_wire_bytes(requested_items);
+    req.put_u32(BUFFER_LENGTH);
+
+    req.freeze()
+}
+
+/// Get array slice request
+pub fn get_slice(tr_handle: u32, array_id: u64, sdl: &[u8], slice_length: u32) -> Bytes {
+    let mut req = BytesMut::with_capacity(32 + sdl.len());
+
+    req.put_u32(WireOp::GetSlice as u32);
+    req.put_u32(tr_handle);
+    req.put_u64(array_id);
+    req.put_u32(slice_length);
+    req.put_wire_bytes(sdl);
+    req.put_u32(0); // Sdl parameters
+    req.put_u32(0); // Slice data, empty when reading
+
+    req.freeze()
+}
+
+/// Put array slice request, creating a new array
+pub fn put_slice(tr_handle: u32, sdl: &[u8], slice_length: u32, slice: &[u8]) -> Bytes {
+    let mut req = BytesMut::with_capacity(32 + sdl.len() + slice.len());
+
+    req.put_u32(WireOp::PutSlice as u32);
+    req.put_u32(tr_handle);
+    req.put_u64(0); // Array id, a new one will be created
+    req.put_u32(slice_length);
+    req.put_wire_bytes(sdl);
+    req.put_u32(0); // Sdl parameters
+    req.put_u32(slice_length);
+    req.put_slice(slice);
+
+    req.freeze()
+}
+
+/// Convert a slice from the native layout to the wire (xdr) representation
+pub fn slice_to_xdr(slice_type: SliceType, data: &[u8]) -> Bytes {
+    let mut xdr = BytesMut::with_capacity(data.len() * 2);
+
+    let u32_at = |el: &[u8], i: usize| u32::from_ne_bytes([el[i], el[i + 1], el[i + 2], el[i + 3]]);
+
+    for el in data.chunks_exact(slice_type.size()) {
+        match slice_type {
+            SliceType::Int64 { .. } | SliceType::Double => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(el);
+                xdr.put_u64(u64::from_ne_bytes(bytes));
+            }
+
+            SliceType::Timestamp => {
+                xdr.put_u32(u32_at(el, 0));
+                xdr.put_u32(u32_at(el, 4));
+            }
+
+            SliceType::Date | SliceType::Time => xdr.put_u32(u32_at(el, 0)),
+
+            SliceType::Varying { length } => {
+                let len = u16::from_ne_bytes([el[0], el[1]]).min(length) as usize;
+
+                xdr.put_wire_bytes(&el[2..2 + len]);
+            }
+
+            SliceType::Boolean => xdr.put_slice(&[el[0], 0, 0, 0]),
+        }
+    }
+
+    xdr.freeze()
+}
+
+/// Parse the array slice response (`WireOp::Slice`), returning the data in the native layout
+pub fn parse_slice_response(resp: &mut Bytes, slice_type: SliceType) -> Result<Vec<u8>, FbError> {
+    let _returned_length = resp.get_u32()?;
+    let length = resp.get_u32()? as usize;
+
+    let mut data = Vec::with_capacity(length);
+
+    for _ in 0..length / slice_type.size() {
+        match slice_type {
+            SliceType::Int64 { .. } | SliceType::Double => {
+                data.extend_from_slice(&resp.get_u64()?.to_ne_bytes())
+            }
+
+            SliceType::Timestamp => {
+                data.extend_from_slice(&resp.get_u32()?.to_ne_bytes());
+                data.extend_from_slice(&resp.get_u32()?.to_ne_bytes());
+            }
+
+            SliceType::Date | SliceType::Time => {
+                data.extend_from_slice(&resp.get_u32()?.to_ne_bytes())
+            }
+
+            SliceType::Varying { length } => {
+                let text = resp.get_wire_bytes()?;
+                if text.len() > length as usize {
+                    return err_invalid_response();
+                }
+
+                data.extend_from_slice(&(text.len() as u16).to_ne_bytes());
+                data.extend_from_slice(&text);
+                data.resize(data.len() + length as usize - text.len(), 0);
+            }
+
+            SliceType::Boolean => {
+                data.push(resp.get_u8()?);
+                resp.advance(3)?; // Pad to 4 bytes
+            }
+        }
+    }
+
+    Ok(data)
+}
+
 #[derive(Debug)]
 /// `WireOp::Response` response
 pub struct Response {
     pub handle: u32,
     pub object_id: u64,
     pub data: Bytes,
+    /// Warning returned along with the response
+    pub warning: Option<FbWarning>,
 }
 
 /// Parse a server response (`WireOp::Response`)
@@ -512,12 +698,13 @@ pub fn parse_response(resp: &mut Bytes) -> Result<Response, FbError> {
 
     let data = resp.get_wire_bytes()?;
 
-    parse_status_vector(resp)?;
+    let warning = parse_status_vector(resp)?;
 
     Ok(Response {
         handle,
         object_id,
         data,
+        warning,
     })
 }
 
@@ -611,10 +798,17 @@ pub fn parse_sql_response(
                         SqlType::Null,
                     )))
                 } else {
+                    // Low byte of the sub type, the high byte is the collation
+                    let value = if var.sqlsubtype as u8 == OCTETS_ID {
+                        SqlType::Binary(d.to_vec())
+                    } else {
+                        SqlType::Text(charset.decode(&d[..])?)
+                    };
+
                     data.push(ParsedColumn::Complete(Column::new(
                         var.alias_name.clone(),
                         sqltype,
-                        SqlType::Text(charset.decode(&d[..])?),
+                        value,
                     )))
                 }
             }
@@ -629,6 +823,12 @@ pub fn parse_sql_response(
                         sqltype,
                         SqlType::Null,
                     )))
+                } else if var.scale != 0 {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Decimal(Numeric::new(i as i128, var.scale)),
+                    )))
                 } else {
                     data.push(ParsedColumn::Complete(Column::new(
                         var.alias_name.clone(),
@@ -638,6 +838,54 @@ pub fn parse_sql_response(
                 }
             }
 
+            ibase::SQL_INT128 => {
+                let i = resp.get_i128()?;
+
+                let null = read_null(resp, col_index)?;
+                if null {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Null,
+                    )))
+                } else if var.scale != 0 {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Decimal(Numeric::new(i, var.scale)),
+                    )))
+                } else {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Int128(i),
+                    )))
+                }
+            }
+
+            ibase::SQL_DEC16 | ibase::SQL_DEC34 => {
+                let d = if sqltype == ibase::SQL_DEC16 {
+                    DecFloat::from_dpd64(resp.get_u64()?)
+                } else {
+                    DecFloat::from_dpd128(resp.get_u128()?)
+                };
+
+                let null = read_null(resp, col_index)?;
+                if null {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Null,
+                    )))
+                } else {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::DecimalFloat(d),
+                    )))
+                }
+            }
+
             ibase::SQL_DOUBLE => {
                 let f = resp.get_f64()?;
 
@@ -679,7 +927,101 @@ pub fn parse_sql_response(
                 }
             }
 
-            ibase::SQL_BLOB if var.sqlsubtype <= 1 => {
+            ibase::SQL_TYPE_DATE => {
+                let date = resp.get_i32()?;
+
+                let null = read_null(resp, col_index)?;
+                if null {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Null,
+                    )))
+                } else {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Date(rsfbclient_core::date_time::decode_date(date)),
+                    )))
+                }
+            }
+
+            ibase::SQL_TYPE_TIME => {
+                let time = resp.get_u32()?;
+
+                let null = read_null(resp, col_index)?;
+                if null {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Null,
+                    )))
+                } else {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Time(rsfbclient_core::date_time::decode_time(time)),
+                    )))
+                }
+            }
+
+            ibase::SQL_TIMESTAMP_TZ_EX => {
+                let ts = ibase::ISC_TIMESTAMP_TZ_EX {
+                    utc_timestamp: ibase::ISC_TIMESTAMP {
+                        timestamp_date: resp.get_i32()?,
+                        timestamp_time: resp.get_u32()?,
+                    },
+                    // Sent as 4 bytes in the wire
+                    time_zone: resp.get_u32()? as u16,
+                    ext_offset: resp.get_i32()? as i16,
+                };
+
+                let null = read_null(resp, col_index)?;
+                if null {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Null,
+                    )))
+                } else if date_time::is_time_zone_region(ts.time_zone) {
+                    data.push(ParsedColumn::TimestampTzRegion {
+                        value: ts,
+                        col_name: var.alias_name.clone(),
+                    })
+                } else {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::TimestampTz(date_time::decode_timestamp_tz(ts)?, None),
+                    )))
+                }
+            }
+
+            ibase::SQL_TIME_TZ_EX => {
+                let time = ibase::ISC_TIME_TZ_EX {
+                    utc_time: resp.get_u32()?,
+                    // Sent as 4 bytes in the wire
+                    time_zone: resp.get_u32()? as u16,
+                    ext_offset: resp.get_i32()? as i16,
+                };
+
+                let null = read_null(resp, col_index)?;
+                if null {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Null,
+                    )))
+                } else {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::TimeTz(rsfbclient_core::date_time::decode_time_tz(time)?),
+                    )))
+                }
+            }
+
+            ibase::SQL_BLOB => {
                 let id = resp.get_u64()?;
 
                 let null = read_null(resp, col_index)?;
@@ -691,13 +1033,41 @@ pub fn parse_sql_response(
                     )))
                 } else {
                     data.push(ParsedColumn::Blob {
-                        binary: var.sqlsubtype == 0,
+                        // Text blobs have the charset id in the scale
+                        charset: if var.sqlsubtype == 1 {
+                            Some(
+                                Charset::from_id(var.scale as u8)
+                                    .unwrap_or_else(|| charset.clone()),
+                            )
+                        } else {
+                            None
+                        },
                         id: BlobId(id),
                         col_name: var.alias_name.clone(),
                     })
                 }
             }
 
+            ibase::SQL_ARRAY => {
+                let id = resp.get_u64()?;
+
+                let null = read_null(resp, col_index)?;
+                if null {
+                    data.push(ParsedColumn::Complete(Column::new(
+                        var.alias_name.clone(),
+                        sqltype,
+                        SqlType::Null,
+                    )))
+                } else {
+                    data.push(ParsedColumn::Array {
+                        id: ArrayId(id),
+                        col_name: var.alias_name.clone(),
+                        relation: var.relation_name.clone(),
+                        field: var.field_name.clone(),
+                    })
+                }
+            }
+
             ibase::SQL_BOOLEAN => {
                 let b = resp.get_u8()? == 1;
                 resp.advance(3)?; // Pad to 4 bytes
@@ -738,13 +1108,31 @@ pub enum ParsedColumn {
     Complete(Column),
     /// Blobs need more requests to get the actual data
     Blob {
-        /// True if blob type 0
-        binary: bool,
+        /// Charset to decode the text blobs, `None` if binary
+        charset: Option<Charset>,
         /// Blob id
         id: BlobId,
         /// Column name
         col_name: String,
     },
+    /// Arrays need more requests to get the descriptor and the elements
+    Array {
+        /// Array id
+        id: ArrayId,
+        /// Column name
+        col_name: String,
+        /// Table of the array column
+        relation: String,
+        /// Name of the array column in the table
+        field: String,
+    },
+    /// Time zone regions need a request to get the names
+    TimestampTzRegion {
+        /// Timestamp with the id and offset of the region
+        value: ibase::ISC_TIMESTAMP_TZ_EX,
+        /// Column name
+        col_name: String,
+    },
 }
 
 impl ParsedColumn {
@@ -753,11 +1141,21 @@ impl ParsedColumn {
         self,
         conn: &mut FirebirdWireConnection,
         tr_handle: &mut crate::TrHandle,
+        array_descs: &mut ArrayDescs,
     ) -> Result<Column, FbError> {
         Ok(match self {
             ParsedColumn::Complete(c) => c,
             ParsedColumn::Blob {
-                binary,
+                charset,
+                id,
+                col_name,
+            } if conn.lazy_blobs => Column::new(
+                col_name,
+                ibase::SQL_BLOB,
+                SqlType::LazyBlob(BlobRef { id, charset }),
+            ),
+            ParsedColumn::Blob {
+                charset,
                 id,
                 col_name,
             } => {
@@ -780,38 +1178,98 @@ impl ParsedColumn {
                 Column::new(
                     col_name,
                     ibase::SQL_BLOB,
-                    if binary {
-                        SqlType::Binary(data)
-                    } else {
-                        SqlType::Text(conn.charset.decode(data)?)
+                    match charset {
+                        Some(charset) => SqlType::Text(charset.decode(data)?),
+                        None => SqlType::Binary(data),
                     },
                 )
             }
+            ParsedColumn::Array {
+                id,
+                col_name,
+                relation,
+                field,
+            } => {
+                let desc = conn.array_desc_cached(tr_handle, array_descs, &relation, &field)?;
+
+                let array = conn.get_array(tr_handle, id, desc)?;
+
+                Column::new(col_name, ibase::SQL_ARRAY, SqlType::Array(array))
+            }
+            ParsedColumn::TimestampTzRegion { value, col_name } => {
+                let region = conn.time_zone_name(tr_handle, value.time_zone)?;
+
+                Column::new(
+                    col_name,
+                    ibase::SQL_TIMESTAMP_TZ_EX,
+                    SqlType::TimestampTz(date_time::decode_timestamp_tz(value)?, region),
+                )
+            }
         })
     }
 }
 
 /// Parses the error messages from the response
-pub fn parse_status_vector(resp: &mut Bytes) -> Result<(), FbError> {
+pub fn parse_status_vector(resp: &mut Bytes) -> Result<Option<FbWarning>, FbError> {
     // Sql error code (default to -1)
     let mut sql_code = -1;
     // Error messages
     let mut message = String::new();
+    // Warning messages
+    let mut warn_message = String::new();
+
+    // Error codes and arguments
+    let mut status = StatusVector::default();
+    // Warning codes and arguments
+    let mut warn_status = StatusVector::default();
 
     // Code of the last error message
     let mut gds_code = 0;
     // Error message argument index
     let mut num_arg = 0;
+    // True if the last message is a warning
+    let mut in_warning = false;
 
     loop {
+        let (msg, vector) = if in_warning {
+            (&mut warn_message, &mut warn_status)
+        } else {
+            (&mut message, &mut status)
+        };
+
         match resp.get_u32()? {
             // New error message
             ibase::isc_arg_gds => {
                 gds_code = resp.get_u32()?;
+                in_warning = false;
 
                 if gds_code != 0 {
                     message += gds_to_msg(gds_code);
                     num_arg = 0;
+
+                    status.errors.push(StatusEntry {
+                        gds_code,
+                        args: vec![],
+                    });
+                }
+            }
+
+            // New warning message
+            ibase::isc_arg_warning => {
+                gds_code = resp.get_u32()?;
+                in_warning = true;
+
+                if gds_code != 0 {
+                    if !warn_message.is_empty() && !warn_message.ends_with('\n') {
+                        warn_message.push('\n');
+                    }
+                    warn_message += gds_to_msg(gds_code);
+                    num_arg = 0;
+
+                    warn_status.errors.push(StatusEntry {
+                        gds_code,
+                        args: vec![],
+                    });
                 }
             }
 
@@ -824,28 +1282,43 @@ pub fn parse_status_vector(resp: &mut Bytes) -> Result<(), FbError> {
                 }
 
                 num_arg += 1;
-                message = message.replace(&format!("@{}", num_arg), &format!("{}", num));
+                *msg = msg.replace(&format!("@{}", num_arg), &format!("{}", num));
+
+                if let Some(err) = vector.errors.last_mut() {
+                    err.args.push(StatusArg::Number(num));
+                }
             }
 
             // Error message arg string
             ibase::isc_arg_string => {
-                let msg = resp.get_wire_bytes()?;
-                let msg = std::str::from_utf8(&msg[..]).unwrap_or("**Invalid message**");
+                let arg = resp.get_wire_bytes()?;
+                let arg = std::str::from_utf8(&arg[..]).unwrap_or("**Invalid message**");
 
                 num_arg += 1;
-                message = message.replace(&format!("@{}", num_arg), msg);
+                *msg = msg.replace(&format!("@{}", num_arg), arg);
+
+                if let Some(err) = vector.errors.last_mut() {
+                    err.args.push(StatusArg::Text(arg.to_string()));
+                }
             }
 
             // Aditional error message string
             ibase::isc_arg_interpreted => {
-                let msg = resp.get_wire_bytes()?;
-                let msg = std::str::from_utf8(&msg[..]).unwrap_or("**Invalid message**");
+                let arg = resp.get_wire_bytes()?;
+                let arg = std::str::from_utf8(&arg[..]).unwrap_or("**Invalid message**");
 
-                message += msg;
+                *msg += arg;
+
+                vector.errors.push(StatusEntry {
+                    gds_code: 0,
+                    args: vec![StatusArg::Text(arg.to_string())],
+                });
             }
 
             ibase::isc_arg_sql_state => {
-                resp.get_wire_bytes()?;
+                let state = resp.get_wire_bytes()?;
+
+                vector.sql_state = Some(String::from_utf8_lossy(&state).into_owned());
             }
 
             // End of error messages
@@ -865,9 +1338,19 @@ pub fn parse_status_vector(resp: &mut Bytes) -> Result<(), FbError> {
         Err(FbError::Sql {
             code: sql_code,
             msg: message,
+            status,
         })
+    } else if !warn_status.errors.is_empty() {
+        if warn_message.ends_with('\n') {
+            warn_message.pop();
+        }
+
+        Ok(Some(FbWarning {
+            msg: warn_message,
+            status: warn_status,
+        }))
     } else {
-        Ok(())
+        Ok(None)
     }
 }
 
@@ -1038,3 +1521,33 @@ pub fn parse_info_sql_affected_rows(data: &mut Bytes) -> Result<usize, FbError>
 
     Ok(affected_rows)
 }
+
+#[test]
+fn status_vector_warning() {
+    let mut resp = BytesMut::new();
+    resp.put_u32(ibase::isc_arg_gds);
+    resp.put_u32(0);
+    resp.put_u32(ibase::isc_arg_warning);
+    resp.put_u32(335544436);
+    resp.put_u32(ibase::isc_arg_number);
+    resp.put_i32(-104);
+    resp.put_u32(ibase::isc_arg_end);
+
+    let warning = parse_status_vector(&mut resp.freeze())
+        .unwrap()
+        .expect("Warning not parsed");
+    assert_eq!("SQL error code = -104", warning.msg);
+    assert_eq!(
+        vec![StatusEntry {
+            gds_code: 335544436,
+            args: vec![StatusArg::Number(-104)],
+        }],
+        warning.status.errors
+    );
+
+    let mut resp = BytesMut::new();
+    resp.put_u32(ibase::isc_arg_gds);
+    resp.put_u32(0);
+    resp.put_u32(ibase::isc_arg_end);
+    assert!(parse_status_vector(&mut resp.freeze()).unwrap().is_none());
+}
@@ -4,7 +4,7 @@
 
 use crate::util::*;
 use bytes::{BufMut, Bytes, BytesMut};
-use rsfbclient_core::{ibase, FbError, StmtType};
+use rsfbclient_core::{charset::OCTETS_ID, ibase, ColumnMeta, FbError, StmtType};
 use std::{convert::TryFrom, mem};
 
 use crate::consts;
@@ -30,6 +30,23 @@ pub const XSQLDA_DESCRIBE_VARS: [u8; 17] = [
     ibase::isc_info_sql_describe_end as u8, // End of column data
 ];
 
+/// Data to return about the parameters of a statement
+pub const XSQLDA_DESCRIBE_PARAMS: [u8; 13] = [
+    ibase::isc_info_sql_bind as u8,          // Select params
+    ibase::isc_info_sql_describe_vars as u8, // Param count
+    ibase::isc_info_sql_sqlda_seq as u8,     // Param index
+    ibase::isc_info_sql_type as u8,          // Sql Type code
+    ibase::isc_info_sql_sub_type as u8,      // Blob subtype
+    ibase::isc_info_sql_scale as u8,         // Decimal / Numeric scale
+    ibase::isc_info_sql_length as u8,        // Data length
+    ibase::isc_info_sql_null_ind as u8,      // Null indicator (0 or -1)
+    ibase::isc_info_sql_field as u8,         // Column related to the param
+    ibase::isc_info_sql_relation as u8,      // Table related to the param
+    ibase::isc_info_sql_owner as u8,         //
+    ibase::isc_info_sql_alias as u8,         //
+    ibase::isc_info_sql_describe_end as u8,  // End of param data
+];
+
 #[derive(Debug, Default)]
 /// Sql query column information
 pub struct XSqlVar {
@@ -59,11 +76,21 @@ pub struct XSqlVar {
 }
 
 impl XSqlVar {
+    /// Description of the column, must be called before the coercion
+    pub fn to_meta(&self) -> ColumnMeta {
+        ColumnMeta {
+            alias: self.alias_name.clone(),
+            field: self.field_name.clone(),
+            relation: self.relation_name.clone(),
+            owner: self.owner_name.clone(),
+            ..ColumnMeta::from_type(self.sqltype, self.scale, self.sqlsubtype, self.data_length)
+        }
+    }
+
     /// Coerces the data types of this XSqlVar as necessary
     pub fn coerce(&mut self) -> Result<(), FbError> {
         // Remove nullable type indicator
         let sqltype = self.sqltype & (!1);
-        let sqlsubtype = self.sqlsubtype;
 
         // var.null_ind = 1;
 
@@ -75,13 +102,27 @@ impl XSqlVar {
             ibase::SQL_SHORT | ibase::SQL_LONG | ibase::SQL_INT64 => {
                 self.data_length = mem::size_of::<i64>() as i16;
 
-                if self.scale == 0 {
-                    self.sqltype = ibase::SQL_INT64 as i16 + 1;
-                } else {
-                    // Is actually a decimal or numeric value, so coerce as double
-                    self.scale = 0;
-                    self.sqltype = ibase::SQL_DOUBLE as i16 + 1;
-                }
+                // Decimal and numeric values keep the scale, so
+                // they can be returned as exact values
+                self.sqltype = ibase::SQL_INT64 as i16 + 1;
+            }
+
+            ibase::SQL_INT128 => {
+                self.data_length = mem::size_of::<i128>() as i16;
+
+                self.sqltype = ibase::SQL_INT128 as i16 + 1;
+            }
+
+            ibase::SQL_DEC16 => {
+                self.data_length = mem::size_of::<u64>() as i16;
+
+                self.sqltype = ibase::SQL_DEC16 as i16 + 1;
+            }
+
+            ibase::SQL_DEC34 => {
+                self.data_length = mem::size_of::<u128>() as i16;
+
+                self.sqltype = ibase::SQL_DEC34 as i16 + 1;
             }
 
             ibase::SQL_FLOAT | ibase::SQL_DOUBLE => {
@@ -90,13 +131,38 @@ impl XSqlVar {
                 self.sqltype = ibase::SQL_DOUBLE as i16 + 1;
             }
 
-            ibase::SQL_TIMESTAMP | ibase::SQL_TYPE_DATE | ibase::SQL_TYPE_TIME => {
+            ibase::SQL_TIMESTAMP => {
                 self.data_length = mem::size_of::<ibase::ISC_TIMESTAMP>() as i16;
 
                 self.sqltype = ibase::SQL_TIMESTAMP as i16 + 1;
             }
 
-            ibase::SQL_BLOB if sqlsubtype <= 1 => {
+            ibase::SQL_TYPE_DATE => {
+                self.data_length = mem::size_of::<ibase::ISC_DATE>() as i16;
+
+                self.sqltype = ibase::SQL_TYPE_DATE as i16 + 1;
+            }
+
+            ibase::SQL_TYPE_TIME => {
+                self.data_length = mem::size_of::<ibase::ISC_TIME>() as i16;
+
+                self.sqltype = ibase::SQL_TYPE_TIME as i16 + 1;
+            }
+
+            ibase::SQL_TIMESTAMP_TZ | ibase::SQL_TIMESTAMP_TZ_EX => {
+                // The extended type includes the offset of the region time zones
+                self.data_length = mem::size_of::<ibase::ISC_TIMESTAMP_TZ_EX>() as i16;
+
+                self.sqltype = ibase::SQL_TIMESTAMP_TZ_EX as i16 + 1;
+            }
+
+            ibase::SQL_TIME_TZ | ibase::SQL_TIME_TZ_EX => {
+                self.data_length = mem::size_of::<ibase::ISC_TIME_TZ_EX>() as i16;
+
+                self.sqltype = ibase::SQL_TIME_TZ_EX as i16 + 1;
+            }
+
+            ibase::SQL_BLOB => {
                 self.sqltype = ibase::SQL_BLOB as i16 + 1;
             }
 
@@ -104,6 +170,13 @@ impl XSqlVar {
                 self.sqltype = ibase::SQL_BOOLEAN as i16 + 1;
             }
 
+            ibase::SQL_ARRAY => {
+                // Only the array id, the elements are read in slices
+                self.data_length = mem::size_of::<u64>() as i16;
+
+                self.sqltype = ibase::SQL_ARRAY as i16 + 1;
+            }
+
             sqltype => {
                 return Err(format!("Unsupported column type ({})", sqltype).into());
             }
@@ -130,24 +203,44 @@ pub fn xsqlda_to_blr(xsqlda: &[XSqlVar]) -> Result<Bytes, FbError> {
         let sqltype = var.sqltype as u32 & (!1);
 
         match sqltype as u32 {
+            // Keeps the charset, so the binary data is not transliterated
+            ibase::SQL_VARYING if var.sqlsubtype as u8 == OCTETS_ID => {
+                blr.put_u8(consts::blr::VARYING2);
+                blr.put_u16_le(OCTETS_ID as u16);
+                blr.put_i16_le(var.data_length);
+            }
+
             ibase::SQL_VARYING => {
                 blr.put_u8(consts::blr::VARYING);
                 blr.put_i16_le(var.data_length);
             }
 
-            ibase::SQL_INT64 => blr.put_slice(&[
-                consts::blr::INT64,
-                0, // Scale
-            ]),
+            ibase::SQL_INT64 => blr.put_slice(&[consts::blr::INT64, var.scale as u8]),
+
+            ibase::SQL_INT128 => blr.put_slice(&[consts::blr::INT128, var.scale as u8]),
+
+            ibase::SQL_DEC16 => blr.put_u8(consts::blr::DEC64),
+
+            ibase::SQL_DEC34 => blr.put_u8(consts::blr::DEC128),
+
+            ibase::SQL_TIMESTAMP_TZ_EX => blr.put_u8(consts::blr::EX_TIMESTAMP_TZ),
+
+            ibase::SQL_TIME_TZ_EX => blr.put_u8(consts::blr::EX_TIME_TZ),
 
             ibase::SQL_DOUBLE => blr.put_u8(consts::blr::DOUBLE),
 
             ibase::SQL_TIMESTAMP => blr.put_u8(consts::blr::TIMESTAMP),
 
-            ibase::SQL_BLOB => blr.put_slice(&[consts::blr::QUAD, var.sqlsubtype as u8]),
+            ibase::SQL_TYPE_DATE => blr.put_u8(consts::blr::SQL_DATE),
+
+            ibase::SQL_TYPE_TIME => blr.put_u8(consts::blr::SQL_TIME),
+
+            ibase::SQL_BLOB => blr.put_slice(&[consts::blr::QUAD, 0]),
 
             ibase::SQL_BOOLEAN => blr.put_u8(consts::blr::BOOL),
 
+            ibase::SQL_ARRAY => blr.put_slice(&[consts::blr::QUAD, 0]),
+
             sqltype => {
                 return Err(format!("Conversion from sql type {} not implemented", sqltype).into());
             }
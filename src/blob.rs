@@ -0,0 +1,262 @@
+//!
+//! Rust Firebird Client
+//!
+//! Streaming access to the blob contents
+//!
+
+use rsfbclient_core::{BlobId, BlobSeekMode, FbError, FirebirdClient};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::{transaction::TransactionData, Connection, Transaction};
+
+/// Handle to read or write the contents of a blob, without loading all the data in memory.
+///
+/// Created with `Transaction::open_blob` to read an existing blob,
+/// or `Transaction::create_blob` to write a new one. The id of a written
+/// blob can then be used as a parameter to store it in a column.
+pub struct Blob<'c, 't, C: FirebirdClient> {
+    /// Blob handle, `None` after closed
+    handle: Option<C::BlobHandle>,
+    /// Blob id
+    id: BlobId,
+    /// True if the blob was created for writing
+    writable: bool,
+    /// Segment data not yet read
+    buffer: Vec<u8>,
+    /// Read position in the `buffer`
+    buffer_pos: usize,
+    /// True if the last segment was received
+    eof: bool,
+    /// Current position in the blob
+    position: u64,
+    tr: &'t mut Transaction<'c, C>,
+}
+
+impl<'c, 't, C> Blob<'c, 't, C>
+where
+    C: FirebirdClient,
+{
+    /// Open an existing blob for reading
+    pub fn open(tr: &'t mut Transaction<'c, C>, id: BlobId) -> Result<Self, FbError> {
+        let handle = tr
+            .conn
+            .cli
+            .open_blob(&mut tr.conn.handle, &mut tr.data.handle, id)?;
+
+        Ok(Self::new(tr, handle, id, false))
+    }
+
+    /// Create a new blob for writing
+    pub fn create(tr: &'t mut Transaction<'c, C>) -> Result<Self, FbError> {
+        let (handle, id) = tr
+            .conn
+            .cli
+            .create_blob(&mut tr.conn.handle, &mut tr.data.handle)?;
+
+        Ok(Self::new(tr, handle, id, true))
+    }
+
+    fn new(
+        tr: &'t mut Transaction<'c, C>,
+        handle: C::BlobHandle,
+        id: BlobId,
+        writable: bool,
+    ) -> Self {
+        Self {
+            handle: Some(handle),
+            id,
+            writable,
+            buffer: Vec::new(),
+            buffer_pos: 0,
+            eof: false,
+            position: 0,
+            tr,
+        }
+    }
+
+    /// Id of the blob
+    pub fn id(&self) -> BlobId {
+        self.id
+    }
+
+    /// Total length of the blob in bytes
+    pub fn len(&mut self) -> Result<u64, FbError> {
+        let handle = opened(&mut self.handle)?;
+
+        self.tr.conn.cli.blob_length(handle)
+    }
+
+    /// Returns true if the blob has no data
+    pub fn is_empty(&mut self) -> Result<bool, FbError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Close the blob, returning the id.
+    ///
+    /// A new blob must be closed before the id is used as a parameter
+    pub fn close(mut self) -> Result<BlobId, FbError> {
+        if let Some(mut handle) = self.handle.take() {
+            self.tr.conn.cli.close_blob(&mut handle)?;
+        }
+
+        Ok(self.id)
+    }
+
+    /// Read segments until some data is available or the blob ends
+    fn fill_buffer(&mut self) -> Result<(), FbError> {
+        while self.buffer_pos >= self.buffer.len() && !self.eof {
+            let handle = opened(&mut self.handle)?;
+
+            let (data, end) = self.tr.conn.cli.get_segment(handle)?;
+
+            self.buffer = data;
+            self.buffer_pos = 0;
+            self.eof = end;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> Read for Blob<'_, '_, C>
+where
+    C: FirebirdClient,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.writable {
+            return Err(io::Error::other("The blob was created for writing"));
+        }
+
+        self.fill_buffer().map_err(to_io_error)?;
+
+        let available = &self.buffer[self.buffer_pos..];
+        let len = usize::min(available.len(), buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+
+        self.buffer_pos += len;
+        self.position += len as u64;
+
+        Ok(len)
+    }
+}
+
+impl<C> Write for Blob<'_, '_, C>
+where
+    C: FirebirdClient,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.writable {
+            return Err(io::Error::other("The blob was opened for reading"));
+        }
+
+        let handle = opened(&mut self.handle).map_err(to_io_error)?;
+        self.tr
+            .conn
+            .cli
+            .put_segment(handle, buf)
+            .map_err(to_io_error)?;
+
+        self.position += buf.len() as u64;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // The segments are sent immediately
+        Ok(())
+    }
+}
+
+/// Only works in stream blobs, like the ones created by `Transaction::create_blob`.
+/// Seeking in segmented blobs returns an error from the server
+impl<C> Seek for Blob<'_, '_, C>
+where
+    C: FirebirdClient,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        if self.writable {
+            return Err(io::Error::other("Can't seek in a blob created for writing"));
+        }
+
+        // Computed here, as the position returned by the server is not reliable
+        let target = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+            SeekFrom::End(offset) => self.len().map_err(to_io_error)? as i64 + offset,
+        };
+
+        if target < 0 || target > i32::MAX as i64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid seek to a position outside of the blob limits",
+            ));
+        }
+
+        let handle = opened(&mut self.handle).map_err(to_io_error)?;
+        self.tr
+            .conn
+            .cli
+            .seek_blob(handle, BlobSeekMode::Start, target as i32)
+            .map_err(to_io_error)?;
+
+        self.buffer.clear();
+        self.buffer_pos = 0;
+        self.eof = false;
+        self.position = target as u64;
+
+        Ok(self.position)
+    }
+}
+
+impl<C> Drop for Blob<'_, '_, C>
+where
+    C: FirebirdClient,
+{
+    fn drop(&mut self) {
+        if let Some(mut handle) = self.handle.take() {
+            self.tr.conn.cli.close_blob(&mut handle).ok();
+        }
+    }
+}
+
+/// Read all the data of a blob
+pub(crate) fn read_blob<C: FirebirdClient>(
+    conn: &mut Connection<C>,
+    tr: &mut TransactionData<C>,
+    id: BlobId,
+) -> Result<Vec<u8>, FbError> {
+    let mut handle = conn.cli.open_blob(&mut conn.handle, &mut tr.handle, id)?;
+
+    let mut data = Vec::with_capacity(256);
+    let res = loop {
+        match conn.cli.get_segment(&mut handle) {
+            Ok((segment, end)) => {
+                data.extend_from_slice(&segment);
+
+                if end {
+                    break Ok(data);
+                }
+            }
+            Err(e) => break Err(e),
+        }
+    };
+
+    // Close even if the read failed
+    conn.cli.close_blob(&mut handle)?;
+
+    res
+}
+
+/// Get the handle of a blob not yet closed
+fn opened<H>(handle: &mut Option<H>) -> Result<&mut H, FbError> {
+    handle
+        .as_mut()
+        .ok_or_else(|| FbError::from("The blob is already closed"))
+}
+
+fn to_io_error(e: FbError) -> io::Error {
+    match e {
+        FbError::Io(e) => e,
+        e => io::Error::other(e),
+    }
+}
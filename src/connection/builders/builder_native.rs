@@ -220,6 +220,13 @@ where
         self.conn_conf.no_db_triggers = true;
         self
     }
+
+    /// Fetch the blob columns as a `BlobRef`, without the data.
+    /// The data is read with `Transaction::read_blob`
+    pub fn lazy_blobs(&mut self) -> &mut Self {
+        self.conn_conf.lazy_blobs = true;
+        self
+    }
 }
 
 impl<A, B> NativeConnectionBuilder<A, B> {
@@ -112,6 +112,13 @@ impl PureRustConnectionBuilder {
         self
     }
 
+    /// Fetch the blob columns as a `BlobRef`, without the data.
+    /// The data is read with `Transaction::read_blob`
+    pub fn lazy_blobs(&mut self) -> &mut Self {
+        self.0.lazy_blobs = true;
+        self
+    }
+
     /// Default transaction configuration
     pub fn transaction(&mut self, conf: TransactionConfiguration) -> &mut Self {
         self.0.transaction_conf = conf;
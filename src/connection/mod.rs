@@ -4,8 +4,9 @@
 //! Connection functions
 //!
 use rsfbclient_core::{
-    Dialect, FbError, FirebirdClient, FirebirdClientDbEvents, FirebirdClientDbOps, FromRow,
-    IntoParams, TransactionConfiguration,
+    add_warnings, expand_list_params, Dialect, FbError, FbWarning, FirebirdClient,
+    FirebirdClientDbEvents, FirebirdClientDbOps, FromRow, IntoParams, TrOp,
+    TransactionConfiguration,
 };
 use std::{marker, mem};
 
@@ -61,6 +62,7 @@ pub struct ConnectionConfiguration<A> {
     attachment_conf: A,
     dialect: Dialect,
     no_db_triggers: bool,
+    lazy_blobs: bool,
     stmt_cache_size: usize,
     transaction_conf: TransactionConfiguration,
 }
@@ -73,6 +75,7 @@ impl<A: Default> Default for ConnectionConfiguration<A> {
             stmt_cache_size: 20,
             transaction_conf: TransactionConfiguration::default(),
             no_db_triggers: false,
+            lazy_blobs: false,
         }
     }
 }
@@ -101,6 +104,9 @@ pub struct Connection<C: FirebirdClient> {
 
     /// Default configuration for new transactions
     pub(crate) def_confs_tr: TransactionConfiguration,
+
+    /// Warnings returned by the server, not yet taken
+    pub(crate) warnings: Vec<FbWarning>,
 }
 
 impl<C: FirebirdClient> Connection<C> {
@@ -111,6 +117,7 @@ impl<C: FirebirdClient> Connection<C> {
     ) -> Result<Connection<C>, FbError> {
         let handle =
             cli.attach_database(&conf.attachment_conf, conf.dialect, conf.no_db_triggers)?;
+        cli.set_lazy_blobs(conf.lazy_blobs);
         let stmt_cache = StmtCache::new(conf.stmt_cache_size);
 
         Ok(Connection {
@@ -121,6 +128,7 @@ impl<C: FirebirdClient> Connection<C> {
             in_transaction: false,
             cli,
             def_confs_tr: conf.transaction_conf,
+            warnings: Vec::new(),
         })
     }
 
@@ -131,6 +139,7 @@ impl<C: FirebirdClient> Connection<C> {
         page_size: Option<u32>,
     ) -> Result<Connection<C>, FbError> {
         let handle = cli.create_database(&conf.attachment_conf, page_size, conf.dialect)?;
+        cli.set_lazy_blobs(conf.lazy_blobs);
         let stmt_cache = StmtCache::new(conf.stmt_cache_size);
 
         Ok(Connection {
@@ -141,6 +150,7 @@ impl<C: FirebirdClient> Connection<C> {
             in_transaction: false,
             cli,
             def_confs_tr: conf.transaction_conf,
+            warnings: Vec::new(),
         })
     }
 
@@ -173,7 +183,10 @@ impl<C: FirebirdClient> Connection<C> {
     }
 
     /// Run a closure with a transaction, if the closure returns an error
-    /// and the default transaction is not active, the transaction will rollback, else it will be committed
+    /// and the default transaction is not active, the transaction will rollback, else it will be committed.
+    ///
+    /// If the default transaction is active (started by [`begin_transaction`][`Connection::begin_transaction`]),
+    /// the closure runs inside a savepoint, so an error undoes only the changes made by the closure
     pub fn with_transaction<T, F>(&mut self, closure: F) -> Result<T, FbError>
     where
         F: FnOnce(&mut Transaction<C>) -> Result<T, FbError>,
@@ -182,12 +195,32 @@ impl<C: FirebirdClient> Connection<C> {
     }
 
     /// Run a closure with a transaction, if the closure returns an error
-    /// and the default transaction is not active, the transaction will rollback, else it will be committed
+    /// and the default transaction is not active, the transaction will rollback, else it will be committed.
+    ///
+    /// If the default transaction is active (started by [`begin_transaction`][`Connection::begin_transaction`]),
+    /// the closure runs inside a savepoint, so an error undoes only the changes made by the closure
     pub fn with_transaction_config<T, F>(
         &mut self,
         confs: TransactionConfiguration,
         closure: F,
     ) -> Result<T, FbError>
+    where
+        F: FnOnce(&mut Transaction<C>) -> Result<T, FbError>,
+    {
+        if self.in_transaction {
+            self.auto_transaction(confs, |tr| tr.with_savepoint(closure))
+        } else {
+            self.auto_transaction(confs, closure)
+        }
+    }
+
+    /// Run a closure with the default transaction, committing or rolling back
+    /// it after the closure returns, unless the default transaction is active
+    fn auto_transaction<T, F>(
+        &mut self,
+        confs: TransactionConfiguration,
+        closure: F,
+    ) -> Result<T, FbError>
     where
         F: FnOnce(&mut Transaction<C>) -> Result<T, FbError>,
     {
@@ -283,6 +316,42 @@ impl<C: FirebirdClient> Connection<C> {
 
         self.use_transaction(self.def_confs_tr, |tr| tr.rollback_retaining())
     }
+
+    /// Ids of the transactions in limbo in the database: prepared for the two-phase
+    /// commit, but not committed or rolled back, like when the application coordinating
+    /// a [`DistributedTransaction`][`crate::DistributedTransaction`] crashed.
+    ///
+    /// The message passed to the prepare is stored in the `RDB$TRANSACTIONS` table.
+    /// See the [`DistributedTransaction`][`crate::DistributedTransaction`] docs about
+    /// when to commit or rollback them
+    pub fn limbo_transactions(&mut self) -> Result<Vec<u64>, FbError> {
+        self.cli.limbo_transactions(&mut self.handle)
+    }
+
+    /// Commit a transaction in limbo
+    pub fn commit_limbo_transaction(&mut self, id: u64) -> Result<(), FbError> {
+        let mut tr = self.cli.reconnect_transaction(&mut self.handle, id)?;
+
+        self.cli.transaction_operation(&mut tr, TrOp::Commit)
+    }
+
+    /// Rollback a transaction in limbo
+    pub fn rollback_limbo_transaction(&mut self, id: u64) -> Result<(), FbError> {
+        let mut tr = self.cli.reconnect_transaction(&mut self.handle, id)?;
+
+        self.cli.transaction_operation(&mut tr, TrOp::Rollback)
+    }
+
+    /// Take the warnings returned by the server along with the successful
+    /// operations of this connection, since the last call.
+    ///
+    /// Only the last `MAX_WARNINGS` are kept until taken
+    pub fn take_warnings(&mut self) -> Vec<FbWarning> {
+        let mut warnings = mem::take(&mut self.warnings);
+        add_warnings(&mut warnings, self.cli.take_warnings());
+
+        warnings
+    }
 }
 
 impl<C: FirebirdClient> Connection<C>
@@ -373,10 +442,10 @@ where
         R: FromRow + 'static,
     {
         let stmt_cache_data = self.use_transaction(self.def_confs_tr, |tr| {
-            let params = params.to_params();
+            let (sql, params) = expand_list_params(sql, params.try_to_params()?)?;
 
             // Get a statement from the cache
-            let mut stmt_cache_data = StmtCache::get_or_prepare(tr, sql, params.named())?;
+            let mut stmt_cache_data = StmtCache::get_or_prepare(tr, &sql, params.named())?;
 
             match stmt_cache_data.stmt.query(tr.conn, &mut tr.data, params) {
                 Ok(_) => Ok(stmt_cache_data),
@@ -411,11 +480,11 @@ where
     where
         P: IntoParams,
     {
-        let params = params.to_params();
+        let (sql, params) = expand_list_params(sql, params.try_to_params()?)?;
 
-        self.with_transaction(|tr| {
+        self.auto_transaction(self.def_confs_tr, |tr| {
             // Get a statement from the cache
-            let mut stmt_cache_data = StmtCache::get_or_prepare(tr, sql, params.named())?;
+            let mut stmt_cache_data = StmtCache::get_or_prepare(tr, &sql, params.named())?;
 
             // Do not return now in case of error, because we need to return the statement to the cache
             let res = stmt_cache_data.stmt.execute(tr.conn, &mut tr.data, params);
@@ -432,11 +501,11 @@ where
         P: IntoParams,
         R: FromRow + 'static,
     {
-        let params = params.to_params();
+        let (sql, params) = expand_list_params(sql, params.try_to_params()?)?;
 
-        self.with_transaction(|tr| {
+        self.auto_transaction(self.def_confs_tr, |tr| {
             // Get a statement from the cache
-            let mut stmt_cache_data = StmtCache::get_or_prepare(tr, sql, params.named())?;
+            let mut stmt_cache_data = StmtCache::get_or_prepare(tr, &sql, params.named())?;
 
             // Do not return now in case of error, because we need to return the statement to the cache
             let res = stmt_cache_data.stmt.execute2(tr.conn, &mut tr.data, params);
@@ -4,7 +4,7 @@
 //!
 
 use crate::{Connection, Execute, FbError, FromRow, IntoParams, Queryable};
-use rsfbclient_core::TransactionConfiguration;
+use rsfbclient_core::{FbWarning, TransactionConfiguration};
 
 #[cfg(feature = "linking")]
 use rsfbclient_native::DynLink;
@@ -177,6 +177,58 @@ impl SimpleConnection {
         }
     }
 
+    /// Ids of the transactions in limbo in the database: prepared for the two-phase
+    /// commit, but not committed or rolled back.
+    ///
+    /// The message passed to the prepare is stored in the `RDB$TRANSACTIONS` table
+    pub fn limbo_transactions(&mut self) -> Result<Vec<u64>, FbError> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeConnectionContainer::NativeDynLink(c) => c.limbo_transactions(),
+            #[cfg(feature = "dynamic_loading")]
+            TypeConnectionContainer::NativeDynLoad(c) => c.limbo_transactions(),
+            #[cfg(feature = "pure_rust")]
+            TypeConnectionContainer::PureRust(c) => c.limbo_transactions(),
+        }
+    }
+
+    /// Commit a transaction in limbo
+    pub fn commit_limbo_transaction(&mut self, id: u64) -> Result<(), FbError> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeConnectionContainer::NativeDynLink(c) => c.commit_limbo_transaction(id),
+            #[cfg(feature = "dynamic_loading")]
+            TypeConnectionContainer::NativeDynLoad(c) => c.commit_limbo_transaction(id),
+            #[cfg(feature = "pure_rust")]
+            TypeConnectionContainer::PureRust(c) => c.commit_limbo_transaction(id),
+        }
+    }
+
+    /// Rollback a transaction in limbo
+    pub fn rollback_limbo_transaction(&mut self, id: u64) -> Result<(), FbError> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeConnectionContainer::NativeDynLink(c) => c.rollback_limbo_transaction(id),
+            #[cfg(feature = "dynamic_loading")]
+            TypeConnectionContainer::NativeDynLoad(c) => c.rollback_limbo_transaction(id),
+            #[cfg(feature = "pure_rust")]
+            TypeConnectionContainer::PureRust(c) => c.rollback_limbo_transaction(id),
+        }
+    }
+
+    /// Take the warnings returned by the server along with the successful
+    /// operations of this connection, since the last call
+    pub fn take_warnings(&mut self) -> Vec<FbWarning> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeConnectionContainer::NativeDynLink(c) => c.take_warnings(),
+            #[cfg(feature = "dynamic_loading")]
+            TypeConnectionContainer::NativeDynLoad(c) => c.take_warnings(),
+            #[cfg(feature = "pure_rust")]
+            TypeConnectionContainer::PureRust(c) => c.take_warnings(),
+        }
+    }
+
     /// Wait for an event to be posted on database
     pub fn wait_for_event(&mut self, name: String) -> Result<(), FbError> {
         match &mut self.inner {
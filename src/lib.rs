@@ -71,6 +71,14 @@
 //! Can find the official `fbclient` native library by path at runtime, does not need the library at compiletime. Useful when you need to build in a system without a firebird client installed.
 //! ### `pure_rust`
 //! Uses a pure rust implementation of the firebird wire protocol, does not need the native library at all. Useful for cross-compilation and allow a single binary to be deployed without needing to install the firebird client.
+//! ### `bigdecimal`, `rust_decimal`
+//! Allow the `BigDecimal` and `rust_decimal::Decimal` types to be used in the parameters and columns.
+//! ### `uuid`
+//! Allow the `Uuid` type to be used in the parameters and columns, stored as `CHAR(16) CHARACTER SET OCTETS`. Also reads the text form returned by `UUID_TO_CHAR`.
+//! ### `time`
+//! Allow the `time` crate date and time types to be used in the parameters and columns, as an alternative to chrono.
+//! ### `serde`
+//! Allow the rows to be deserialized to any `serde::Deserialize` type, with `Row::deserialize` or `Deserialized<T>` as the row type, converted to json with `Row::to_json` or `serde_json::Value` as the row type, and any `serde::Serialize` struct to be used as named parameters with `Serialized(value)`.
 
 #[cfg(test)]
 #[macro_use]
@@ -83,9 +91,10 @@ pub mod prelude {
         TrDataAccessMode, TrIsolationLevel, TrLockResolution, TrRecordVersion,
         TransactionConfiguration,
     };
-    pub use rsfbclient_derive::IntoParams;
+    pub use rsfbclient_derive::{FromColumn, FromRow, IntoParam, IntoParams};
 }
 
+mod blob;
 mod connection;
 mod events;
 mod query;
@@ -94,19 +103,28 @@ mod transaction;
 mod utils;
 
 pub use crate::{
+    blob::Blob,
     connection::{Connection, ConnectionConfiguration, FirebirdClientFactory, SimpleConnection},
     events::RemoteEventsManager,
     query::{Execute, Queryable},
     statement::Statement,
-    transaction::{SimpleTransaction, Transaction},
+    transaction::{DistributedTransaction, SimpleTransaction, Transaction},
     utils::{EngineVersion, SystemInfos},
 };
 pub use rsfbclient_core::{
-    Column, ColumnToVal, Dialect, FbError, FromRow, IntoParam, IntoParams, ParamsType, Row, SqlType,
+    BlobId, BlobRef, Column, ColumnMeta, ColumnToVal, DecFloat, Dialect, FbError, FbWarning,
+    FromRow, IntoNamedParams, IntoParam, IntoParams, IntoPositionalParams, Numeric, ParamList,
+    ParamsType, Row, SqlType, StatusArg, StatusEntry, StatusVector,
 };
 
+#[cfg(feature = "serde")]
+pub use rsfbclient_core::{serialize_params, Deserialized, Serialized};
+
+pub use rsfbclient_core::array::{ArrayBound, SqlArray};
+pub use rsfbclient_core::date_time::OffsetTime;
+
 #[doc(hidden)]
-pub use rsfbclient_core::{charset, Charset};
+pub use rsfbclient_core::{charset, Charset, NamedColumns};
 
 //builders are behind feature gates inside this module
 pub use crate::connection::builders;
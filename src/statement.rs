@@ -9,7 +9,8 @@ use crate::{
     Connection,
 };
 use rsfbclient_core::{
-    Column, FbError, FirebirdClient, FreeStmtOp, FromRow, IntoParams, NamedParams, StmtType,
+    add_warnings, Column, ColumnMeta, FbError, FbWarning, FirebirdClient, FreeStmtOp, FromRow,
+    IntoParams, NamedParams, SqlType, StmtType,
 };
 
 pub struct Statement<'c, 't, C: FirebirdClient> {
@@ -60,6 +61,24 @@ where
             _marker: Default::default(),
         })
     }
+
+    /// Warnings returned by the server in the last execution
+    /// of the statement, including the fetch of the rows
+    pub fn warnings(&self) -> &[FbWarning] {
+        &self.data.warnings
+    }
+
+    /// Description of the columns returned by the statement,
+    /// empty if it doesn't return any row
+    pub fn columns(&mut self) -> Result<Vec<ColumnMeta>, FbError> {
+        self.data.columns(self.tr.conn)
+    }
+
+    /// Description of the input parameters of the statement,
+    /// in the order of the placeholders in the sql
+    pub fn params(&mut self) -> Result<Vec<ColumnMeta>, FbError> {
+        self.data.params(self.tr.conn)
+    }
 }
 
 impl<C> Drop for Statement<'_, '_, C>
@@ -120,6 +139,8 @@ pub struct StatementData<C: FirebirdClient> {
     pub(crate) handle: C::StmtHandle,
     pub(crate) stmt_type: StmtType,
     named_params: NamedParams,
+    /// Warnings returned in the last execution
+    pub(crate) warnings: Vec<FbWarning>,
 }
 
 impl<C: FirebirdClient> StatementData<C>
@@ -148,6 +169,7 @@ where
             stmt_type,
             handle,
             named_params,
+            warnings: Vec::new(),
         })
     }
 
@@ -163,12 +185,14 @@ where
     where
         T: IntoParams,
     {
-        let rows_count = conn.cli.execute(
-            &mut conn.handle,
-            &mut tr.handle,
-            &mut self.handle,
-            self.named_params.convert(params)?,
-        )?;
+        self.reset_warnings(conn);
+
+        let params = self.convert_params(params)?;
+        let rows_count =
+            conn.cli
+                .execute(&mut conn.handle, &mut tr.handle, &mut self.handle, params);
+        self.collect_warnings(conn);
+        let rows_count = rows_count?;
 
         if self.stmt_type == StmtType::Select {
             // Close the cursor, as it will not be used
@@ -190,12 +214,15 @@ where
     where
         T: IntoParams,
     {
-        conn.cli.execute2(
-            &mut conn.handle,
-            &mut tr.handle,
-            &mut self.handle,
-            self.named_params.convert(params)?,
-        )
+        self.reset_warnings(conn);
+
+        let params = self.convert_params(params)?;
+        let row = conn
+            .cli
+            .execute2(&mut conn.handle, &mut tr.handle, &mut self.handle, params);
+        self.collect_warnings(conn);
+
+        row
     }
 
     /// Execute the current statement
@@ -211,12 +238,15 @@ where
     where
         T: IntoParams,
     {
-        conn.cli.execute(
-            &mut conn.handle,
-            &mut tr.handle,
-            &mut self.handle,
-            self.named_params.convert(params)?,
-        )
+        self.reset_warnings(conn);
+
+        let params = self.convert_params(params)?;
+        let rows_count =
+            conn.cli
+                .execute(&mut conn.handle, &mut tr.handle, &mut self.handle, params);
+        self.collect_warnings(conn);
+
+        rows_count
     }
 
     /// Fetch for the next row, needs to be called after `query`
@@ -225,8 +255,55 @@ where
         conn: &mut Connection<C>,
         tr: &mut TransactionData<C>,
     ) -> Result<Option<Vec<Column>>, FbError> {
-        conn.cli
-            .fetch(&mut conn.handle, &mut tr.handle, &mut self.handle)
+        let row = conn
+            .cli
+            .fetch(&mut conn.handle, &mut tr.handle, &mut self.handle);
+        self.collect_warnings(conn);
+
+        row
+    }
+
+    /// Description of the columns returned by the statement
+    pub fn columns(&mut self, conn: &mut Connection<C>) -> Result<Vec<ColumnMeta>, FbError> {
+        conn.cli.describe_columns(&mut self.handle)
+    }
+
+    /// Description of the input parameters of the statement
+    pub fn params(&mut self, conn: &mut Connection<C>) -> Result<Vec<ColumnMeta>, FbError> {
+        conn.cli.describe_params(&mut self.handle)
+    }
+
+    /// Convert the parameters to the positional ones expected by the statement
+    fn convert_params<T>(&self, params: T) -> Result<Vec<SqlType>, FbError>
+    where
+        T: IntoParams,
+    {
+        let params = self.named_params.convert(params)?;
+
+        if params.iter().any(|p| matches!(p, SqlType::List(_))) {
+            return Err(FbError::from(
+                "List parameters are only supported in the `Queryable` and `Execute` functions, as the statement depends on the list length",
+            ));
+        }
+
+        Ok(params)
+    }
+
+    /// Clear the warnings of the last execution. The ones returned by the
+    /// previous operations in the connection are not from this statement
+    fn reset_warnings(&mut self, conn: &mut Connection<C>) {
+        self.warnings.clear();
+        add_warnings(&mut conn.warnings, conn.cli.take_warnings());
+    }
+
+    /// Move the warnings returned by the client to the statement and the connection
+    fn collect_warnings(&mut self, conn: &mut Connection<C>) {
+        let warnings = conn.cli.take_warnings();
+
+        if !warnings.is_empty() {
+            add_warnings(&mut self.warnings, warnings.iter().cloned());
+            add_warnings(&mut conn.warnings, warnings);
+        }
     }
 
     /// Closes the statement cursor, if it was open
@@ -247,7 +324,7 @@ static TABLE_COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU3
 #[cfg(test)]
 mk_tests_default! {
     use crate::{prelude::*, Connection, Row};
-    use rsfbclient_core::FirebirdClient;
+    use rsfbclient_core::{ibase, FirebirdClient};
 
     #[test]
     fn new_api_select() {
@@ -402,6 +479,49 @@ mk_tests_default! {
         conn.close().expect("error on close the connection");
     }
 
+    #[test]
+    fn describe() {
+        let (mut conn, table) = setup();
+
+        conn.with_transaction(|tr| {
+            let mut stmt = tr.prepare(
+                &format!("select id, name as product, 1 from {} where quantity > ? and name = ?", table),
+                false,
+            )?;
+
+            let columns = stmt.columns()?;
+            assert_eq!(3, columns.len());
+            assert_eq!(table.to_uppercase(), columns[0].relation);
+            assert_eq!("ID", columns[0].field);
+            assert_eq!(ibase::SQL_LONG, columns[0].sql_type);
+            assert!(columns[0].nullable);
+            assert_eq!("PRODUCT", columns[1].alias);
+            assert_eq!("NAME", columns[1].field);
+            assert_eq!(ibase::SQL_VARYING, columns[1].sql_type);
+            assert_eq!("", columns[2].relation);
+
+            let params = stmt.params()?;
+            assert_eq!(2, params.len());
+            assert_eq!(ibase::SQL_LONG, params[0].sql_type);
+            assert_eq!(ibase::SQL_VARYING, params[1].sql_type);
+
+            Ok(())
+        })
+        .expect("Error describing the select");
+
+        conn.with_transaction(|tr| {
+            let mut stmt = tr.prepare(&format!("delete from {}", table), false)?;
+
+            assert!(stmt.columns()?.is_empty());
+            assert!(stmt.params()?.is_empty());
+
+            Ok(())
+        })
+        .expect("Error describing the delete");
+
+        conn.close().expect("error on close the connection");
+    }
+
     // #[test]
     // fn immediate_insert() {
     //     let (mut conn, table) = setup();
@@ -5,8 +5,9 @@
 //!
 
 mk_tests_default! {
-    use crate::{prelude::*, FbError, SqlType, EngineVersion, SystemInfos};
-    use chrono::{NaiveDate, NaiveTime};
+    use crate::{prelude::*, ArrayBound, DecFloat, FbError, Numeric, ParamList, SqlArray, SqlType, EngineVersion, SystemInfos, Transaction};
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+    use rsfbclient_core::date_time::OffsetTime;
     use rand::{distributions::Standard, Rng};
 
     #[test]
@@ -185,6 +186,98 @@ mk_tests_default! {
         Ok(())
     }
 
+    #[test]
+    fn struct_params_attributes() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        #[derive(Clone, IntoParams)]
+        struct Audit {
+            pub user_name: String,
+        }
+
+        #[allow(dead_code)]
+        #[derive(Clone, IntoParams)]
+        struct Customer {
+            #[rsfbclient(rename = "CUST_ID")]
+            pub id: i32,
+            #[rsfbclient(skip)]
+            pub cache: Vec<u8>,
+            #[rsfbclient(flatten)]
+            pub audit: Audit,
+        }
+
+        let customer = Customer {
+            id: 10,
+            cache: vec![],
+            audit: Audit { user_name: "abc".to_string() },
+        };
+
+        let res: Option<(i32,)> = conn.query_first(
+            "select 1 from rdb$database where 10 = :CUST_ID and 'abc' = :user_name",
+            customer,
+        )?;
+        assert!(res.is_some());
+
+        #[allow(dead_code)]
+        #[derive(Clone, IntoParams)]
+        struct Positional(i32, #[rsfbclient(skip)] bool, String);
+
+        let res: Option<(i32,)> = conn.query_first(
+            "select 1 from rdb$database where 10 = ? and 'abc' = ?",
+            Positional(10, true, "abc".to_string()),
+        )?;
+        assert!(res.is_some());
+
+        #[derive(Clone, IntoParams)]
+        struct Range(#[rsfbclient(flatten)] (i32, i32), String);
+
+        let res: Option<(i32,)> = conn.query_first(
+            "select 1 from rdb$database where 10 between ? and ? and 'abc' = ?",
+            Range((1, 20), "abc".to_string()),
+        )?;
+        assert!(res.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn derive_column_values() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        #[derive(Debug, Clone, Copy, PartialEq, IntoParam, FromColumn)]
+        struct CustomerId(i64);
+
+        #[derive(Debug, Clone, Copy, PartialEq, IntoParam, FromColumn)]
+        enum Priority {
+            Low = 1,
+            High = 10,
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, IntoParam, FromColumn)]
+        enum Status {
+            #[rsfbclient(text = "A")]
+            Active,
+            #[rsfbclient(text = "I")]
+            Inactive,
+        }
+
+        let row: Option<(CustomerId, Priority, Status)> = conn.query_first(
+            "select cast(? as bigint), cast(? as int), cast(? as char(3)) from rdb$database",
+            (CustomerId(42), Priority::High, Status::Inactive),
+        )?;
+        assert_eq!(Some((CustomerId(42), Priority::High, Status::Inactive)), row);
+
+        let res: Result<Option<(Priority,)>, _> =
+            conn.query_first("select 5 from rdb$database", ());
+        assert!(res.is_err());
+
+        let res: Result<Option<(Status,)>, _> =
+            conn.query_first("select 'X' from rdb$database", ());
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn boolean() -> Result<(), FbError> {
         let mut conn = cbuilder().connect()?;
@@ -312,6 +405,59 @@ mk_tests_default! {
         )?;
         assert!(val_exists.is_some());
 
+        // Compared without casting to timestamp
+        let (count,): (i32,) = conn
+            .query_first(
+                "select count(*) from pdates where a = ? or c = ?",
+                (
+                    NaiveDate::from_ymd_opt(2009, 8, 7).unwrap(),
+                    NaiveTime::from_hms_opt(11, 22, 33).unwrap(),
+                ),
+            )?
+            .unwrap();
+        assert_eq!(2, count);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dates_with_time_zone() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        if conn.server_engine()? <= EngineVersion::V3 {
+            return Ok(());
+        }
+
+        conn.execute("DROP TABLE PDATESTZ", ()).ok();
+        conn.execute(
+            "CREATE TABLE PDATESTZ (ref char(1), a timestamp with time zone, b time with time zone)",
+            (),
+        )?;
+
+        let dt = DateTime::parse_from_rfc3339("2009-08-07T11:32:25+02:00").unwrap();
+        conn.execute("insert into pdatestz (ref, a) values ('a', ?)", (dt,))?;
+        let val_exists: Option<(i16,)> = conn.query_first(
+            "select 1 from pdatestz where ref = 'a' and a = '2009-08-07 11:32:25 +02:00'",
+            (),
+        )?;
+        assert!(val_exists.is_some());
+
+        let (a,): (DateTime<FixedOffset>,) = conn
+            .query_first("select a from pdatestz where ref = 'a'", ())?
+            .unwrap();
+        assert_eq!(dt, a);
+
+        let time = OffsetTime::new(
+            NaiveTime::from_hms_opt(11, 22, 33).unwrap(),
+            FixedOffset::west_opt(3 * 3600).unwrap(),
+        );
+        conn.execute("insert into pdatestz (ref, b) values ('b', ?)", (time,))?;
+        let val_exists: Option<(i16,)> = conn.query_first(
+            "select 1 from pdatestz where ref = 'b' and b = '11:22:33 -03:00'",
+            (),
+        )?;
+        assert!(val_exists.is_some());
+
         Ok(())
     }
 
@@ -371,6 +517,176 @@ mk_tests_default! {
         Ok(())
     }
 
+    #[test]
+    fn exact_fixed_points() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE PEXACTFIXEDS", ()).ok();
+        conn.execute(
+            "CREATE TABLE PEXACTFIXEDS (ref char(1), a numeric(15, 2), b decimal(18, 4))",
+            (),
+        )?;
+
+        conn.execute(
+            "insert into pexactfixeds (ref, a) values ('a', ?)",
+            (Numeric::new(1000000000001, -2),),
+        )?;
+        let val_exists: Option<(i16,)> = conn.query_first(
+            "select 1 from pexactfixeds where ref = 'a' and a = 10000000000.01",
+            (),
+        )?;
+        assert!(val_exists.is_some());
+
+        conn.execute(
+            "insert into pexactfixeds (ref, b) values ('b', ?)",
+            ("-0.0003".parse::<Numeric>()?,),
+        )?;
+        let (b,): (Numeric,) =
+            conn.query_first("select b from pexactfixeds where ref = 'b'", ())?
+                .unwrap();
+        assert_eq!(Numeric::new(-3, -4), b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn int128() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        if conn.server_engine()? <= EngineVersion::V3 {
+            return Ok(());
+        }
+
+        conn.execute("DROP TABLE PINT128", ()).ok();
+        conn.execute(
+            "CREATE TABLE PINT128 (ref char(1), a int128, b numeric(38, 4))",
+            (),
+        )?;
+
+        conn.execute("insert into pint128 (ref, a) values ('a', ?)", (i128::MAX,))?;
+        let val_exists: Option<(i16,)> = conn.query_first(
+            "select 1 from pint128 where ref = 'a' and a = 170141183460469231731687303715884105727",
+            (),
+        )?;
+        assert!(val_exists.is_some());
+
+        conn.execute(
+            "insert into pint128 (ref, b) values ('b', ?)",
+            (Numeric::new(-12345678901234567890123456789, -4),),
+        )?;
+        let val_exists: Option<(i16,)> = conn.query_first(
+            "select 1 from pint128 where ref = 'b' and b = -1234567890123456789012345.6789",
+            (),
+        )?;
+        assert!(val_exists.is_some());
+
+        let (b,): (Numeric,) = conn
+            .query_first("select b from pint128 where ref = 'b'", ())?
+            .unwrap();
+        assert_eq!(Numeric::new(-12345678901234567890123456789, -4), b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn arrays() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE PARRAYS", ()).ok();
+        conn.execute(
+            "CREATE TABLE PARRAYS (ref char(1), a int[2, 0:2], b varchar(10)[4], c numeric(9, 2)[3])",
+            (),
+        )?;
+
+        conn.execute(
+            "insert into parrays (ref, a, b, c) values ('a', ?, ?, ?)",
+            (
+                SqlArray::from_values(vec![1, 2, 3, 4, 5, 6]),
+                SqlArray::from_values(vec!["abc", "ãé"]),
+                SqlArray::from_values(vec![Numeric::new(125, -2), Numeric::new(-3, 0), Numeric::new(0, 0)]),
+            ),
+        )?;
+
+        let (a, b, c): (SqlArray, SqlArray, SqlArray) = conn
+            .query_first("select a, b, c from parrays where ref = 'a'", ())?
+            .unwrap();
+
+        assert_eq!(vec![ArrayBound::new(1, 2), ArrayBound::new(0, 2)], a.bounds);
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], a.to_vec::<i32>()?);
+
+        // Only the first elements were written
+        assert_eq!(vec![ArrayBound::new(1, 4)], b.bounds);
+        assert_eq!(vec!["abc", "ãé", "", ""], b.to_vec::<String>()?);
+
+        assert_eq!(
+            vec![Numeric::new(125, -2), Numeric::new(-300, -2), Numeric::new(0, -2)],
+            c.to_vec::<Numeric>()?
+        );
+
+        // Slice of the first row of a
+        conn.execute(
+            "update parrays set a = ? where ref = 'a'",
+            (SqlArray::with_bounds(
+                vec![ArrayBound::new(1, 1), ArrayBound::new(0, 2)],
+                vec![7.into(), 8.into(), 9.into()],
+            )?,),
+        )?;
+
+        let (a,): (SqlArray,) = conn
+            .query_first("select a from parrays where ref = 'a'", ())?
+            .unwrap();
+        assert_eq!(vec![7, 8, 9, 0, 0, 0], a.to_vec::<i32>()?);
+
+        // Wrong element count
+        let res = conn.execute(
+            "update parrays set a = ? where ref = 'a'",
+            (SqlArray::from_values(vec![1, 2, 3]),),
+        );
+        assert!(res.is_err());
+
+        conn.execute("insert into parrays (ref) values ('b')", ())?;
+        let (a,): (Option<SqlArray>,) = conn
+            .query_first("select a from parrays where ref = 'b'", ())?
+            .unwrap();
+        assert!(a.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn decfloat() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        if conn.server_engine()? <= EngineVersion::V3 {
+            return Ok(());
+        }
+
+        conn.execute("DROP TABLE PDECFLOATS", ()).ok();
+        conn.execute(
+            "CREATE TABLE PDECFLOATS (ref char(1), a decfloat(16), b decfloat(34))",
+            (),
+        )?;
+
+        conn.execute(
+            "insert into pdecfloats (ref, a) values ('a', ?)",
+            ("-7.50".parse::<DecFloat>()?,),
+        )?;
+        let val_exists: Option<(i16,)> = conn.query_first(
+            "select 1 from pdecfloats where ref = 'a' and a = -7.5",
+            (),
+        )?;
+        assert!(val_exists.is_some());
+
+        let big: DecFloat = "1.234567890123456789012345678901234E-6000".parse()?;
+        conn.execute("insert into pdecfloats (ref, b) values ('b', ?)", (big,))?;
+        let (b,): (DecFloat,) = conn
+            .query_first("select b from pdecfloats where ref = 'b'", ())?
+            .unwrap();
+        assert_eq!(big, b);
+
+        Ok(())
+    }
+
     #[test]
     fn float_points() -> Result<(), FbError> {
         let mut conn = cbuilder().connect()?;
@@ -479,4 +795,225 @@ mk_tests_default! {
 
         Ok(())
     }
+
+    #[test]
+    fn blob_stream() -> Result<(), FbError> {
+        use std::io::{Read, Seek, SeekFrom, Write};
+
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE PBLOBSTREAM", ()).ok();
+        conn.execute("CREATE TABLE PBLOBSTREAM (id int, content blob sub_type 0)", ())?;
+
+        let data: Vec<u8> = rand::thread_rng().sample_iter::<u8, _>(Standard).take(150_000).collect();
+
+        let mut tr = Transaction::new(&mut conn, TransactionConfiguration::default())?;
+
+        let mut blob = tr.create_blob()?;
+        for chunk in data.chunks(40_000) {
+            blob.write_all(chunk).map_err(FbError::from)?;
+        }
+        let id = blob.close()?;
+
+        {
+            let mut blob = tr.open_blob(id)?;
+            assert_eq!(data.len() as u64, blob.len()?);
+
+            let mut read = vec![];
+            blob.read_to_end(&mut read).map_err(FbError::from)?;
+            assert_eq!(data, read);
+
+            let mut tail = [0; 10];
+            blob.seek(SeekFrom::End(-10)).map_err(FbError::from)?;
+            blob.read_exact(&mut tail).map_err(FbError::from)?;
+            assert_eq!(&data[data.len() - 10..], &tail);
+
+            let mut middle = [0; 100];
+            blob.seek(SeekFrom::Start(70_000)).map_err(FbError::from)?;
+            blob.read_exact(&mut middle).map_err(FbError::from)?;
+            assert_eq!(&data[70_000..70_100], &middle[..]);
+        }
+
+        tr.execute("insert into pblobstream (id, content) values (1, ?)", (id,))?;
+        tr.commit()?;
+
+        let (content,): (Vec<u8>,) = conn
+            .query_first("select content from pblobstream where id = 1", ())?
+            .unwrap();
+        assert_eq!(data, content);
+
+        Ok(())
+    }
+
+    #[test]
+    fn coerce_to_described_types() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE PCOERCE", ()).ok();
+        conn.execute(
+            "CREATE TABLE PCOERCE (id smallint, amount numeric(10, 2), dt date)",
+            (),
+        )?;
+
+        conn.execute(
+            "insert into pcoerce (id, amount, dt) values (?, ?, ?)",
+            ("12", 10, "2020-02-01"),
+        )?;
+
+        let (id, amount, dt): (i16, f64, NaiveDate) = conn
+            .query_first("select id, amount, dt from pcoerce where amount = ?", ("10.00",))?
+            .expect("Coerced value not found");
+        assert_eq!(12, id);
+        assert_eq!(10.0, amount);
+        assert_eq!(NaiveDate::from_ymd_opt(2020, 2, 1).unwrap(), dt);
+
+        let err = conn
+            .execute(
+                "insert into pcoerce (id, amount, dt) values (?, ?, ?)",
+                (1, "abc", "2020-02-01"),
+            )
+            .expect_err("Invalid numeric must return an error")
+            .to_string();
+        assert!(err.contains("parameter 2"), "{}", err);
+
+        let err = conn
+            .execute(
+                "insert into pcoerce (id, amount, dt) values (?, ?, ?)",
+                (100_000, 1, "2020-02-01"),
+            )
+            .expect_err("Value out of range must return an error")
+            .to_string();
+        assert!(err.contains("parameter 1") && err.contains("SMALLINT"), "{}", err);
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_params() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE PLIST", ()).ok();
+        conn.execute("CREATE TABLE PLIST (id int, name varchar(10))", ())?;
+        for (id, name) in [(1, "a"), (2, "b"), (3, "c"), (4, "a")] {
+            conn.execute("insert into plist (id, name) values (?, ?)", (id, name))?;
+        }
+
+        let ids: Vec<(i32,)> = conn.query(
+            "select id from plist where id in (?) and name <> ? order by id",
+            (ParamList(vec![1, 2, 4]), "b"),
+        )?;
+        assert_eq!(vec![(1,), (4,)], ids);
+
+        // Other length, other statement
+        let ids: Vec<(i32,)> = conn.query(
+            "select id from plist where id in (?) order by id",
+            (ParamList(vec![3]),),
+        )?;
+        assert_eq!(vec![(3,)], ids);
+
+        let ids: Vec<(i32,)> = conn.query(
+            "select id from plist where id in (?)",
+            (ParamList::<i32>(vec![]),),
+        )?;
+        assert!(ids.is_empty());
+
+        #[derive(IntoParams)]
+        struct Filter {
+            ids: ParamList<i32>,
+            names: ParamList<&'static str>,
+        }
+
+        let ids: Vec<(i32,)> = conn.query(
+            "select id from plist where id in (:ids) or name in (:names) order by id",
+            Filter {
+                ids: vec![2].into(),
+                names: vec!["a", "c"].into(),
+            },
+        )?;
+        assert_eq!(vec![(1,), (2,), (3,), (4,)], ids);
+
+        let mut tr = Transaction::new(&mut conn, TransactionConfiguration::default())?;
+        let mut stmt = tr.prepare("select id from plist where id in (?)", false)?;
+        assert!(stmt.execute((ParamList(vec![1, 2]),)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn octets() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE POCTETS", ()).ok();
+        conn.execute("CREATE TABLE POCTETS (id CHAR(4) CHARACTER SET OCTETS)", ())?;
+
+        let bin: Vec<u8> = vec![0, 0xFF, 0xC3, 0x28];
+        conn.execute("insert into poctets (id) values (?)", (bin.clone(),))?;
+
+        let row: Option<(Vec<u8>,)> = conn.query_first("select id from poctets where id = ?", (bin.clone(),))?;
+        assert_eq!(Some((bin,)), row);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn uuids() -> Result<(), FbError> {
+        use uuid::Uuid;
+
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE PUUID", ()).ok();
+        conn.execute("CREATE TABLE PUUID (id CHAR(16) CHARACTER SET OCTETS)", ())?;
+
+        let uuid = Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+        conn.execute("insert into puuid (id) values (?)", (uuid,))?;
+        conn.execute("insert into puuid (id) values (GEN_UUID())", ())?;
+
+        let (id, text): (Uuid, Uuid) = conn
+            .query_first("select id, UUID_TO_CHAR(id) from puuid where id = CHAR_TO_UUID(?)", (uuid.hyphenated().to_string(),))?
+            .expect("No row returned");
+        assert_eq!(uuid, id);
+        assert_eq!(uuid, text);
+
+        let ids: Vec<(Uuid,)> = conn.query("select id from puuid", ())?;
+        assert_eq!(2, ids.len());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn rust_decimals() -> Result<(), FbError> {
+        use rust_decimal::Decimal;
+
+        let mut conn = cbuilder().connect()?;
+
+        let d: Decimal = "-1234.56".parse().unwrap();
+        let (value, text): (Decimal, Decimal) = conn
+            .query_first("select cast(? as numeric(10, 2)), cast(? as varchar(20)) from rdb$database", (d, d))?
+            .expect("No row returned");
+        assert_eq!(d, value);
+        assert_eq!(d, text);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_crate_dates() -> Result<(), FbError> {
+        use time::{Date, Month, PrimitiveDateTime, Time};
+
+        let mut conn = cbuilder().connect()?;
+
+        let date = Date::from_calendar_date(2021, Month::March, 4).unwrap();
+        let dt = PrimitiveDateTime::new(date, Time::from_hms_milli(10, 20, 30, 100).unwrap());
+
+        let (d, ts): (Date, PrimitiveDateTime) = conn
+            .query_first("select cast(? as date), cast(? as timestamp) from rdb$database", (date, dt))?
+            .expect("No row returned");
+        assert_eq!(date, d);
+        assert_eq!(dt, ts);
+
+        Ok(())
+    }
 }
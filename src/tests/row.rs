@@ -5,8 +5,9 @@
 //!
 
 mk_tests_default! {
-    use crate::{prelude::*, FbError, Row, EngineVersion, SystemInfos};
-    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use crate::{prelude::*, DecFloat, FbError, Numeric, Row, SqlType, EngineVersion, SystemInfos};
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+    use rsfbclient_core::date_time::OffsetTime;
     use rsfbclient_core::ColumnToVal;
     use std::str;
     use rand::{distributions::Standard, Rng};
@@ -169,6 +170,35 @@ mk_tests_default! {
         Ok(())
     }
 
+    #[test]
+    fn blob_other_subtypes() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE RBLOBSUBTYPES", ()).ok();
+        conn.execute("CREATE TABLE RBLOBSUBTYPES (custom blob sub_type -5, txt blob sub_type 1 character set win1252)", ())?;
+
+        conn.execute("insert into rblobsubtypes (custom, txt) values (x'00ff10', 'abc äbç')", ())?;
+
+        let row: Row = conn.query_first("select custom, txt from rblobsubtypes", ())?.unwrap();
+
+        assert_eq!(-5, row.cols[0].sub_type);
+        assert_eq!(vec![0x00, 0xff, 0x10], row.get::<Vec<u8>>(0)?);
+
+        assert_eq!(1, row.cols[1].sub_type);
+        assert_eq!("abc äbç", row.get::<String>(1)?);
+
+        // Internal subtype, blr of a system procedure or trigger
+        let blr: Option<(Vec<u8>,)> = conn.query_first(
+            "select first 1 rdb$trigger_blr from rdb$triggers where rdb$trigger_blr is not null",
+            (),
+        )?;
+        if let Some((blr,)) = blr {
+            assert!(!blr.is_empty());
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn blob_text_subtype() -> Result<(), FbError> {
         let mut conn = cbuilder().connect()?;
@@ -210,6 +240,37 @@ mk_tests_default! {
         Ok(())
     }
 
+    #[test]
+    fn lazy_blobs() -> Result<(), FbError> {
+        let mut conn = cbuilder().lazy_blobs().connect()?;
+
+        conn.execute("DROP TABLE RLAZYBLOB", ()).ok();
+        conn.execute("CREATE TABLE RLAZYBLOB (id int, bin blob sub_type 0, txt blob sub_type 1)", ())?;
+
+        conn.execute("insert into rlazyblob (id, bin, txt) values (1, ?, ?)", (vec![1_u8, 2, 3], "abc äbç"))?;
+        conn.execute("insert into rlazyblob (id, bin, txt) values (2, null, null)", ())?;
+
+        // Only the ids are fetched into the rows
+        let rows: Vec<(i32, Option<crate::BlobRef>, Option<crate::BlobRef>)> = conn.query("select id, bin, txt from rlazyblob order by id", ())?;
+        assert!(rows[1].1.is_none() && rows[1].2.is_none());
+
+        let (bin, txt) = conn.with_transaction(|tr| {
+            let bin: Vec<u8> = tr.read_blob(rows[0].1.as_ref().unwrap())?;
+            let txt: String = tr.read_blob(rows[0].2.as_ref().unwrap())?;
+
+            Ok((bin, txt))
+        })?;
+        assert_eq!(vec![1, 2, 3], bin);
+        assert_eq!("abc äbç", txt);
+
+        let row: Row = conn.query_first("select bin from rlazyblob where id = 1", ())?.unwrap();
+        assert!(matches!(row.cols[0].value, SqlType::LazyBlob(_)));
+        assert!(row.get::<crate::BlobId>(0).is_ok());
+        assert!(row.get::<Vec<u8>>(0).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn big_blob_text() -> Result<(), FbError> {
         let mut conn = cbuilder().connect()?;
@@ -257,6 +318,68 @@ mk_tests_default! {
         assert_eq!(NaiveDate::from_ymd(2010, 10, 10).and_hms(10, 10, 10), b);
         assert_eq!(NaiveTime::from_hms(10, 10, 10), c);
 
+        let row: Row = conn
+            .query_first(
+                "select cast('2010-10-10' as date), cast('10:10:10' as TIME) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert!(matches!(row.cols[0].value, SqlType::Date(_)));
+        assert!(matches!(row.cols[1].value, SqlType::Time(_)));
+
+        // Dates still can be read as timestamps
+        let (a,): (NaiveDateTime,) = conn
+            .query_first("select cast('2010-10-10' as date) from rdb$database", ())?
+            .unwrap();
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2010, 10, 10)
+                .unwrap()
+                .and_time(NaiveTime::MIN),
+            a
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn dates_with_time_zone() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        if conn.server_engine()? <= EngineVersion::V3 {
+            return Ok(());
+        }
+
+        let (a, b): (DateTime<FixedOffset>, DateTime<FixedOffset>) = conn
+            .query_first(
+                "select cast('2010-10-10 10:10:10.1234 -03:00' as timestamp with time zone), cast('2010-01-10 10:10:10 America/Sao_Paulo' as timestamp with time zone) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2010-10-10T10:10:10.1234-03:00").unwrap(),
+            a
+        );
+        // Region time zones are returned with the offset of the date
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2010-01-10T10:10:10-02:00").unwrap(),
+            b
+        );
+        assert_eq!(-2 * 3600, b.offset().local_minus_utc());
+
+        let (a,): (OffsetTime,) = conn
+            .query_first(
+                "select cast('10:10:10 +05:30' as time with time zone) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!(
+            OffsetTime::new(
+                NaiveTime::from_hms_opt(10, 10, 10).unwrap(),
+                FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap()
+            ),
+            a
+        );
+
         Ok(())
     }
 
@@ -320,6 +443,110 @@ mk_tests_default! {
         Ok(())
     }
 
+    #[test]
+    fn exact_fixed_points() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        let (a, b, c): (Numeric, Numeric, Numeric) = conn
+            .query_first(
+                "select cast(0.1 as numeric(4, 2)), cast(-2358.35321 as decimal(9, 5)), cast(92233720368547.7580 as numeric(18, 4)) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!(Numeric::new(10, -2), a);
+        assert_eq!(Numeric::new(-235835321, -5), b);
+        assert_eq!(Numeric::new(922337203685477580, -4), c);
+
+        let (a, b): (String, String) = conn
+            .query_first(
+                "select cast(0.1 as numeric(4, 2)), cast(-2358.35321 as decimal(9, 5)) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!("0.10", a);
+        assert_eq!("-2358.35321", b);
+
+        let row: Row = conn
+            .query_first("select cast(19.99 as numeric(15, 2)) from rdb$database", ())?
+            .unwrap();
+        assert!(matches!(
+            row.cols[0].value,
+            SqlType::Decimal(Numeric { value: 1999, scale: -2 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn int128() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        if conn.server_engine()? <= EngineVersion::V3 {
+            return Ok(());
+        }
+
+        let (a, b, c): (i128, i128, i128) = conn
+            .query_first(
+                "select cast(170141183460469231731687303715884105727 as int128), cast(-170141183460469231731687303715884105728 as int128), cast(-7 as int128) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!(i128::MAX, a);
+        assert_eq!(i128::MIN, b);
+        assert_eq!(-7, c);
+
+        let (a,): (String,) = conn
+            .query_first("select cast(-12345678901234567890123 as int128) from rdb$database", ())?
+            .unwrap();
+        assert_eq!("-12345678901234567890123", a);
+
+        let (a, b): (Numeric, String) = conn
+            .query_first(
+                "select cast(12345678901234567890123456.789 as numeric(38, 3)), cast(-0.05 as decimal(38, 2)) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!(Numeric::new(12345678901234567890123456789, -3), a);
+        assert_eq!("-0.05", b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decfloat() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        if conn.server_engine()? <= EngineVersion::V3 {
+            return Ok(());
+        }
+
+        let (a, b): (DecFloat, DecFloat) = conn
+            .query_first(
+                "select cast(-7.50 as decfloat(16)), cast(1234567890123456789012345678901234E+6000 as decfloat(34)) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!(DecFloat::new(-750, -2), a);
+        assert_eq!(DecFloat::new(1234567890123456789012345678901234, 6000), b);
+
+        let (a, b, c): (String, String, f64) = conn
+            .query_first(
+                "select cast(123.45 as decfloat(16)), cast('-Infinity' as decfloat(34)), cast(0.5 as decfloat(34)) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!("123.45", a);
+        assert_eq!("-Infinity", b);
+        assert_eq!(0.5, c);
+
+        let (a,): (DecFloat,) = conn
+            .query_first("select cast('NaN' as decfloat(16)) from rdb$database", ())?
+            .unwrap();
+        assert!(a.is_nan());
+
+        Ok(())
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     fn float_points() -> Result<(), FbError> {
@@ -412,6 +639,38 @@ mk_tests_default! {
         Ok(())
     }
 
+    #[test]
+    fn checked_ints() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        let (a, b, c): (u8, u64, usize) = conn
+            .query_first(
+                "select cast(255 as smallint), cast(9223372036854775807 as bigint), cast(10.00 as numeric(10, 2)) from rdb$database",
+                (),
+            )?
+            .unwrap();
+        assert_eq!(255, a);
+        assert_eq!(i64::MAX as u64, b);
+        assert_eq!(10, c);
+
+        let res: Result<Option<(i16,)>, _> = conn.query_first(
+            "select cast(100000 as bigint) as big from rdb$database",
+            (),
+        );
+        let err = res.unwrap_err().to_string();
+        assert!(err.contains("BIG") && err.contains("i16"), "{}", err);
+
+        let res: Result<Option<(u32,)>, _> =
+            conn.query_first("select cast(-1 as int) from rdb$database", ());
+        assert!(res.is_err());
+
+        let res: Result<Option<(i64,)>, _> =
+            conn.query_first("select cast(1.5 as double precision) from rdb$database", ());
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn lots_of_columns() -> Result<(), FbError> {
         let mut conn = cbuilder().connect()?;
@@ -458,4 +717,118 @@ mk_tests_default! {
 
         Ok(())
     }
+
+    #[test]
+    fn derive_from_row() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        #[derive(Debug, PartialEq, FromRow)]
+        struct User {
+            id: i32,
+            #[rsfbclient(rename = "USER_NAME")]
+            name: String,
+            email: Option<String>,
+            #[rsfbclient(default)]
+            active: bool,
+        }
+
+        // Columns in other order and with other cases
+        let user: User = conn
+            .query_first(
+                "select cast(null as varchar(10)) as email, 'abc' as user_name, 10 as \"Id\" from rdb$database",
+                (),
+            )?
+            .expect("No row returned");
+
+        assert_eq!(
+            User {
+                id: 10,
+                name: "abc".to_string(),
+                email: None,
+                active: false,
+            },
+            user
+        );
+
+        let res: Result<Option<User>, _> =
+            conn.query_first("select 10 as id, 'abc' as name from rdb$database", ());
+        let err = res.expect_err("Missing column must return an error").to_string();
+        assert!(err.contains("USER_NAME"), "{}", err);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_by_name() -> Result<(), FbError> {
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE RGETBYNAME", ()).ok();
+        conn.execute(
+            "CREATE TABLE RGETBYNAME (ID INT NOT NULL, NAME VARCHAR(10) CHARACTER SET UTF8)",
+            (),
+        )?;
+        conn.execute("INSERT INTO RGETBYNAME (ID, NAME) VALUES (1, 'abc')", ())?;
+
+        let row: Row = conn
+            .query_first("SELECT r.id, r.name AS alias FROM RGETBYNAME r", ())?
+            .expect("No row returned");
+
+        assert_eq!(1, row.get_by_name::<i32>("id")?);
+        assert_eq!(1, row.get_by_name::<i32>("RGETBYNAME.ID")?);
+        assert_eq!("abc", row.get_by_name::<String>("Alias")?);
+        assert_eq!("abc", row.get_by_name::<String>("rgetbyname.name")?);
+        assert!(row.get_by_name::<i32>("OTHER").is_err());
+
+        let id = row.cols[0].meta.as_ref().expect("No metadata");
+        assert_eq!("RGETBYNAME", id.relation);
+        assert_eq!("ID", id.field);
+        assert!(!id.nullable);
+
+        let name = row.cols[1].meta.as_ref().expect("No metadata");
+        assert_eq!("ALIAS", name.alias);
+        assert_eq!("NAME", name.field);
+        assert!(name.nullable);
+        assert_eq!(40, name.length);
+        assert_eq!(Some(4), name.charset_id);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_rows() -> Result<(), FbError> {
+        use crate::{Deserialized, Serialized};
+        use std::collections::HashMap;
+
+        let mut conn = cbuilder().connect()?;
+
+        conn.execute("DROP TABLE RSERDE", ()).ok();
+        conn.execute(
+            "CREATE TABLE RSERDE (ID INT NOT NULL, NAME VARCHAR(10), VAL NUMERIC(10, 2))",
+            (),
+        )?;
+
+        let mut params = HashMap::new();
+        params.insert("id", SqlType::Integer(1));
+        params.insert("name", SqlType::Text("abc".to_string()));
+        params.insert("val", SqlType::Text("10.5".to_string()));
+        conn.execute(
+            "INSERT INTO RSERDE (ID, NAME, VAL) VALUES (:id, :name, :val)",
+            Serialized(params),
+        )?;
+
+        let rows: Vec<Deserialized<(i32, String, f64)>> =
+            conn.query("SELECT ID, NAME, VAL FROM RSERDE", ())?;
+        assert_eq!(vec![Deserialized((1, "abc".to_string(), 10.5))], rows);
+
+        let row: Row = conn
+            .query_first("SELECT ID, NAME, VAL FROM RSERDE", ())?
+            .expect("No row returned");
+        let json = row.to_json()?;
+        assert_eq!(json["ID"], 1);
+        assert_eq!(json["NAME"], "abc");
+        assert_eq!(json["VAL"], "10.50");
+
+        Ok(())
+    }
 }
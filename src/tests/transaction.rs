@@ -5,7 +5,7 @@
 //!
 
 mk_tests_default! {
-    use crate::{FbError, Connection, Transaction, query::Queryable, EngineVersion, SystemInfos};
+    use crate::{FbError, Connection, DistributedTransaction, Transaction, query::{Execute, Queryable}, EngineVersion, SystemInfos};
     use rsfbclient_core::*;
 
     macro_rules! recreate_tbl_fmtstring{
@@ -266,4 +266,190 @@ mk_tests_default! {
         conn2.close()?;
         teardown(conn, TABLE_NAME)
     }
+
+    #[test]
+    fn savepoints() -> Result<(), FbError> {
+        const TABLE_NAME: &str = "RSFBCLIENT_TEST_TRANS9";
+
+        let mut conn = cbuilder().connect()?;
+        setup(&mut conn, TABLE_NAME)?;
+
+        let mut transaction = Transaction::new(&mut conn, TransactionConfiguration::default())?;
+        transaction.execute(&format!("insert into {} (id) values (1)", TABLE_NAME), ())?;
+        transaction.savepoint("SP1")?;
+        transaction.execute(&format!("insert into {} (id) values (2)", TABLE_NAME), ())?;
+        transaction.rollback_to_savepoint("SP1")?;
+        transaction.execute(&format!("insert into {} (id) values (3)", TABLE_NAME), ())?;
+        transaction.release_savepoint("SP1")?;
+
+        assert!(transaction.savepoint("SP1; DELETE FROM RDB$DATABASE").is_err());
+
+        let ids: Vec<(i32,)> = transaction.query(&format!("select id from {} order by id", TABLE_NAME), ())?;
+        assert_eq!(vec![(1,), (3,)], ids);
+
+        transaction.commit()?;
+
+        teardown(conn, TABLE_NAME)
+    }
+
+    #[test]
+    fn nested_savepoints() -> Result<(), FbError> {
+        const TABLE_NAME: &str = "RSFBCLIENT_TEST_TRANS10";
+
+        let mut conn = cbuilder().connect()?;
+        setup(&mut conn, TABLE_NAME)?;
+
+        let insert = format!("insert into {} (id) values (?)", TABLE_NAME);
+
+        let mut transaction = Transaction::new(&mut conn, TransactionConfiguration::default())?;
+        transaction.with_savepoint(|tr| {
+            tr.execute(&insert, (1,))?;
+
+            // Skips only the bad record
+            let res = tr.with_savepoint(|tr| {
+                tr.execute(&insert, (2,))?;
+                tr.execute(&insert, (1,))
+            });
+            assert!(res.is_err());
+
+            tr.with_savepoint(|tr| tr.execute(&insert, (3,)))
+        })?;
+
+        let ids: Vec<(i32,)> = transaction.query(&format!("select id from {} order by id", TABLE_NAME), ())?;
+        assert_eq!(vec![(1,), (3,)], ids);
+
+        transaction.commit()?;
+
+        teardown(conn, TABLE_NAME)
+    }
+
+    #[test]
+    fn with_transaction_in_transaction() -> Result<(), FbError> {
+        const TABLE_NAME: &str = "RSFBCLIENT_TEST_TRANS11";
+
+        let mut conn = cbuilder().connect()?;
+        setup(&mut conn, TABLE_NAME)?;
+
+        let insert = format!("insert into {} (id) values (?)", TABLE_NAME);
+
+        conn.begin_transaction()?;
+        conn.execute(&insert, (1,))?;
+
+        let res = conn.with_transaction(|tr| {
+            tr.execute(&insert, (2,))?;
+            tr.execute(&insert, (1,))
+        });
+        assert!(res.is_err());
+
+        conn.with_transaction(|tr| tr.execute(&insert, (3,)))?;
+        conn.commit()?;
+
+        let ids: Vec<(i32,)> = conn.query(&format!("select id from {} order by id", TABLE_NAME), ())?;
+        assert_eq!(vec![(1,), (3,)], ids);
+
+        teardown(conn, TABLE_NAME)
+    }
+
+    #[test]
+    fn distributed_commit() -> Result<(), FbError> {
+        const TABLE_NAME: &str = "RSFBCLIENT_TEST_TRANS12";
+
+        let mut conn1 = cbuilder().connect()?;
+        let mut conn2 = cbuilder().connect()?;
+        setup(&mut conn1, TABLE_NAME)?;
+
+        let insert = format!("insert into {} (id) values (?)", TABLE_NAME);
+
+        let mut tr = DistributedTransaction::new([&mut conn1, &mut conn2], TransactionConfiguration::default())?;
+        tr.get(0).unwrap().execute(&insert, (1,))?;
+        tr.get(1).unwrap().execute(&insert, (2,))?;
+        tr.prepare_commit(b"distributed_commit")?;
+        assert!(tr.is_prepared());
+        tr.commit()?;
+
+        let ids: Vec<(i32,)> = conn2.query(&format!("select id from {} order by id", TABLE_NAME), ())?;
+        assert_eq!(vec![(1,), (2,)], ids);
+
+        // Prepared with the default message
+        let mut tr = DistributedTransaction::new([&mut conn1, &mut conn2], TransactionConfiguration::default())?;
+        tr.get(0).unwrap().execute(&insert, (3,))?;
+
+        let tr_ids = tr
+            .transactions()
+            .iter_mut()
+            .map(|tr| tr.query_first("select current_transaction from rdb$database", ()))
+            .collect::<Result<Vec<Option<(i64,)>>, _>>()?;
+
+        let message = String::from_utf8(tr.prepare_message()?).unwrap();
+        assert!(message.starts_with("rsfbclient "));
+        for tr_id in tr_ids {
+            assert!(message.contains(&format!(":{}", tr_id.unwrap().0)));
+        }
+        assert_ne!(message.as_bytes(), &tr.prepare_message()?[..]);
+
+        tr.commit()?;
+
+        let ids: Vec<(i32,)> = conn2.query(&format!("select id from {} order by id", TABLE_NAME), ())?;
+        assert_eq!(vec![(1,), (2,), (3,)], ids);
+
+        conn2.close()?;
+        teardown(conn1, TABLE_NAME)
+    }
+
+    #[test]
+    fn distributed_rollback() -> Result<(), FbError> {
+        const TABLE_NAME: &str = "RSFBCLIENT_TEST_TRANS13";
+
+        let mut conn1 = cbuilder().connect()?;
+        let mut conn2 = cbuilder().connect()?;
+        setup(&mut conn1, TABLE_NAME)?;
+
+        let insert = format!("insert into {} (id) values (?)", TABLE_NAME);
+
+        let mut tr = DistributedTransaction::new([&mut conn1, &mut conn2], TransactionConfiguration::default())?;
+        tr.get(0).unwrap().execute(&insert, (1,))?;
+        tr.get(1).unwrap().execute(&insert, (2,))?;
+        tr.prepare_commit(&[])?;
+        tr.rollback()?;
+
+        let ids: Vec<(i32,)> = conn2.query(&format!("select id from {} order by id", TABLE_NAME), ())?;
+        assert!(ids.is_empty());
+
+        conn2.close()?;
+        teardown(conn1, TABLE_NAME)
+    }
+
+    #[test]
+    fn limbo_recovery() -> Result<(), FbError> {
+        const TABLE_NAME: &str = "RSFBCLIENT_TEST_TRANS14";
+
+        let mut conn = cbuilder().connect()?;
+        setup(&mut conn, TABLE_NAME)?;
+
+        let before = conn.limbo_transactions()?;
+
+        // Prepared, but the connection is dropped before the commit
+        {
+            let mut conn = cbuilder().connect()?;
+            let mut tr = Transaction::new(&mut conn, TransactionConfiguration::default())?;
+            tr.execute(&format!("insert into {} (id) values (1)", TABLE_NAME), ())?;
+            tr.prepare_commit(b"limbo_recovery")?;
+            std::mem::forget(tr);
+        }
+
+        let limbo: Vec<u64> = conn
+            .limbo_transactions()?
+            .into_iter()
+            .filter(|id| !before.contains(id))
+            .collect();
+        assert_eq!(1, limbo.len());
+
+        conn.commit_limbo_transaction(limbo[0])?;
+        assert!(!conn.limbo_transactions()?.contains(&limbo[0]));
+
+        let ids: Vec<(i32,)> = conn.query(&format!("select id from {}", TABLE_NAME), ())?;
+        assert_eq!(vec![(1,)], ids);
+
+        teardown(conn, TABLE_NAME)
+    }
 }
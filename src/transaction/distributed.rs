@@ -0,0 +1,184 @@
+//!
+//! Rust Firebird Client
+//!
+//! Transaction spanning multiple databases, with the two-phase commit
+//!
+
+use rsfbclient_core::{FbError, FirebirdClient, TransactionConfiguration};
+use std::{
+    mem, process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::Transaction;
+use crate::{connection::Connection, Queryable};
+
+/// A transaction spanning the databases of multiple connections, committed
+/// with the two-phase commit: first the transaction is prepared in all the
+/// databases, and only if all succeed it is committed in them.
+///
+/// If dropped without a commit, the transaction is rolled back in all the databases.
+///
+/// # Recovery
+///
+/// If the application crashes between the prepare and the end of the commit, the
+/// transactions are left in limbo, listed by [`Connection::limbo_transactions`], and
+/// the prepare message can be read in the `RDB$TRANSACTION_DESCRIPTION` column of the
+/// `RDB$TRANSACTIONS` table. The default message (see [`prepare_message`][`Self::prepare_message`])
+/// identifies the distributed transaction and all its participants, so they can be found
+/// in each database. Then:
+///
+/// - If the transaction was committed in any of the participants, the commit
+///   had started, and the ones in limbo must be committed
+///   with [`Connection::commit_limbo_transaction`].
+/// - Otherwise, the prepare may not have finished in all of them, and the ones in
+///   limbo must be rolled back with [`Connection::rollback_limbo_transaction`].
+pub struct DistributedTransaction<'c, C>
+where
+    C: FirebirdClient,
+{
+    /// Transactions in each database, in the order of the connections
+    trs: Vec<Transaction<'c, C>>,
+
+    /// If the first phase of the commit was done
+    prepared: bool,
+}
+
+impl<'c, C: FirebirdClient> DistributedTransaction<'c, C> {
+    /// Start a new transaction in the databases of the connections
+    pub fn new<I>(conns: I, confs: TransactionConfiguration) -> Result<Self, FbError>
+    where
+        I: IntoIterator<Item = &'c mut Connection<C>>,
+    {
+        let trs = conns
+            .into_iter()
+            .map(|conn| Transaction::new(conn, confs))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if trs.is_empty() {
+            return Err("A distributed transaction needs at least one connection".into());
+        }
+
+        Ok(DistributedTransaction {
+            trs,
+            prepared: false,
+        })
+    }
+
+    /// Transaction in the database of the connection at the index,
+    /// in the order the connections were passed to `new`
+    pub fn get(&mut self, index: usize) -> Option<&mut Transaction<'c, C>> {
+        self.trs.get_mut(index)
+    }
+
+    /// Transactions in the databases, in the order the connections were passed to `new`
+    pub fn transactions(&mut self) -> &mut [Transaction<'c, C>] {
+        &mut self.trs
+    }
+
+    /// Returns true if the transaction was already prepared
+    pub fn is_prepared(&self) -> bool {
+        self.prepared
+    }
+
+    /// Default message for the prepare, identifying the distributed transaction
+    /// in all the databases, in the format
+    /// `rsfbclient <global id> <database 1>:<transaction id 1> <database 2>:<transaction id 2> ...`.
+    ///
+    /// A new global id is generated in every call
+    pub fn prepare_message(&mut self) -> Result<Vec<u8>, FbError> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+
+        let mut message = format!(
+            "rsfbclient {:x}-{:x}-{:x}",
+            time,
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+
+        for tr in &mut self.trs {
+            let (db_name, tr_id): (String, i64) = tr
+                .query_first(
+                    "select rdb$get_context('SYSTEM', 'DB_NAME'), current_transaction from rdb$database",
+                    (),
+                )?
+                .ok_or_else(|| FbError::from("Could not identify the transaction"))?;
+
+            message.push_str(&format!(" {}:{}", db_name, tr_id));
+        }
+
+        Ok(message.into_bytes())
+    }
+
+    /// Prepare the transaction in all the databases, the first phase
+    /// of the commit. If it fails, the transaction must be rolled back.
+    ///
+    /// The message is stored in all the databases with the transaction, to
+    /// help the recovery if it is left in limbo, so it must identify the
+    /// distributed transaction and its participants. See
+    /// [`prepare_message`][`Self::prepare_message`] for a default one
+    pub fn prepare_commit(&mut self, message: &[u8]) -> Result<(), FbError> {
+        if self.prepared {
+            return Err("The distributed transaction was already prepared".into());
+        }
+
+        for tr in &mut self.trs {
+            tr.prepare_commit(message)?;
+        }
+
+        self.prepared = true;
+
+        Ok(())
+    }
+
+    /// Commit the transaction in all the databases, preparing it first with the
+    /// default message if `prepare_commit` was not called. If the prepare fails,
+    /// the transaction is rolled back in all the databases.
+    ///
+    /// After a successful prepare, an error committing in one database does
+    /// not stop the commit in the others, and the failed transactions are
+    /// left in limbo to be resolved later. The first error is returned
+    pub fn commit(mut self) -> Result<(), FbError> {
+        if !self.prepared {
+            let message = self.prepare_message()?;
+            self.prepare_commit(&message)?;
+        }
+
+        let mut result = Ok(());
+
+        for tr in mem::take(&mut self.trs) {
+            // Not rolled back on errors, as the other databases may be committed already
+            let mut tr = mem::ManuallyDrop::new(tr);
+            let tr = &mut *tr;
+
+            if let Err(e) = tr.data.commit(tr.conn) {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Rollback the transaction in all the databases, returning the first error
+    pub fn rollback(mut self) -> Result<(), FbError> {
+        let mut result = Ok(());
+
+        for tr in mem::take(&mut self.trs) {
+            if let Err(e) = tr.rollback() {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+
+        result
+    }
+}
@@ -5,12 +5,17 @@
 //!
 
 use rsfbclient_core::{
-    FbError, FirebirdClient, FromRow, IntoParams, TrOp, TransactionConfiguration,
+    expand_list_params, ibase, BlobId, BlobRef, Column, ColumnToVal, FbError, FirebirdClient,
+    FromRow, IntoParams, TrOp, TransactionConfiguration,
 };
 use std::marker;
 use std::mem;
 
-use super::{connection::Connection, statement::Statement};
+use super::{
+    blob::{read_blob, Blob},
+    connection::Connection,
+    statement::Statement,
+};
 use crate::{
     connection::stmt_cache::StmtCache, connection::stmt_cache::StmtCacheData,
     statement::StatementData, Execute, Queryable,
@@ -22,6 +27,9 @@ pub use simple::SimpleTransaction;
 mod builder;
 pub use builder::{transaction_builder, TransactionConfigurationBuilder};
 
+mod distributed;
+pub use distributed::DistributedTransaction;
+
 pub struct Transaction<'c, C>
 where
     C: FirebirdClient,
@@ -64,6 +72,15 @@ impl<'c, C: FirebirdClient> Transaction<'c, C> {
         self.data.rollback_retaining(self.conn)
     }
 
+    /// Prepare the transaction for the two-phase commit. After this,
+    /// the transaction can only be committed or rolled back.
+    ///
+    /// The message is stored in the database with the transaction, to
+    /// help the recovery if it is left in limbo
+    pub fn prepare_commit(&mut self, message: &[u8]) -> Result<(), FbError> {
+        self.data.prepare_commit(self.conn, message)
+    }
+
     /// Rollback the current transaction changes
     pub fn rollback(mut self) -> Result<(), FbError> {
         let result = self.data.rollback(self.conn);
@@ -71,6 +88,63 @@ impl<'c, C: FirebirdClient> Transaction<'c, C> {
         result
     }
 
+    /// Create a savepoint, to allow undoing only the changes made after it with
+    /// `rollback_to_savepoint`. An existing savepoint with the same name is replaced.
+    ///
+    /// The name must be a regular identifier, like `SP1`
+    pub fn savepoint(&mut self, name: &str) -> Result<(), FbError> {
+        let sql = format!("SAVEPOINT {}", check_savepoint_name(name)?);
+        self.execute_immediate(&sql)
+    }
+
+    /// Release the savepoint and the ones created after it, keeping the changes
+    pub fn release_savepoint(&mut self, name: &str) -> Result<(), FbError> {
+        let sql = format!("RELEASE SAVEPOINT {}", check_savepoint_name(name)?);
+        self.execute_immediate(&sql)
+    }
+
+    /// Undo the changes made after the savepoint was created. The savepoint
+    /// is kept, and the ones created after it are released
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), FbError> {
+        let sql = format!("ROLLBACK TO SAVEPOINT {}", check_savepoint_name(name)?);
+        self.execute_immediate(&sql)
+    }
+
+    /// Run a closure inside a savepoint. If the closure returns an error,
+    /// only the changes made by the closure are undone, else they are kept.
+    /// In both cases the transaction stays active.
+    ///
+    /// Can be nested, each level using its own savepoint
+    pub fn with_savepoint<T, F>(&mut self, closure: F) -> Result<T, FbError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, FbError>,
+    {
+        let name = format!("RSFB_SP_{}", self.data.savepoints + 1);
+
+        self.savepoint(&name)?;
+        self.data.savepoints += 1;
+
+        let res = {
+            let guard = SavepointLevel(self);
+            closure(&mut *guard.0)
+        };
+
+        match res {
+            Ok(val) => {
+                self.release_savepoint(&name)?;
+                Ok(val)
+            }
+            Err(e) => {
+                // The error of the closure is returned, not the one undoing the changes
+                self.rollback_to_savepoint(&name)
+                    .and_then(|_| self.release_savepoint(&name))
+                    .ok();
+
+                Err(e)
+            }
+        }
+    }
+
     /// Execute the statement without returning any row
     pub fn execute_immediate(&mut self, sql: &str) -> Result<(), FbError> {
         self.data.execute_immediate(self.conn, sql)
@@ -84,6 +158,56 @@ impl<'c, C: FirebirdClient> Transaction<'c, C> {
     ) -> Result<Statement<'c, 't, C>, FbError> {
         Statement::prepare(self, sql, named_params)
     }
+
+    /// Open an existing blob to read the contents
+    pub fn open_blob<'t>(&'t mut self, id: BlobId) -> Result<Blob<'c, 't, C>, FbError> {
+        Blob::open(self, id)
+    }
+
+    /// Create a new blob to write the contents. Use the
+    /// id returned by `Blob::close` as a parameter to store it
+    pub fn create_blob<'t>(&'t mut self) -> Result<Blob<'c, 't, C>, FbError> {
+        Blob::create(self)
+    }
+
+    /// Read all the contents of a blob fetched with the lazy blobs
+    /// enabled, converting to a value as done with the columns
+    pub fn read_blob<T>(&mut self, blob: &BlobRef) -> Result<T, FbError>
+    where
+        Column: ColumnToVal<T>,
+    {
+        let data = read_blob(self.conn, &mut self.data, blob.id)?;
+
+        Column::new(String::new(), ibase::SQL_BLOB, blob.clone().decode(data)?).to_val()
+    }
+}
+
+/// Leaves the savepoint nesting level when dropped, even if the closure panics
+struct SavepointLevel<'a, 'c, C: FirebirdClient>(&'a mut Transaction<'c, C>);
+
+impl<C: FirebirdClient> Drop for SavepointLevel<'_, '_, C> {
+    fn drop(&mut self) {
+        self.0.data.savepoints -= 1;
+    }
+}
+
+/// Returns the name if it is a regular identifier, as the savepoint
+/// statements can't be prepared with the name as a parameter
+fn check_savepoint_name(name: &str) -> Result<&str, FbError> {
+    let mut chars = name.chars();
+
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if valid {
+        Ok(name)
+    } else {
+        Err(format!(
+            "Invalid savepoint name '{}', must be a regular identifier",
+            name
+        )
+        .into())
+    }
 }
 
 impl<'c, C: FirebirdClient> Drop for Transaction<'c, C> {
@@ -152,10 +276,10 @@ impl<'c, C: FirebirdClient> Queryable for Transaction<'c, C> {
         P: IntoParams,
         R: FromRow + 'static,
     {
-        let params = params.to_params();
+        let (sql, params) = expand_list_params(sql, params.try_to_params()?)?;
 
         // Get a statement from the cache
-        let mut stmt_cache_data = StmtCache::get_or_prepare(self, sql, params.named())?;
+        let mut stmt_cache_data = StmtCache::get_or_prepare(self, &sql, params.named())?;
 
         match stmt_cache_data
             .stmt
@@ -185,10 +309,10 @@ impl<C: FirebirdClient> Execute for Transaction<'_, C> {
     where
         P: IntoParams,
     {
-        let params = params.to_params();
+        let (sql, params) = expand_list_params(sql, params.try_to_params()?)?;
 
         // Get a statement from the cache
-        let mut stmt_cache_data = StmtCache::get_or_prepare(self, sql, params.named())?;
+        let mut stmt_cache_data = StmtCache::get_or_prepare(self, &sql, params.named())?;
 
         // Do not return now in case of error, because we need to return the statement to the cache
         let res = stmt_cache_data
@@ -206,10 +330,10 @@ impl<C: FirebirdClient> Execute for Transaction<'_, C> {
         P: IntoParams,
         R: FromRow + 'static,
     {
-        let params = params.to_params();
+        let (sql, params) = expand_list_params(sql, params.try_to_params()?)?;
 
         // Get a statement from the cache
-        let mut stmt_cache_data = StmtCache::get_or_prepare(self, sql, params.named())?;
+        let mut stmt_cache_data = StmtCache::get_or_prepare(self, &sql, params.named())?;
 
         // Do not return now in case of error, because we need to return the statement to the cache
         let res = stmt_cache_data
@@ -229,6 +353,9 @@ impl<C: FirebirdClient> Execute for Transaction<'_, C> {
 /// Needs to be closed calling `rollback` before dropping.
 pub struct TransactionData<C: FirebirdClient> {
     pub(crate) handle: C::TrHandle,
+
+    /// Number of active savepoints created by `with_savepoint`, used to name the next one
+    pub(crate) savepoints: usize,
 }
 
 impl<C: FirebirdClient> TransactionData<C>
@@ -239,7 +366,10 @@ where
     fn new(conn: &mut Connection<C>, confs: TransactionConfiguration) -> Result<Self, FbError> {
         let handle = conn.cli.begin_transaction(&mut conn.handle, confs)?;
 
-        Ok(Self { handle })
+        Ok(Self {
+            handle,
+            savepoints: 0,
+        })
     }
 
     /// Execute the statement without returning any row
@@ -266,6 +396,15 @@ where
             .transaction_operation(&mut self.handle, TrOp::RollbackRetaining)
     }
 
+    /// Prepare the transaction for the two-phase commit
+    pub fn prepare_commit(
+        &mut self,
+        conn: &mut Connection<C>,
+        message: &[u8],
+    ) -> Result<(), FbError> {
+        conn.cli.prepare_transaction(&mut self.handle, message)
+    }
+
     /// Rollback the transaction, invalidating it
     pub fn rollback(&mut self, conn: &mut Connection<C>) -> Result<(), FbError> {
         conn.cli
@@ -148,6 +148,22 @@ impl<'c> SimpleTransaction<'c> {
         }
     }
 
+    /// Prepare the transaction for the two-phase commit. After this,
+    /// the transaction can only be committed or rolled back.
+    ///
+    /// The message is stored in the database with the transaction, to
+    /// help the recovery if it is left in limbo
+    pub fn prepare_commit(&mut self, message: &[u8]) -> Result<(), FbError> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeTransactionContainer::NativeDynLink(tr) => tr.prepare_commit(message),
+            #[cfg(feature = "dynamic_loading")]
+            TypeTransactionContainer::NativeDynLoad(tr) => tr.prepare_commit(message),
+            #[cfg(feature = "pure_rust")]
+            TypeTransactionContainer::PureRust(tr) => tr.prepare_commit(message),
+        }
+    }
+
     /// Rollback the current transaction changes
     pub fn rollback(self) -> Result<(), FbError> {
         match self.inner {
@@ -160,6 +176,46 @@ impl<'c> SimpleTransaction<'c> {
         }
     }
 
+    /// Create a savepoint, to allow undoing only the changes made after it with
+    /// `rollback_to_savepoint`. An existing savepoint with the same name is replaced.
+    ///
+    /// The name must be a regular identifier, like `SP1`
+    pub fn savepoint(&mut self, name: &str) -> Result<(), FbError> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeTransactionContainer::NativeDynLink(tr) => tr.savepoint(name),
+            #[cfg(feature = "dynamic_loading")]
+            TypeTransactionContainer::NativeDynLoad(tr) => tr.savepoint(name),
+            #[cfg(feature = "pure_rust")]
+            TypeTransactionContainer::PureRust(tr) => tr.savepoint(name),
+        }
+    }
+
+    /// Release the savepoint and the ones created after it, keeping the changes
+    pub fn release_savepoint(&mut self, name: &str) -> Result<(), FbError> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeTransactionContainer::NativeDynLink(tr) => tr.release_savepoint(name),
+            #[cfg(feature = "dynamic_loading")]
+            TypeTransactionContainer::NativeDynLoad(tr) => tr.release_savepoint(name),
+            #[cfg(feature = "pure_rust")]
+            TypeTransactionContainer::PureRust(tr) => tr.release_savepoint(name),
+        }
+    }
+
+    /// Undo the changes made after the savepoint was created. The savepoint
+    /// is kept, and the ones created after it are released
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), FbError> {
+        match &mut self.inner {
+            #[cfg(feature = "linking")]
+            TypeTransactionContainer::NativeDynLink(tr) => tr.rollback_to_savepoint(name),
+            #[cfg(feature = "dynamic_loading")]
+            TypeTransactionContainer::NativeDynLoad(tr) => tr.rollback_to_savepoint(name),
+            #[cfg(feature = "pure_rust")]
+            TypeTransactionContainer::PureRust(tr) => tr.rollback_to_savepoint(name),
+        }
+    }
+
     /// Execute the statement without returning any row
     pub fn execute_immediate(&mut self, sql: &str) -> Result<(), FbError> {
         match &mut self.inner {